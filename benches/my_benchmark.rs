@@ -1,11 +1,23 @@
 use base94::*;
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+/// Returns `len` pseudo-random bytes, the same ones on every run so results stay comparable.
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0; len];
+    StdRng::seed_from_u64(94).fill_bytes(&mut bytes);
+    bytes
+}
+
+/// Returns a generator with a fixed seed, for random values other than byte strings.
+fn rng() -> StdRng {
+    StdRng::seed_from_u64(94)
+}
 
 fn criterion_benchmark(c: &mut Criterion) {
-    let random_bytes_1000 = (0..1000).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
-    let random_bytes_10000 = (0..10000)
-        .map(|_| rand::random::<u8>())
-        .collect::<Vec<u8>>();
+    let random_bytes_1000 = random_bytes(1000);
+    let random_bytes_10000 = random_bytes(10000);
 
     c.bench_function("encode_1000", |b| b.iter(|| encode(&random_bytes_1000, 94)));
     c.bench_function("encode_10000", |b| {
@@ -13,5 +25,241 @@ fn criterion_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, criterion_benchmark);
+fn chunked_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("chunked_vs_whole");
+    group.sample_size(10);
+
+    for size in [10_000, 1_000_000, 10_000_000] {
+        let data = random_bytes(size);
+
+        group.bench_with_input(
+            BenchmarkId::new("encode_chunked", size),
+            &data,
+            |b, data| b.iter(|| encode_chunked(data, 94, 32)),
+        );
+
+        // The divide-and-conquer conversion of the default backend makes the whole-number
+        // path subquadratic, so it is measured at every size.
+        group.bench_with_input(BenchmarkId::new("encode", size), &data, |b, data| {
+            b.iter(|| encode(data, 94))
+        });
+    }
+
+    group.finish();
+}
+
+fn encode_into_benchmark(c: &mut Criterion) {
+    let records = (0..1000)
+        .map(|i| {
+            let mut record = vec![0; 64];
+            StdRng::seed_from_u64(i).fill_bytes(&mut record);
+            record
+        })
+        .collect::<Vec<Vec<u8>>>();
+
+    let mut group = c.benchmark_group("encode_64_byte_records");
+    group.bench_function("encode", |b| {
+        b.iter(|| {
+            for record in &records {
+                criterion::black_box(encode(record, 94));
+            }
+        })
+    });
+    group.bench_function("encode_into", |b| {
+        let mut buffer = String::new();
+        b.iter(|| {
+            for record in &records {
+                encode_into(record, 94, &mut buffer);
+                criterion::black_box(&buffer);
+            }
+        })
+    });
+    group.finish();
+}
+
+fn validate_benchmark(c: &mut Criterion) {
+    let data = random_bytes(10000);
+    let encoded = encode(&data, 94);
+
+    let mut group = c.benchmark_group("validate_vs_decode_10000");
+    group.bench_function("validate", |b| b.iter(|| validate(&encoded, 94)));
+    group.bench_function("decode", |b| b.iter(|| decode(&encoded, 94)));
+    group.finish();
+}
+
+fn u64_benchmark(c: &mut Criterion) {
+    let n = rng().gen::<u64>();
+    let encoded = encode_u64(n, 94);
+
+    let mut group = c.benchmark_group("u64_vs_bytes");
+    group.bench_function("encode_u64", |b| b.iter(|| encode_u64(n, 94)));
+    group.bench_function("encode", |b| b.iter(|| encode(&n.to_le_bytes(), 94)));
+    group.bench_function("decode_u64", |b| b.iter(|| decode_u64(&encoded, 94)));
+    group.bench_function("decode", |b| b.iter(|| decode(&encoded, 94)));
+    group.finish();
+}
+
+/// 512 bytes is the largest input `Codec` converts with code specialized on its base when the
+/// default backend is used; larger ones take the same divide-and-conquer path as `encode`.
+fn codec_benchmark(c: &mut Criterion) {
+    let data = random_bytes(512);
+    let encoded = encode(&data, 94);
+    let codec = Codec::<94>::new();
+
+    let mut group = c.benchmark_group("codec_vs_runtime_base_512");
+    group.bench_function("Codec::<94>::encode", |b| b.iter(|| codec.encode(&data)));
+    group.bench_function("encode", |b| b.iter(|| encode(&data, 94)));
+    group.bench_function("Codec::<94>::decode", |b| b.iter(|| codec.decode(&encoded)));
+    group.bench_function("decode", |b| b.iter(|| decode(&encoded, 94)));
+    group.finish();
+}
+
+/// Decoding of 1 MB, with throughput in encoded bytes. `validate` only maps characters to
+/// digits, so it is the number that isolates the lookup-table path; the other functions add
+/// the radix conversion of the chunked or whole-number text on top of it.
+fn decode_1mb_benchmark(c: &mut Criterion) {
+    let data = random_bytes(1_000_000);
+    let chunked = encode_chunked(&data, 94, 32);
+    let whole = encode(&data, 94);
+
+    let mut group = c.benchmark_group("decode_1mb");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(chunked.len() as u64));
+    group.bench_function("validate", |b| b.iter(|| validate(&chunked, 94)));
+    group.bench_function("decode_chunked", |b| {
+        b.iter(|| decode_chunked(&chunked, 94, 32))
+    });
+    group.throughput(Throughput::Bytes(whole.len() as u64));
+    group.bench_function("decode", |b| b.iter(|| decode(&whole, 94)));
+    group.bench_function("decode_bytes", |b| {
+        b.iter(|| decode_bytes(whole.as_bytes(), 94))
+    });
+    group.finish();
+}
+
+/// Measures whichever radix backend the crate was built with. Run it once with the default
+/// features and once with `--no-default-features --features backend-native`; criterion
+/// reports the change between the two runs.
+fn backend_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("backend");
+    group.sample_size(10);
+
+    for size in [1_000, 100_000] {
+        let data = random_bytes(size);
+        let encoded = encode(&data, 94);
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::new("encode", size), &data, |b, data| {
+            b.iter(|| encode(data, 94))
+        });
+        group.bench_with_input(BenchmarkId::new("decode", size), &encoded, |b, encoded| {
+            b.iter(|| decode(encoded, 94))
+        });
+    }
+
+    group.finish();
+}
+
+fn short_input_benchmark(c: &mut Criterion) {
+    let data = rng().gen::<[u8; 16]>();
+    let encoded = encode(&data, 94);
+
+    let mut group = c.benchmark_group("16_byte_input");
+    group.bench_function("encode", |b| b.iter(|| encode(&data, 94)));
+    group.bench_function("decode", |b| b.iter(|| decode(&encoded, 94)));
+    group.finish();
+}
+
+/// Whole-number conversion of large inputs, where the divide-and-conquer algorithm of the
+/// default backend matters most. Each iteration takes seconds, so keep the sample count low.
+fn large_input_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("large_input");
+    group.sample_size(10);
+
+    for size in [1 << 20, 10 << 20] {
+        let data = random_bytes(size);
+        let encoded = encode(&data, 94);
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::new("encode", size), &data, |b, data| {
+            b.iter(|| encode(data, 94))
+        });
+        group.bench_with_input(BenchmarkId::new("decode", size), &encoded, |b, encoded| {
+            b.iter(|| decode(encoded, 94))
+        });
+    }
+
+    group.finish();
+}
+
+/// Chunked encoding of 50 MB on thread pools of growing size. Requires `--features parallel`.
+#[cfg(feature = "parallel")]
+fn parallel_benchmark(c: &mut Criterion) {
+    let data = random_bytes(50_000_000);
+    let encoded = encode_chunked(&data, 94, 32);
+
+    let mut group = c.benchmark_group("parallel_50mb");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    group.sample_size(10);
+
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let mut threads = 1;
+    while threads <= cores {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        group.bench_with_input(BenchmarkId::new("encode", threads), &data, |b, data| {
+            b.iter(|| pool.install(|| encode_chunked_parallel(data, 94, 32)))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("decode", threads),
+            &encoded,
+            |b, encoded| b.iter(|| pool.install(|| decode_chunked_parallel(encoded, 94, 32))),
+        );
+        threads *= 2;
+    }
+
+    group.finish();
+}
+
+/// Encoding and decoding throughput across bases and input sizes. Low bases produce much
+/// longer strings, and the growth from 1 KB to 1 MB shows how far conversion is from linear.
+fn bases_benchmark(c: &mut Criterion) {
+    for base in [2, 16, 64, 94] {
+        let mut group = c.benchmark_group(format!("base_{}", base));
+        group.sample_size(10);
+
+        for size in [1_000, 100_000, 1_000_000] {
+            let data = random_bytes(size);
+            let encoded = encode(&data, base);
+            group.throughput(Throughput::Bytes(size as u64));
+            group.bench_with_input(BenchmarkId::new("encode", size), &data, |b, data| {
+                b.iter(|| encode(data, base))
+            });
+            group.bench_with_input(BenchmarkId::new("decode", size), &encoded, |b, encoded| {
+                b.iter(|| decode(encoded, base))
+            });
+        }
+
+        group.finish();
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+fn parallel_benchmark(_: &mut Criterion) {}
+
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    chunked_benchmark,
+    encode_into_benchmark,
+    validate_benchmark,
+    u64_benchmark,
+    codec_benchmark,
+    decode_1mb_benchmark,
+    backend_benchmark,
+    short_input_benchmark,
+    large_input_benchmark,
+    bases_benchmark,
+    parallel_benchmark
+);
 criterion_main!(benches);
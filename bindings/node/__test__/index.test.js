@@ -0,0 +1,72 @@
+// Run with `npm test` after `npm run build` or `npm run build:debug`.
+
+const assert = require("node:assert/strict");
+const crypto = require("node:crypto");
+const { describe, it } = require("node:test");
+
+const { encode, decode, encodeAsync, decodeAsync } = require("..");
+
+const MAX_BASE = 94;
+
+/** Checks that `error` is a `Base94Error` with the given code and position. */
+function checkError(error, code, position) {
+  assert.ok(error instanceof Error);
+  assert.equal(error.code, code);
+  assert.equal(error.position, position);
+  return true;
+}
+
+describe("encode and decode", () => {
+  it("round-trips random data in every base", () => {
+    for (let base = 2; base <= MAX_BASE; base++) {
+      const data = crypto.randomBytes(100);
+      const encoded = encode(data, base);
+      assert.equal(typeof encoded, "string");
+      assert.deepEqual(decode(encoded, base), data);
+    }
+  });
+
+  it("matches the Rust output", () => {
+    assert.equal(encode(Buffer.from("Hello"), 94), "G8GP4b");
+    assert.ok(Buffer.isBuffer(decode("G8GP4b", 94)));
+    assert.deepEqual(decode("G8GP4b", 94), Buffer.from("Hello"));
+    assert.equal(encode(Buffer.alloc(0), 94), "");
+    assert.deepEqual(decode("", 94), Buffer.alloc(0));
+  });
+
+  it("throws errors with a code and position", () => {
+    assert.throws(() => decode("Hel lo", 94), (error) => {
+      assert.equal(error.message, "Invalid character ' ' at position 3");
+      return checkError(error, 1, 3);
+    });
+    assert.throws(() => decode("!!~", 10), (error) => checkError(error, 2, 2));
+  });
+
+  it("rejects bases outside 2 to 94", () => {
+    for (const base of [0, 1, 95]) {
+      assert.throws(() => encode(Buffer.from("Hi"), base), (error) => checkError(error, 3, undefined));
+      assert.throws(() => decode("!!", base), (error) => checkError(error, 3, undefined));
+    }
+  });
+});
+
+describe("encodeAsync and decodeAsync", () => {
+  it("round-trips a multi-megabyte payload without blocking the event loop", async () => {
+    const data = crypto.randomBytes(2 * 1024 * 1024);
+    let ticks = 0;
+    const timer = setInterval(() => ticks++, 1);
+    try {
+      const encoded = await encodeAsync(data, 94);
+      assert.deepEqual(await decodeAsync(encoded, 94), data);
+    } finally {
+      clearInterval(timer);
+    }
+    assert.ok(ticks > 0);
+  });
+
+  it("rejects with errors with a code and position", async () => {
+    await assert.rejects(decodeAsync("Hel lo", 94), (error) => checkError(error, 1, 3));
+    await assert.rejects(encodeAsync(Buffer.from("Hi"), 95), (error) => checkError(error, 3, undefined));
+    await assert.rejects(decodeAsync("!!", 1), (error) => checkError(error, 3, undefined));
+  });
+});
@@ -0,0 +1,24 @@
+/**
+ * The error thrown by the functions below, or the one their promises reject with.
+ */
+export interface Base94Error extends Error {
+  /** The stable code of the error, the same as `DecodeError::code` in Rust. */
+  code: number;
+  /**
+   * The byte offset of the offending character in the UTF-8 form of the input, or
+   * `undefined` for errors without a position.
+   */
+  position?: number;
+}
+
+/** Encodes `data` in `base`, which must be between 2 and 94 (inclusive). */
+export function encode(data: Buffer, base: number): string;
+
+/** Decodes `encoded` in `base`, which must be between 2 and 94 (inclusive). */
+export function decode(encoded: string, base: number): Buffer;
+
+/** Encodes `data` in `base` on the libuv thread pool, resolving to the encoded string. */
+export function encodeAsync(data: Buffer, base: number): Promise<string>;
+
+/** Decodes `encoded` in `base` on the libuv thread pool, resolving to the decoded bytes. */
+export function decodeAsync(encoded: string, base: number): Promise<Buffer>;
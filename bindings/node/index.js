@@ -0,0 +1,2 @@
+// Loads the native addon built by `npm run build`. See `index.d.ts` for the API.
+module.exports = require("./base94.node");
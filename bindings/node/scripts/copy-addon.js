@@ -0,0 +1,14 @@
+// Copies the addon built by cargo to `base94.node`, where `index.js` loads it from.
+// Usage: node scripts/copy-addon.js <release|debug>
+
+const fs = require("node:fs");
+const path = require("node:path");
+
+const profile = process.argv[2] || "release";
+const targetDir = process.env.CARGO_TARGET_DIR || path.join(__dirname, "..", "..", "..", "target");
+const library = {
+  darwin: "libbase94_node.dylib",
+  win32: "base94_node.dll",
+}[process.platform] || "libbase94_node.so";
+
+fs.copyFileSync(path.join(targetDir, profile, library), path.join(__dirname, "..", "base94.node"));
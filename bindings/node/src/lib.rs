@@ -0,0 +1,99 @@
+//! Node.js bindings to [`base94::encode`] and [`base94::decode`], built with napi-rs.
+//!
+//! The addon exports `encode(buffer, base)` and `decode(text, base)`, and `encodeAsync` and
+//! `decodeAsync`, which run the conversion on the libuv thread pool and return a promise, so
+//! multi-megabyte payloads do not block the event loop. `index.js` loads the addon and
+//! `index.d.ts` declares its types.
+//!
+//! Errors are thrown, or the promises rejected, with JavaScript `Error`s whose message is
+//! the Rust error message, with a `code` property holding the [`DecodeError::code`] and a
+//! `position` property holding the [`DecodeError::position`], or `undefined`. Positions are
+//! byte offsets into the UTF-8 form of the input, the same as in the `wasm` bindings of the
+//! main crate.
+
+use base94::{decode_bytes, try_encode, DecodeError, EncodeError};
+use napi::bindgen_prelude::{AsyncTask, Buffer};
+use napi::{Env, JsValue, Result, Task};
+use napi_derive::napi;
+
+/// Converts `err` into a JavaScript `Error` with `code` and `position` properties.
+fn js_error(env: &Env, err: &DecodeError) -> napi::Error {
+    let build = || -> Result<napi::Error> {
+        let mut error = env.create_error(napi::Error::from_reason(err.to_string()))?;
+        error.set("code", err.code())?;
+        if let Some(position) = err.position() {
+            error.set("position", position as f64)?;
+        }
+        Ok(error.to_unknown().into())
+    };
+    build().unwrap_or_else(|failure| failure)
+}
+
+/// The error thrown for a `base` outside 2 to 94, the same for encoding and decoding.
+fn invalid_base(env: &Env, base: u8) -> napi::Error {
+    js_error(env, &DecodeError::InvalidBase { base })
+}
+
+/// Encodes `data` in `base`, which must be between 2 and 94 (inclusive).
+#[napi]
+pub fn encode(env: Env, data: Buffer, base: u8) -> Result<String> {
+    try_encode(&data, base).map_err(|_| invalid_base(&env, base))
+}
+
+/// Decodes `encoded` in `base`, which must be between 2 and 94 (inclusive).
+#[napi]
+pub fn decode(env: Env, encoded: String, base: u8) -> Result<Buffer> {
+    decode_bytes(encoded.as_bytes(), base)
+        .map(Buffer::from)
+        .map_err(|err| js_error(&env, &err))
+}
+
+/// [`encode`] on the libuv thread pool.
+pub struct EncodeTask {
+    data: Buffer,
+    base: u8,
+}
+
+impl Task for EncodeTask {
+    type Output = std::result::Result<String, EncodeError>;
+    type JsValue = String;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(try_encode(&self.data, self.base))
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> Result<String> {
+        output.map_err(|_| invalid_base(&env, self.base))
+    }
+}
+
+/// [`decode`] on the libuv thread pool.
+pub struct DecodeTask {
+    encoded: String,
+    base: u8,
+}
+
+impl Task for DecodeTask {
+    type Output = std::result::Result<Vec<u8>, DecodeError>;
+    type JsValue = Buffer;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(decode_bytes(self.encoded.as_bytes(), self.base))
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Buffer> {
+        output.map(Buffer::from).map_err(|err| js_error(&env, &err))
+    }
+}
+
+/// Encodes `data` in `base` on the libuv thread pool, resolving to the encoded string.
+#[napi(ts_return_type = "Promise<string>")]
+pub fn encode_async(data: Buffer, base: u8) -> AsyncTask<EncodeTask> {
+    AsyncTask::new(EncodeTask { data, base })
+}
+
+/// Decodes `encoded` in `base` on the libuv thread pool, resolving to the decoded bytes.
+#[napi(ts_return_type = "Promise<Buffer>")]
+pub fn decode_async(encoded: String, base: u8) -> AsyncTask<DecodeTask> {
+    AsyncTask::new(DecodeTask { encoded, base })
+}
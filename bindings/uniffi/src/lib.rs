@@ -0,0 +1,8 @@
+//! The UniFFI interface of `base94` as a `cdylib`, with nothing else in it.
+//!
+//! UniFFI's test runners build the `cdylib` of the package they test with its default
+//! features, so they cannot reach the main crate's `uniffi` module directly. This crate
+//! links `base94` with that feature and re-exports it, which puts the interface in its
+//! library. Mobile apps can ship this library instead of the main crate's.
+
+pub use base94;
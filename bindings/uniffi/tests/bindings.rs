@@ -0,0 +1,42 @@
+//! Generates the UniFFI bindings for this crate's `cdylib` and runs the scripts in
+//! `tests/bindings` against them, with the runners UniFFI provides for each language.
+//!
+//! A language whose toolchain (`kotlinc` with JNA on the `CLASSPATH`, `swiftc`, or
+//! `python3`) is not on the `PATH` is skipped with a note on stderr.
+
+use std::process::{Command, Stdio};
+
+use uniffi::deps::anyhow::Result;
+
+type RunTest = fn(&str, &str, &str) -> Result<()>;
+
+/// Runs `script` with `run_test` if `tool` can be started.
+fn run(tool: &str, run_test: RunTest, script: &str) -> Result<()> {
+    let found = Command::new(tool)
+        .arg("-version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok();
+    if !found {
+        eprintln!("skipping {}: `{}` is not installed", script, tool);
+        return Ok(());
+    }
+    let script = format!("{}/tests/bindings/{}", env!("CARGO_MANIFEST_DIR"), script);
+    run_test(env!("CARGO_TARGET_TMPDIR"), env!("CARGO_PKG_NAME"), &script)
+}
+
+#[test]
+fn test_python_bindings() -> Result<()> {
+    run("python3", uniffi::python_test::run_test, "test_base94.py")
+}
+
+#[test]
+fn test_kotlin_bindings() -> Result<()> {
+    run("kotlinc", uniffi::kotlin_test::run_test, "test_base94.kts")
+}
+
+#[test]
+fn test_swift_bindings() -> Result<()> {
+    run("swiftc", uniffi::swift_test::run_test, "test_base94.swift")
+}
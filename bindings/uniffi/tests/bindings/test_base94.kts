@@ -0,0 +1,38 @@
+// Smoke test of the generated Kotlin bindings, run by `tests/bindings.rs`.
+
+import uniffi.base94.*
+
+assert(encode("Hello".toByteArray(), 94u) == "G8GP4b")
+assert(decode("G8GP4b", 94u).contentEquals("Hello".toByteArray()))
+val all = ByteArray(256) { it.toByte() }
+for (base in 2..94) {
+    assert(decode(encode(all, base.toUByte()), base.toUByte()).contentEquals(all))
+}
+
+try {
+    decode("Hel lo", 94u)
+    throw AssertionError("decode accepted a space")
+} catch (e: DecodeException.InvalidCharacter) {
+    assert(e.c == " ")
+    assert(e.position == 3uL)
+}
+
+try {
+    encode("Hi".toByteArray(), 95u)
+    throw AssertionError("encode accepted base 95")
+} catch (e: DecodeException.InvalidBase) {
+    assert(e.base == 95.toUByte())
+}
+
+val hex = Alphabet("0123456789abcdef")
+assert(hex.base() == 16.toUByte())
+assert(hex.symbols() == "0123456789abcdef")
+assert(decodeWithAlphabet(encodeWithAlphabet("Hello".toByteArray(), hex), hex).contentEquals("Hello".toByteArray()))
+assert(Alphabet.standard().base() == 94.toUByte())
+
+try {
+    Alphabet("aba")
+    throw AssertionError("Alphabet accepted a duplicate")
+} catch (e: AlphabetException.DuplicateCharacter) {
+    assert(e.first == 0uL && e.second == 2uL)
+}
@@ -0,0 +1,41 @@
+# Smoke test of the generated Python bindings, run by `tests/bindings.rs`.
+
+from base94 import (
+    Alphabet,
+    AlphabetError,
+    DecodeError,
+    decode,
+    decode_with_alphabet,
+    encode,
+    encode_with_alphabet,
+)
+
+assert encode(b"Hello", 94) == "G8GP4b"
+assert decode("G8GP4b", 94) == b"Hello"
+for base in range(2, 95):
+    assert decode(encode(bytes(range(256)), base), base) == bytes(range(256))
+
+try:
+    decode("Hel lo", 94)
+    raise AssertionError("decode accepted a space")
+except DecodeError.InvalidCharacter as error:
+    assert error.c == " "
+    assert error.position == 3
+
+try:
+    encode(b"Hi", 95)
+    raise AssertionError("encode accepted base 95")
+except DecodeError.InvalidBase as error:
+    assert error.base == 95
+
+hex = Alphabet("0123456789abcdef")
+assert hex.base() == 16
+assert hex.symbols() == "0123456789abcdef"
+assert decode_with_alphabet(encode_with_alphabet(b"Hello", hex), hex) == b"Hello"
+assert Alphabet.standard().base() == 94
+
+try:
+    Alphabet("aba")
+    raise AssertionError("Alphabet accepted a duplicate")
+except AlphabetError.DuplicateCharacter as error:
+    assert (error.first, error.second) == (0, 2)
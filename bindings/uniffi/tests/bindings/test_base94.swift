@@ -0,0 +1,39 @@
+// Smoke test of the generated Swift bindings, run by `tests/bindings.rs`.
+
+import base94
+
+assert(try! encode(data: Data("Hello".utf8), base: 94) == "G8GP4b")
+assert(try! decode(encoded: "G8GP4b", base: 94) == Data("Hello".utf8))
+let all = Data((0...255).map { UInt8($0) })
+for base in UInt8(2)...94 {
+    assert(try! decode(encoded: try! encode(data: all, base: base), base: base) == all)
+}
+
+do {
+    _ = try decode(encoded: "Hel lo", base: 94)
+    fatalError("decode accepted a space")
+} catch DecodeError.InvalidCharacter(let c, let position) {
+    assert(c == " ")
+    assert(position == 3)
+}
+
+do {
+    _ = try encode(data: Data("Hi".utf8), base: 95)
+    fatalError("encode accepted base 95")
+} catch DecodeError.InvalidBase(let base) {
+    assert(base == 95)
+}
+
+let hex = try! Alphabet(symbols: "0123456789abcdef")
+assert(hex.base() == 16)
+assert(hex.symbols() == "0123456789abcdef")
+let encoded = encodeWithAlphabet(data: Data("Hello".utf8), alphabet: hex)
+assert(try! decodeWithAlphabet(encoded: encoded, alphabet: hex) == Data("Hello".utf8))
+assert(Alphabet.standard().base() == 94)
+
+do {
+    _ = try Alphabet(symbols: "aba")
+    fatalError("Alphabet accepted a duplicate")
+} catch AlphabetError.DuplicateCharacter(_, let first, let second) {
+    assert(first == 0 && second == 2)
+}
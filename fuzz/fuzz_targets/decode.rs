@@ -0,0 +1,22 @@
+//! Feeds arbitrary bytes to `decode` and `decode_bytes` in every base, valid or not.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &[u8]| {
+    let Some((&base, encoded)) = input.split_first() else {
+        return;
+    };
+
+    let result = base94::decode_bytes(encoded, base);
+    if let Ok(text) = std::str::from_utf8(encoded) {
+        let from_str = base94::decode(text, base);
+        assert_eq!(from_str.as_ref().ok(), result.as_ref().ok());
+    }
+
+    // Every accepted string is the one and only encoding of what it decodes to.
+    if let Ok(decoded) = result {
+        assert_eq!(base94::encode_to_bytes(&decoded, base), encoded);
+    }
+});
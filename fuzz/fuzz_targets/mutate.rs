@@ -0,0 +1,47 @@
+//! Encodes arbitrary data, corrupts the output, and checks that decoding either succeeds on
+//! a canonical string or reports the first bad character.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use base94::DecodeError;
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    data: Vec<u8>,
+    base: u8,
+    /// Positions, taken modulo the encoded length, and the bytes written there.
+    mutations: Vec<(u16, u8)>,
+}
+
+fuzz_target!(|input: Input| {
+    let base = input.base % 93 + 2;
+    let mut encoded = base94::encode_to_bytes(&input.data, base);
+    assert_eq!(base94::decode_bytes(&encoded, base).unwrap(), input.data);
+    if encoded.is_empty() {
+        return;
+    }
+    for (position, byte) in input.mutations {
+        let len = encoded.len();
+        encoded[position as usize % len] = byte;
+    }
+
+    match base94::decode_bytes(&encoded, base) {
+        Ok(decoded) => assert_eq!(base94::encode_to_bytes(&decoded, base), encoded),
+        Err(DecodeError::InvalidCharacter { c, position })
+        | Err(DecodeError::DigitOutOfRange { c, position, .. }) => {
+            if c != char::REPLACEMENT_CHARACTER {
+                let mut buf = [0; 4];
+                assert!(encoded[position..].starts_with(c.encode_utf8(&mut buf).as_bytes()));
+            }
+            assert!(base94::validate(
+                std::str::from_utf8(&encoded[..position]).unwrap(),
+                base
+            )
+            .is_ok());
+            assert!(!base94::CHARACTERS[..base as usize].contains(&encoded[position]));
+        }
+        Err(err) => panic!("unexpected error {:?}", err),
+    }
+});
@@ -0,0 +1,57 @@
+//! Feeds arbitrary text to the streaming `Decoder` in arbitrary pieces and checks that it
+//! agrees with one-shot `decode_chunked`.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    base: u8,
+    chunk_size: u8,
+    /// Either raw text or data to encode, so that both valid and invalid streams are
+    /// covered.
+    text: Result<String, Vec<u8>>,
+    /// The lengths of the pieces the text is split into.
+    splits: Vec<u8>,
+}
+
+fuzz_target!(|input: Input| {
+    let base = input.base % 93 + 2;
+    let chunk_size = input.chunk_size as usize % 64 + 1;
+    let text = match input.text {
+        Ok(text) => text,
+        Err(data) => base94::encode_chunked(&data, base, chunk_size),
+    };
+    let expected = base94::decode_chunked(&text, base, chunk_size);
+
+    let mut decoder = base94::Decoder::with_chunk_size(base, chunk_size);
+    let mut decoded = Vec::new();
+    let mut rest = text.as_str();
+    let mut splits = input.splits.into_iter();
+    let streamed = loop {
+        let mut len = splits.next().map_or(rest.len(), usize::from).min(rest.len());
+        while !rest.is_char_boundary(len) {
+            len += 1;
+        }
+        let (piece, tail) = rest.split_at(len);
+        rest = tail;
+        match decoder.push(piece) {
+            Ok(bytes) => decoded.extend(bytes),
+            Err(err) => break Err(err),
+        }
+        if rest.is_empty() {
+            break decoder.finalize().map(|bytes| {
+                decoded.extend(bytes);
+                decoded
+            });
+        }
+    };
+
+    match (expected, streamed) {
+        (Ok(expected), Ok(streamed)) => assert_eq!(expected, streamed),
+        (Err(_), Err(_)) => {}
+        (expected, streamed) => panic!("one-shot {:?}, streamed {:?}", expected, streamed),
+    }
+});
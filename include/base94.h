@@ -0,0 +1,91 @@
+#ifndef BASE94_H
+#define BASE94_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Returned by `base94_last_error_position` when the last error has no position.
+#define BASE94_NO_POSITION SIZE_MAX
+
+// The call succeeded.
+#define BASE94_OK 0
+
+// `base` is outside the valid range (2 to 94). Equal to the code of
+// `DecodeError::InvalidBase`, which decoding reports for the same condition.
+#define BASE94_ERROR_INVALID_BASE 3
+
+// A required pointer argument was null.
+#define BASE94_ERROR_NULL_POINTER 1000
+
+// The library panicked. This is a bug; the outputs are left empty.
+#define BASE94_ERROR_PANIC 1001
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Encodes the `len` bytes at `data` in `base`.
+//
+// On success, `*out` points to the encoded text, `*out_len` is its length without the
+// terminating NUL, and the result is `BASE94_OK`. The text must be released with
+// [`base94_free`].
+//
+// # Returns
+//
+// `BASE94_OK`, `BASE94_ERROR_INVALID_BASE`, `BASE94_ERROR_NULL_POINTER` if `out`, `out_len`,
+// or `data` with a nonzero `len` is null, or `BASE94_ERROR_PANIC`.
+//
+// # Safety
+//
+// `data` must be null or valid for reads of `len` bytes, and `out` and `out_len` must be
+// null or valid for writes.
+uint32_t base94_encode(const uint8_t *data, size_t len, uint8_t base, char **out, size_t *out_len);
+
+// Decodes the `len` bytes of text at `encoded` in `base`. The text does not need to be
+// NUL-terminated or valid UTF-8.
+//
+// On success, `*out` points to the decoded bytes, `*out_len` is their number, and the
+// result is `BASE94_OK`. The bytes must be released with [`base94_free`].
+//
+// # Returns
+//
+// `BASE94_OK`, the [`DecodeError::code`] of the decode error, whose byte offset is then
+// available from [`base94_last_error_position`], `BASE94_ERROR_NULL_POINTER` if `out`,
+// `out_len`, or `encoded` with a nonzero `len` is null, or `BASE94_ERROR_PANIC`.
+//
+// # Safety
+//
+// `encoded` must be null or valid for reads of `len` bytes, and `out` and `out_len` must be
+// null or valid for writes.
+uint32_t base94_decode(const char *encoded,
+                       size_t len,
+                       uint8_t base,
+                       uint8_t **out,
+                       size_t *out_len);
+
+// Releases a buffer returned by [`base94_encode`] or [`base94_decode`]. `len` is the length
+// returned with it. A null `ptr` is ignored.
+//
+// # Safety
+//
+// `ptr` must be null or a buffer returned by this library that has not been released yet,
+// and `len` must be the length returned with it.
+void base94_free(uint8_t *ptr, size_t len);
+
+// Returns the status code of the last call on this thread, `BASE94_OK` if it succeeded.
+uint32_t base94_last_error_code(void);
+
+// Returns the byte offset of the last decode error on this thread, or
+// `BASE94_NO_POSITION` if the last call succeeded or its error has no position.
+size_t base94_last_error_position(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* BASE94_H */
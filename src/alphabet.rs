@@ -0,0 +1,775 @@
+//! Alphabets that map digits to characters.
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+use thiserror::Error;
+
+use crate::ALPHABET;
+#[cfg(feature = "alloc")]
+use crate::{
+    collect_digits, decode_digits, encode_digits, encode_symbols, invalid_character, wipe,
+    DecodeError,
+};
+
+/// Marks bytes that are not part of an alphabet in its reverse lookup table.
+const NOT_A_DIGIT: u8 = u8::MAX;
+
+/// The largest number of characters an alphabet can have.
+const MAX_LEN: usize = 94;
+
+/// The standard alphabet of [`CHARACTERS`](crate::CHARACTERS), used by [`encode`](crate::encode)
+/// and [`decode`](crate::decode). A base below 94 uses its first `base` characters.
+pub static STANDARD: Alphabet = match Alphabet::new(ALPHABET) {
+    Ok(alphabet) => alphabet,
+    Err(_) => panic!("characters.txt is a valid alphabet"),
+};
+
+/// The largest number of characters a [`UnicodeAlphabet`] can have, so that every digit
+/// fits in a byte.
+#[cfg(feature = "alloc")]
+const MAX_UNICODE_LEN: usize = 256;
+
+/// Errors that can occur while constructing an [`Alphabet`] or a `UnicodeAlphabet`.
+///
+/// Positions count characters, not bytes.
+#[derive(Error, Debug)]
+pub enum AlphabetError {
+    #[error("Invalid alphabet length {length}, must be in the range 2..={max}")]
+    InvalidLength { length: usize, max: usize },
+    #[error("Character {c:?} at position {position} cannot be used in an alphabet")]
+    InvalidCharacter { c: char, position: usize },
+    #[error("Character {c:?} appears at positions {first} and {second}")]
+    DuplicateCharacter {
+        c: char,
+        first: usize,
+        second: usize,
+    },
+    #[error("Alphabet contains both {upper:?} and {lower:?}, so it cannot ignore case")]
+    CaseCollision { upper: char, lower: char },
+}
+
+/// A set of distinct printable ASCII characters standing for the digits `0..len()`.
+///
+/// The base of an encoding with an alphabet is its length, so an alphabet of 62 characters
+/// encodes in base 62. Apart from the characters, the output is identical to
+/// [`encode`](crate::encode) with the same base.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_with_alphabet, encode_with_alphabet, Alphabet};
+///
+/// let hex = Alphabet::new(b"0123456789abcdef").unwrap();
+/// assert_eq!(hex.len(), 16);
+/// assert_eq!(hex.char_of(10), Some(b'a'));
+/// assert_eq!(hex.digit_of(b'f'), Some(15));
+///
+/// let encoded = encode_with_alphabet(&[0x2a], &hex);
+/// assert_eq!(encoded, "a2");
+/// assert_eq!(decode_with_alphabet(&encoded, &hex).unwrap(), [0x2a]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Alphabet {
+    symbols: [u8; MAX_LEN],
+    len: u8,
+    /// Maps every byte to its digit, or to [`NOT_A_DIGIT`].
+    index: [u8; 256],
+}
+
+impl Alphabet {
+    /// Creates an alphabet whose character at position `i` stands for the digit `i`.
+    ///
+    /// # Returns
+    ///
+    /// The alphabet, or an `AlphabetError` if `symbols` has fewer than 2 or more than 94
+    /// characters, contains a byte outside printable ASCII (`' '` to `'~'`), or contains a
+    /// character twice.
+    pub const fn new(symbols: &[u8]) -> Result<Self, AlphabetError> {
+        if symbols.len() < 2 || symbols.len() > MAX_LEN {
+            return Err(AlphabetError::InvalidLength {
+                length: symbols.len(),
+                max: MAX_LEN,
+            });
+        }
+
+        let mut alphabet = Self {
+            symbols: [0; MAX_LEN],
+            len: symbols.len() as u8,
+            index: [NOT_A_DIGIT; 256],
+        };
+        let mut i = 0;
+        while i < symbols.len() {
+            let c = symbols[i];
+            if !(c >= b' ' && c <= b'~') {
+                return Err(AlphabetError::InvalidCharacter {
+                    c: c as char,
+                    position: i,
+                });
+            }
+            if alphabet.index[c as usize] != NOT_A_DIGIT {
+                return Err(AlphabetError::DuplicateCharacter {
+                    c: c as char,
+                    first: alphabet.index[c as usize] as usize,
+                    second: i,
+                });
+            }
+            alphabet.symbols[i] = c;
+            alphabet.index[c as usize] = i as u8;
+            i += 1;
+        }
+        Ok(alphabet)
+    }
+
+    /// Makes decoding accept every letter of the alphabet in either case, for data that
+    /// passes through people or case-folding systems. Encoding still produces the characters
+    /// as given.
+    ///
+    /// # Returns
+    ///
+    /// The case-insensitive alphabet, or `AlphabetError::CaseCollision` if the alphabet
+    /// contains both cases of a letter, as the standard alphabet does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base94::{decode_with_alphabet, encode_with_alphabet, Alphabet};
+    ///
+    /// let base36 = Alphabet::new(b"0123456789abcdefghijklmnopqrstuvwxyz")
+    ///     .and_then(Alphabet::case_insensitive)
+    ///     .unwrap();
+    /// let encoded = encode_with_alphabet(b"Hello", &base36);
+    /// let shouted = encoded.to_uppercase();
+    /// assert_eq!(decode_with_alphabet(&shouted, &base36).unwrap(), b"Hello");
+    /// ```
+    pub const fn case_insensitive(mut self) -> Result<Self, AlphabetError> {
+        let mut i = 0;
+        while i < self.len() {
+            let c = self.symbols[i];
+            let other = if c.is_ascii_lowercase() {
+                c.to_ascii_uppercase()
+            } else {
+                c.to_ascii_lowercase()
+            };
+            if other != c {
+                if self.index[other as usize] != NOT_A_DIGIT {
+                    return Err(AlphabetError::CaseCollision {
+                        upper: c.to_ascii_uppercase() as char,
+                        lower: c.to_ascii_lowercase() as char,
+                    });
+                }
+                self.index[other as usize] = i as u8;
+            }
+            i += 1;
+        }
+        Ok(self)
+    }
+
+    /// Returns the number of characters, which is the base of the encoding.
+    pub const fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Always returns `false`, since an alphabet has at least two characters.
+    pub const fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Returns the base of the encoding, which is the number of characters.
+    pub const fn base(&self) -> u8 {
+        self.len
+    }
+
+    /// Returns the characters in digit order.
+    pub fn chars(&self) -> &[u8] {
+        &self.symbols[..self.len()]
+    }
+
+    /// Returns the character for `digit`, or `None` if `digit` is not below the base.
+    pub fn char_of(&self, digit: u8) -> Option<u8> {
+        self.chars().get(digit as usize).copied()
+    }
+
+    /// Returns the digit that `c` stands for, or `None` if `c` is not in the alphabet.
+    pub fn digit_of(&self, c: u8) -> Option<u8> {
+        match self.index[c as usize] {
+            NOT_A_DIGIT => None,
+            digit => Some(digit),
+        }
+    }
+
+    /// Looks up the digit of the first byte of `rest`, found at byte offset `position`.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn digit_value(&self, rest: &[u8], position: usize) -> Result<u8, DecodeError> {
+        self.digit_of(rest[0])
+            .ok_or_else(|| invalid_character(rest, position))
+    }
+}
+
+/// Generates alphabets of 2 to 94 distinct printable ASCII characters in random order,
+/// including space.
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for Alphabet {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+
+        let printable = (b' '..=b'~').collect::<Vec<u8>>();
+        proptest::sample::subsequence(printable, 2..=MAX_LEN)
+            .prop_shuffle()
+            .prop_map(|symbols| Alphabet::new(&symbols).expect("characters are distinct"))
+            .boxed()
+    }
+}
+
+impl fmt::Debug for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let chars = core::str::from_utf8(self.chars()).expect("alphabets are ASCII");
+        f.debug_tuple("Alphabet").field(&chars).finish()
+    }
+}
+
+/// An alphabet of up to 256 arbitrary characters, such as CJK ideographs or emoji.
+///
+/// This is the counterpart of [`Alphabet`] for characters outside ASCII. Encoded strings
+/// hold more information per character but more bytes per character, which suits channels
+/// that count characters, such as SMS or social media posts. With 256 characters, every byte
+/// becomes one character.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_with_unicode_alphabet, encode_with_unicode_alphabet, UnicodeAlphabet};
+///
+/// let cjk = UnicodeAlphabet::from_chars((0x4e00..0x4f00).filter_map(char::from_u32)).unwrap();
+/// assert_eq!(cjk.base(), 256);
+///
+/// let encoded = encode_with_unicode_alphabet(b"Hello", &cjk);
+/// assert_eq!(encoded.chars().count(), 5);
+/// assert_eq!(decode_with_unicode_alphabet(&encoded, &cjk).unwrap(), b"Hello");
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnicodeAlphabet {
+    symbols: Vec<char>,
+    /// Every character with its digit, sorted by character.
+    index: Vec<(char, u8)>,
+}
+
+#[cfg(feature = "alloc")]
+impl UnicodeAlphabet {
+    /// Creates an alphabet from the characters of `symbols`, where the character at position
+    /// `i` stands for the digit `i`.
+    ///
+    /// # Returns
+    ///
+    /// The alphabet, or an `AlphabetError` under the same conditions as
+    /// [`from_chars`](Self::from_chars).
+    pub fn new(symbols: &str) -> Result<Self, AlphabetError> {
+        Self::from_chars(symbols.chars())
+    }
+
+    /// Creates an alphabet from `symbols`, where the character at position `i` stands for the
+    /// digit `i`.
+    ///
+    /// # Returns
+    ///
+    /// The alphabet, or an `AlphabetError` if there are fewer than 2 or more than 256
+    /// characters, a control character or whitespace, or a character that appears twice.
+    pub fn from_chars(symbols: impl IntoIterator<Item = char>) -> Result<Self, AlphabetError> {
+        let symbols = symbols.into_iter().collect::<Vec<char>>();
+        if symbols.len() < 2 || symbols.len() > MAX_UNICODE_LEN {
+            return Err(AlphabetError::InvalidLength {
+                length: symbols.len(),
+                max: MAX_UNICODE_LEN,
+            });
+        }
+        if let Some(position) = symbols
+            .iter()
+            .position(|c| c.is_control() || c.is_whitespace())
+        {
+            return Err(AlphabetError::InvalidCharacter {
+                c: symbols[position],
+                position,
+            });
+        }
+
+        let mut index = symbols
+            .iter()
+            .enumerate()
+            .map(|(digit, &c)| (c, digit as u8))
+            .collect::<Vec<(char, u8)>>();
+        index.sort_unstable();
+        if let Some(pair) = index.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            let (first, second) = (pair[0].1 as usize, pair[1].1 as usize);
+            return Err(AlphabetError::DuplicateCharacter {
+                c: pair[0].0,
+                first: first.min(second),
+                second: first.max(second),
+            });
+        }
+        Ok(Self { symbols, index })
+    }
+
+    /// Returns the number of characters, which is the base of the encoding.
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Always returns `false`, since an alphabet has at least two characters.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Returns the base of the encoding, which is the number of characters.
+    pub fn base(&self) -> u16 {
+        self.symbols.len() as u16
+    }
+
+    /// Returns the characters in digit order.
+    pub fn chars(&self) -> &[char] {
+        &self.symbols
+    }
+
+    /// Returns the character for `digit`, or `None` if `digit` is not below the base.
+    pub fn char_of(&self, digit: u8) -> Option<char> {
+        self.symbols.get(digit as usize).copied()
+    }
+
+    /// Returns the digit that `c` stands for, or `None` if `c` is not in the alphabet.
+    pub fn digit_of(&self, c: char) -> Option<u8> {
+        self.index
+            .binary_search_by_key(&c, |&(symbol, _)| symbol)
+            .ok()
+            .map(|i| self.index[i].1)
+    }
+}
+
+/// Encodes a slice of bytes like [`encode`](crate::encode), with the characters of
+/// `alphabet` and its length as the base.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `alphabet` - The characters to encode with.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode, encode_with_alphabet, STANDARD};
+///
+/// assert_eq!(encode_with_alphabet(b"Hello", &STANDARD), encode(b"Hello", 94));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode_with_alphabet(data: &[u8], alphabet: &Alphabet) -> String {
+    String::from_utf8(encode_symbols(data, alphabet.base(), alphabet.chars()))
+        .expect("alphabets are ASCII")
+}
+
+/// Decodes a string produced by [`encode_with_alphabet`] with the same alphabet.
+///
+/// # Arguments
+///
+/// * `encoded` - The string to decode.
+/// * `alphabet` - The characters the string was encoded with.
+///
+/// # Returns
+///
+/// The decoded bytes, or `DecodeError::InvalidCharacter` with its byte offset if `encoded`
+/// contains a character outside `alphabet`.
+#[cfg(feature = "alloc")]
+pub fn decode_with_alphabet(encoded: &str, alphabet: &Alphabet) -> Result<Vec<u8>, DecodeError> {
+    let digits = collect_digits(
+        (0..encoded.len()).map(|i| alphabet.digit_value(&encoded.as_bytes()[i..], i)),
+    )?;
+    Ok(decode_digits(&digits, alphabet.base()))
+}
+
+/// Encodes a slice of bytes as a big-endian number, most significant digit first, with the
+/// characters of `alphabet` and its length as the base.
+///
+/// This is the layout used by Bitcoin's Base58 and by the `base-x` family of libraries:
+/// every leading zero byte becomes a leading zero digit (the first character of
+/// `alphabet`), and the remaining bytes are converted as one number. It is
+/// [`encode_with_alphabet`] applied to the reversed bytes, with the output reversed, so it
+/// differs from [`encode_with_order`](crate::encode_with_order) with
+/// [`DigitOrder::MsbFirst`](crate::DigitOrder::MsbFirst), which reverses only the digits.
+///
+/// To encode an integer ID the way URL shorteners do, pass its minimal big-endian bytes.
+/// Zero then encodes to an empty string where those libraries write a single zero digit.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `alphabet` - The characters to encode with.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode_big_endian_with_alphabet, presets};
+///
+/// assert_eq!(encode_big_endian_with_alphabet(b"Hello World!", &presets::BASE62), "T8dgcjRGkZ3aysdN");
+/// assert_eq!(encode_big_endian_with_alphabet(&[0, 0, 1], &presets::BASE62), "001");
+///
+/// let id = 1234567890u64;
+/// let bytes = id.to_be_bytes();
+/// let minimal = &bytes[id.leading_zeros() as usize / 8..];
+/// assert_eq!(encode_big_endian_with_alphabet(minimal, &presets::BASE62), "1LY7VK");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode_big_endian_with_alphabet(data: &[u8], alphabet: &Alphabet) -> String {
+    let mut reversed = data.iter().rev().copied().collect::<Vec<u8>>();
+    let mut encoded = encode_symbols(&reversed, alphabet.base(), alphabet.chars());
+    wipe(&mut reversed);
+    encoded.reverse();
+    String::from_utf8(encoded).expect("alphabets are ASCII")
+}
+
+/// Decodes a string produced by [`encode_big_endian_with_alphabet`] with the same alphabet.
+///
+/// # Arguments
+///
+/// * `encoded` - The string to decode.
+/// * `alphabet` - The characters the string was encoded with.
+///
+/// # Returns
+///
+/// The decoded bytes, or `DecodeError::InvalidCharacter` with its byte offset if `encoded`
+/// contains a character outside `alphabet`.
+#[cfg(feature = "alloc")]
+pub fn decode_big_endian_with_alphabet(
+    encoded: &str,
+    alphabet: &Alphabet,
+) -> Result<Vec<u8>, DecodeError> {
+    let mut digits = collect_digits(
+        (0..encoded.len()).map(|i| alphabet.digit_value(&encoded.as_bytes()[i..], i)),
+    )?;
+    digits.reverse();
+    let mut decoded = decode_digits(&digits, alphabet.base());
+    wipe(&mut digits);
+    decoded.reverse();
+    Ok(decoded)
+}
+
+/// Encodes a slice of bytes like [`encode_with_alphabet`], with the characters of a
+/// [`UnicodeAlphabet`] and its length as the base.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `alphabet` - The characters to encode with.
+#[cfg(feature = "alloc")]
+pub fn encode_with_unicode_alphabet(data: &[u8], alphabet: &UnicodeAlphabet) -> String {
+    let mut digits = unicode_digits(data, alphabet.base());
+    let encoded = digits
+        .iter()
+        .map(|&digit| alphabet.symbols[digit as usize])
+        .collect();
+    wipe(&mut digits);
+    encoded
+}
+
+/// Converts `data` into its digits in `base`, which may be as large as 256.
+#[cfg(feature = "alloc")]
+fn unicode_digits(data: &[u8], base: u16) -> Vec<u8> {
+    // In base 256 the digits are the bytes themselves, including trailing zeros.
+    match u8::try_from(base) {
+        Ok(base) => encode_digits(data, base),
+        Err(_) => data.to_vec(),
+    }
+}
+
+/// Decodes a string produced by [`encode_with_unicode_alphabet`] with the same alphabet.
+///
+/// # Arguments
+///
+/// * `encoded` - The string to decode.
+/// * `alphabet` - The characters the string was encoded with.
+///
+/// # Returns
+///
+/// The decoded bytes, or `DecodeError::InvalidCharacter` if `encoded` contains a character
+/// outside `alphabet`, with that character and its byte offset.
+#[cfg(feature = "alloc")]
+pub fn decode_with_unicode_alphabet(
+    encoded: &str,
+    alphabet: &UnicodeAlphabet,
+) -> Result<Vec<u8>, DecodeError> {
+    let digits = collect_digits(encoded.char_indices().map(|(position, c)| {
+        alphabet
+            .digit_of(c)
+            .ok_or(DecodeError::InvalidCharacter { c, position })
+    }))?;
+    match u8::try_from(alphabet.base()) {
+        Ok(base) => Ok(decode_digits(&digits, base)),
+        Err(_) => Ok(digits),
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{decode, encode, CHARACTERS};
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_standard_matches_characters() {
+        assert_eq!(STANDARD.chars(), CHARACTERS);
+        for (digit, &c) in CHARACTERS.iter().enumerate() {
+            assert_eq!(STANDARD.char_of(digit as u8), Some(c));
+            assert_eq!(STANDARD.digit_of(c), Some(digit as u8));
+        }
+        assert_eq!(STANDARD.char_of(94), None);
+        assert_eq!(STANDARD.digit_of(b' '), None);
+    }
+
+    #[test]
+    fn test_prefix_alphabets_match_encode() {
+        for base in 2..=MAX_BASE {
+            let alphabet = Alphabet::new(&CHARACTERS[..base as usize]).unwrap();
+            let data = (0..50).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let encoded = encode_with_alphabet(&data, &alphabet);
+            assert_eq!(encoded, encode(&data, base));
+            assert_eq!(decode_with_alphabet(&encoded, &alphabet).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_big_endian_reverses_bytes_and_digits() {
+        for base in 2..=MAX_BASE {
+            let alphabet = Alphabet::new(&CHARACTERS[..base as usize]).unwrap();
+            let mut data = vec![0, 0];
+            data.extend((0..50).map(|_| rand::random::<u8>()));
+            let reversed = data.iter().rev().copied().collect::<Vec<u8>>();
+            let encoded = encode_big_endian_with_alphabet(&data, &alphabet);
+            assert!(encoded.starts_with("!!"));
+            assert_eq!(
+                encoded,
+                encode(&reversed, base).chars().rev().collect::<String>()
+            );
+            assert_eq!(
+                decode_big_endian_with_alphabet(&encoded, &alphabet).unwrap(),
+                data
+            );
+        }
+    }
+
+    #[test]
+    fn test_custom_alphabet_round_trip() {
+        let mut symbols = CHARACTERS.to_vec();
+        symbols.reverse();
+        symbols[93] = b' ';
+        let alphabet = Alphabet::new(&symbols).unwrap();
+        for len in [0, 1, 16, 100] {
+            let mut data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            data.extend([0, 0]);
+            let encoded = encode_with_alphabet(&data, &alphabet);
+            assert!(encoded.ends_with("~~"));
+            assert_eq!(decode_with_alphabet(&encoded, &alphabet).unwrap(), data);
+            assert_eq!(decode(&encoded, 94).is_ok(), !encoded.contains(' '));
+        }
+    }
+
+    #[test]
+    fn test_invalid_length() {
+        for symbols in [&b""[..], b"a", &[b'!'; 95]] {
+            assert!(matches!(
+                Alphabet::new(symbols),
+                Err(AlphabetError::InvalidLength { length, max: 94 }) if length == symbols.len()
+            ));
+        }
+    }
+
+    #[test]
+    fn test_invalid_character() {
+        for c in [b'\n', b'\t', 0x7f, 0x80, 0xff] {
+            assert!(matches!(
+                Alphabet::new(&[b'a', b'b', c]),
+                Err(AlphabetError::InvalidCharacter { c: found, position: 2 }) if found == c as char
+            ));
+        }
+    }
+
+    #[test]
+    fn test_duplicate_character() {
+        assert!(matches!(
+            Alphabet::new(b"abcdb"),
+            Err(AlphabetError::DuplicateCharacter {
+                c: 'b',
+                first: 1,
+                second: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn test_case_insensitive_decoding() {
+        for symbols in [
+            &b"0123456789abcdefghijklmnopqrstuvwxyz"[..],
+            b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+            b"abcdefGHIJKL!?",
+        ] {
+            let strict = Alphabet::new(symbols).unwrap();
+            let alphabet = strict.case_insensitive().unwrap();
+            assert_eq!(alphabet.chars(), strict.chars());
+            for len in [1, 16, 100] {
+                let data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+                let encoded = encode_with_alphabet(&data, &alphabet);
+                assert_eq!(encoded, encode_with_alphabet(&data, &strict));
+                let alternating = encoded
+                    .chars()
+                    .enumerate()
+                    .map(|(i, c)| match i % 2 {
+                        0 => c.to_ascii_uppercase(),
+                        _ => c.to_ascii_lowercase(),
+                    })
+                    .collect::<String>();
+                for variant in [encoded.to_uppercase(), encoded.to_lowercase(), alternating] {
+                    assert_eq!(decode_with_alphabet(&variant, &alphabet).unwrap(), data);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_case_insensitive_rejects_collisions() {
+        assert!(matches!(
+            STANDARD.case_insensitive(),
+            Err(AlphabetError::CaseCollision {
+                upper: 'A',
+                lower: 'a'
+            })
+        ));
+        assert!(matches!(
+            Alphabet::new(b"0123xyzX").unwrap().case_insensitive(),
+            Err(AlphabetError::CaseCollision {
+                upper: 'X',
+                lower: 'x'
+            })
+        ));
+    }
+
+    #[test]
+    fn test_decode_rejects_foreign_characters() {
+        let hex = Alphabet::new(b"0123456789abcdef").unwrap();
+        assert!(matches!(
+            decode_with_alphabet("12g4", &hex),
+            Err(DecodeError::InvalidCharacter {
+                c: 'g',
+                position: 2
+            })
+        ));
+    }
+
+    fn cjk() -> UnicodeAlphabet {
+        UnicodeAlphabet::from_chars((0x4e00..0x4f00).filter_map(char::from_u32)).unwrap()
+    }
+
+    fn emoji() -> UnicodeAlphabet {
+        UnicodeAlphabet::from_chars((0x1f600..0x1f650).filter_map(char::from_u32)).unwrap()
+    }
+
+    #[test]
+    fn test_unicode_round_trip() {
+        for alphabet in [cjk(), emoji(), UnicodeAlphabet::new("01").unwrap()] {
+            for len in [0, 1, 16, 17, 100] {
+                let mut data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+                data.extend([0, 0]);
+                let encoded = encode_with_unicode_alphabet(&data, &alphabet);
+                assert!(encoded.chars().all(|c| alphabet.digit_of(c).is_some()));
+                assert_eq!(
+                    decode_with_unicode_alphabet(&encoded, &alphabet).unwrap(),
+                    data
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_unicode_digits_match_encode() {
+        let data = (0..50).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+
+        let base256 = encode_with_unicode_alphabet(&data, &cjk());
+        let bytes = base256
+            .chars()
+            .map(|c| (c as u32 - 0x4e00) as u8)
+            .collect::<Vec<u8>>();
+        assert_eq!(bytes, data);
+
+        let ascii = UnicodeAlphabet::new(core::str::from_utf8(CHARACTERS).unwrap()).unwrap();
+        assert_eq!(
+            encode_with_unicode_alphabet(&data, &ascii),
+            encode(&data, 94)
+        );
+        assert_eq!(ascii.base(), 94);
+        assert_eq!(emoji().base(), 80);
+    }
+
+    #[test]
+    fn test_unicode_lookup() {
+        let alphabet = emoji();
+        assert_eq!(alphabet.char_of(0), Some('\u{1f600}'));
+        assert_eq!(alphabet.char_of(80), None);
+        assert_eq!(alphabet.digit_of('\u{1f64f}'), Some(79));
+        assert_eq!(alphabet.digit_of('a'), None);
+        assert_eq!(alphabet.chars().len(), alphabet.len());
+    }
+
+    #[test]
+    fn test_unicode_decode_reports_byte_offsets() {
+        let alphabet = emoji();
+        let mut encoded = encode_with_unicode_alphabet(b"Hi", &alphabet);
+        let offset = encoded.len();
+        encoded.push('\u{4e00}');
+        assert!(matches!(
+            decode_with_unicode_alphabet(&encoded, &alphabet),
+            Err(DecodeError::InvalidCharacter { c: '\u{4e00}', position }) if position == offset
+        ));
+    }
+
+    #[test]
+    fn test_unicode_construction_errors() {
+        assert!(matches!(
+            UnicodeAlphabet::new("a"),
+            Err(AlphabetError::InvalidLength {
+                length: 1,
+                max: 256
+            })
+        ));
+        assert!(matches!(
+            UnicodeAlphabet::from_chars((0x4e00..0x4f01).filter_map(char::from_u32)),
+            Err(AlphabetError::InvalidLength {
+                length: 257,
+                max: 256
+            })
+        ));
+        assert!(matches!(
+            UnicodeAlphabet::new("ab c"),
+            Err(AlphabetError::InvalidCharacter {
+                c: ' ',
+                position: 2
+            })
+        ));
+        assert!(matches!(
+            UnicodeAlphabet::new("ab\u{7}"),
+            Err(AlphabetError::InvalidCharacter {
+                c: '\u{7}',
+                position: 2
+            })
+        ));
+        assert!(matches!(
+            UnicodeAlphabet::new("\u{4e00}xy\u{4e00}"),
+            Err(AlphabetError::DuplicateCharacter {
+                c: '\u{4e00}',
+                first: 0,
+                second: 3
+            })
+        ));
+    }
+}
@@ -0,0 +1,197 @@
+//! Decoding pasted text without knowing which of the crate's formats it is in.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::armor::{decode_armored_block, has_begin};
+use crate::tagged::has_header;
+use crate::{decode, decode_tagged, ArmorError, DecodeError};
+
+/// The format that [`decode_any`] found its input in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Container {
+    /// A bare string, decoded in the base passed to [`decode_any`].
+    Plain { base: u8 },
+    /// A string written by [`encode_tagged`](crate::encode_tagged).
+    Tagged { base: u8 },
+    /// A block written by [`encode_armored`](crate::encode_armored).
+    Armored { label: String, base: u8 },
+}
+
+impl Container {
+    /// Returns the base the payload was decoded in.
+    pub fn base(&self) -> u8 {
+        match *self {
+            Container::Plain { base }
+            | Container::Tagged { base }
+            | Container::Armored { base, .. } => base,
+        }
+    }
+}
+
+/// The bytes decoded by [`decode_any`] and the format they were found in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Decoded {
+    pub data: Vec<u8>,
+    pub container: Container,
+}
+
+/// Decodes `input` in whichever format it is in: armored, tagged, or plain.
+///
+/// The format is chosen by markers that a plain string cannot contain, never by guessing
+/// between bases:
+/// - Text containing a `-----BEGIN ...-----` line is read as armor, since plain strings
+///   never contain a space.
+/// - A string starting with `~` followed by three decimal digits is read as tagged.
+/// - Anything else is decoded as a plain string in `base`.
+///
+/// Leading and trailing whitespace is ignored for tagged and plain input, so error positions
+/// count from the first other character.
+///
+/// A plain base 94 string can start like a tagged header by chance (about once in 80,000
+/// random strings), so use [`decode`] directly when the input is known to be plain.
+///
+/// # Arguments
+///
+/// * `input` - The text to decode.
+/// * `base` - The base of plain input, or `None` to accept only armored and tagged input.
+///
+/// # Returns
+///
+/// The decoded bytes and their container, or a `DecodeError` if:
+/// - The input is plain and `base` is `None`, with `DecodeError::UnknownFormat`.
+/// - The detected format fails to decode, with the error of [`decode_tagged`] or
+///   [`decode`]. Armor errors other than a decode error of the body are returned as
+///   `DecodeError::Armor`.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_any, encode, encode_armored, encode_tagged, Container, DecodeError};
+///
+/// let armored = decode_any(&encode_armored(b"Hi", 50, "MESSAGE"), None).unwrap();
+/// assert_eq!(armored.data, b"Hi");
+/// assert_eq!(armored.container, Container::Armored { label: "MESSAGE".into(), base: 50 });
+///
+/// let tagged = decode_any(&encode_tagged(b"Hi", 16), None).unwrap();
+/// assert_eq!(tagged.container, Container::Tagged { base: 16 });
+///
+/// let plain = decode_any(&encode(b"Hi", 10), Some(10)).unwrap();
+/// assert_eq!(plain.container.base(), 10);
+///
+/// assert!(matches!(decode_any(&encode(b"Hi", 10), None), Err(DecodeError::UnknownFormat)));
+/// ```
+pub fn decode_any(input: &str, base: Option<u8>) -> Result<Decoded, DecodeError> {
+    if has_begin(input) {
+        let (label, base, data) = decode_armored_block(input).map_err(|err| match err {
+            ArmorError::Decode(err) => err,
+            err => DecodeError::Armor(Box::new(err)),
+        })?;
+        return Ok(Decoded {
+            data,
+            container: Container::Armored { label, base },
+        });
+    }
+
+    let input = input.trim();
+    if has_header(input) {
+        let (data, base) = decode_tagged(input)?;
+        return Ok(Decoded {
+            data,
+            container: Container::Tagged { base },
+        });
+    }
+
+    let base = base.ok_or(DecodeError::UnknownFormat)?;
+    Ok(Decoded {
+        data: decode(input, base)?,
+        container: Container::Plain { base },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{encode, encode_armored, encode_tagged};
+
+    const MAX_BASE: u8 = 94;
+
+    fn random_data() -> Vec<u8> {
+        (0..100).map(|_| rand::random::<u8>()).collect()
+    }
+
+    #[test]
+    fn test_armored() {
+        for base in 2..=MAX_BASE {
+            let data = random_data();
+            let text = format!("Here:\n{}\n", encode_armored(&data, base, "KEY"));
+            let decoded = decode_any(&text, Some(94)).unwrap();
+            assert_eq!(decoded.data, data);
+            assert_eq!(
+                decoded.container,
+                Container::Armored {
+                    label: "KEY".into(),
+                    base
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_tagged() {
+        for base in 2..=MAX_BASE {
+            let data = random_data();
+            let text = format!("  {}\n", encode_tagged(&data, base));
+            let decoded = decode_any(&text, None).unwrap();
+            assert_eq!(decoded.data, data);
+            assert_eq!(decoded.container, Container::Tagged { base });
+        }
+        assert!(matches!(
+            decode_any("~294abc", Some(94)),
+            Err(DecodeError::UnsupportedVersion { version: b'2' })
+        ));
+    }
+
+    #[test]
+    fn test_plain() {
+        for base in 2..=MAX_BASE {
+            let mut data = random_data();
+            data[0] = 0;
+            let encoded = encode(&data, base);
+            let decoded = decode_any(&format!("{}\r\n", encoded), Some(base)).unwrap();
+            assert_eq!(decoded.data, data);
+            assert_eq!(decoded.container, Container::Plain { base });
+        }
+        assert_eq!(decode_any("", Some(94)).unwrap().data, b"");
+        assert!(matches!(
+            decode_any("~1", Some(94)),
+            Ok(Decoded {
+                container: Container::Plain { base: 94 },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            decode_any(&encode(b"Hello", 94), None),
+            Err(DecodeError::UnknownFormat)
+        ));
+        assert!(matches!(
+            decode_any("", None),
+            Err(DecodeError::UnknownFormat)
+        ));
+
+        let armored = encode_armored(b"Hello", 94, "MESSAGE");
+        assert!(matches!(
+            decode_any(&armored.replace("END MESSAGE", "END KEY"), Some(94)),
+            Err(DecodeError::Armor(err)) if matches!(*err, ArmorError::LabelMismatch { .. })
+        ));
+        assert!(matches!(
+            decode_any(&armored.replace("Base: 94", "Base: 10"), None),
+            Err(DecodeError::DigitOutOfRange { base: 10, .. })
+        ));
+    }
+}
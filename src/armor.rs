@@ -0,0 +1,251 @@
+//! PEM-style ASCII armor for pasting encoded data into emails, tickets, and chat.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use thiserror::Error;
+
+use crate::{decode, encode_wrapped, DecodeError, LineEnding};
+
+/// The number of characters per body line.
+const LINE_WIDTH: usize = 64;
+
+/// The dashes around the BEGIN and END lines.
+const DASHES: &str = "-----";
+
+/// The header that records the base of the body.
+const BASE_HEADER: &str = "Base";
+
+/// Errors that can occur while reading armored text or an [`ArmoredFile`](crate::ArmoredFile).
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ArmorError {
+    #[error("No BEGIN line found")]
+    MissingBegin,
+    #[error("No '-----END {label}-----' line found")]
+    MissingEnd { label: String },
+    #[error("BEGIN label {begin:?} does not match END label {end:?}")]
+    LabelMismatch { begin: String, end: String },
+    #[error("Missing 'Base:' header")]
+    MissingBase,
+    #[error("Invalid header {line:?}")]
+    InvalidHeader { line: String },
+    #[error("Armored file ends before its 'end' and checksum lines")]
+    Truncated,
+    #[error(transparent)]
+    Decode(#[from] DecodeError),
+}
+
+/// Encodes `data` in `base` inside a PEM-style block:
+///
+/// ```text
+/// -----BEGIN LABEL-----
+/// Base: 94
+///
+/// <body, wrapped at 64 characters>
+/// -----END LABEL-----
+/// ```
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+/// * `label` - The name of the block, such as `MESSAGE` or `PRIVATE KEY`.
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94), or if
+/// `label` contains a line break or `-----`.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_armored, encode_armored};
+///
+/// let armored = encode_armored(b"Hello, World!", 94, "MESSAGE");
+/// assert!(armored.starts_with("-----BEGIN MESSAGE-----\nBase: 94\n"));
+///
+/// let pasted = format!("See below:\n\n{}\nThanks!", armored);
+/// let (label, data) = decode_armored(&pasted).unwrap();
+/// assert_eq!(label, "MESSAGE");
+/// assert_eq!(data, b"Hello, World!");
+/// ```
+pub fn encode_armored(data: &[u8], base: u8, label: &str) -> String {
+    if label.contains(['\n', '\r']) || label.contains(DASHES) {
+        panic!("Invalid armor label {:?}", label);
+    }
+
+    let body = encode_wrapped(data, base, LINE_WIDTH, LineEnding::Lf);
+    let mut out = String::with_capacity(body.len() + 2 * label.len() + 64);
+    out.push_str(&format!("{DASHES}BEGIN {label}{DASHES}\n"));
+    out.push_str(&format!("{BASE_HEADER}: {base}\n\n"));
+    if !body.is_empty() {
+        out.push_str(&body);
+        out.push('\n');
+    }
+    out.push_str(&format!("{DASHES}END {label}{DASHES}\n"));
+    out
+}
+
+/// Finds the first block written by [`encode_armored`] in `text` and decodes it.
+///
+/// Anything before the BEGIN line and after the END line is ignored. Inside the block, blank
+/// lines and leading or trailing whitespace are skipped, so text that has been reflowed or
+/// quoted with added spaces still decodes. Headers are the lines containing `": "`, which
+/// cannot occur in the body, and unknown headers are ignored.
+///
+/// # Returns
+///
+/// The label and the decoded bytes, or an `ArmorError` if:
+/// - There is no BEGIN line, or no END line after it.
+/// - The END label differs from the BEGIN label.
+/// - The `Base` header is missing or not a number.
+/// - The body does not decode in that base. Error positions then count characters of the
+///   body with its line breaks removed.
+pub fn decode_armored(text: &str) -> Result<(String, Vec<u8>), ArmorError> {
+    let (label, _, data) = decode_armored_block(text)?;
+    Ok((label, data))
+}
+
+/// Decodes like [`decode_armored`], also returning the base from the `Base` header.
+pub(crate) fn decode_armored_block(text: &str) -> Result<(String, u8, Vec<u8>), ArmorError> {
+    let mut lines = text.lines().map(str::trim);
+    let label = lines
+        .by_ref()
+        .find_map(|line| marker(line, "BEGIN "))
+        .ok_or(ArmorError::MissingBegin)?;
+
+    let mut base = None;
+    let mut body = String::new();
+    let mut end = None;
+    for line in lines.by_ref() {
+        if let Some(found) = marker(line, "END ") {
+            end = Some(found);
+            break;
+        }
+        match line.split_once(": ") {
+            Some((name, value)) if body.is_empty() => {
+                if name.eq_ignore_ascii_case(BASE_HEADER) {
+                    let value = value.trim().parse::<u8>();
+                    base = Some(value.map_err(|_| ArmorError::InvalidHeader {
+                        line: line.to_string(),
+                    })?);
+                }
+            }
+            _ => body.push_str(line),
+        }
+    }
+
+    match end {
+        None => Err(ArmorError::MissingEnd {
+            label: label.to_string(),
+        }),
+        Some(end) if end != label => Err(ArmorError::LabelMismatch {
+            begin: label.to_string(),
+            end: end.to_string(),
+        }),
+        Some(_) => {
+            let base = base.ok_or(ArmorError::MissingBase)?;
+            Ok((label.to_string(), base, decode(&body, base)?))
+        }
+    }
+}
+
+/// Returns whether `text` contains a BEGIN line written by [`encode_armored`].
+pub(crate) fn has_begin(text: &str) -> bool {
+    text.lines()
+        .any(|line| marker(line.trim(), "BEGIN ").is_some())
+}
+
+/// Returns the label of `line` if it is `-----<kind><label>-----`.
+fn marker<'a>(line: &'a str, kind: &str) -> Option<&'a str> {
+    line.strip_prefix(DASHES)?
+        .strip_prefix(kind)?
+        .strip_suffix(DASHES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_round_trip() {
+        for base in 2..=MAX_BASE {
+            for len in [0, 1, 47, 48, 200] {
+                let mut data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+                data.push(0);
+                let armored = encode_armored(&data, base, "TEST DATA");
+                assert!(armored.lines().all(|line| line.len() <= LINE_WIDTH));
+                let (label, decoded) = decode_armored(&armored).unwrap();
+                assert_eq!(label, "TEST DATA");
+                assert_eq!(decoded, data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_format() {
+        let armored = encode_armored(&[], 94, "EMPTY");
+        assert_eq!(
+            armored,
+            "-----BEGIN EMPTY-----\nBase: 94\n\n-----END EMPTY-----\n"
+        );
+        assert_eq!(decode_armored(&armored).unwrap().1, b"");
+    }
+
+    #[test]
+    fn test_reflowed_text() {
+        let data = (0..300).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+        let armored = encode_armored(&data, 94, "MESSAGE");
+        let reflowed = armored
+            .lines()
+            .map(|line| format!("  {}   ", line))
+            .collect::<Vec<String>>()
+            .join("\r\n\r\n\n");
+        let text = format!(
+            "Hi,\n> here it is:\n\n{}\n\n-- \nSent from my phone\n",
+            reflowed
+        );
+        assert_eq!(
+            decode_armored(&text).unwrap(),
+            ("MESSAGE".to_string(), data)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let armored = encode_armored(b"Hello, World!", 50, "MESSAGE");
+        assert!(matches!(
+            decode_armored("Hello, World!"),
+            Err(ArmorError::MissingBegin)
+        ));
+        assert!(matches!(
+            decode_armored(&armored.replace("-----END MESSAGE-----", "")),
+            Err(ArmorError::MissingEnd { label }) if label == "MESSAGE"
+        ));
+        assert!(matches!(
+            decode_armored(&armored.replace("END MESSAGE", "END KEY")),
+            Err(ArmorError::LabelMismatch { begin, end }) if begin == "MESSAGE" && end == "KEY"
+        ));
+        assert!(matches!(
+            decode_armored(&armored.replace("Base: 50", "")),
+            Err(ArmorError::MissingBase)
+        ));
+        assert!(matches!(
+            decode_armored(&armored.replace("Base: 50", "Base: fifty")),
+            Err(ArmorError::InvalidHeader { line }) if line == "Base: fifty"
+        ));
+        assert!(matches!(
+            decode_armored(&armored.replace("Base: 50", "Base: 10")),
+            Err(ArmorError::Decode(DecodeError::DigitOutOfRange { .. }))
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_label() {
+        encode_armored(b"", 94, "A-----B");
+    }
+}
@@ -0,0 +1,208 @@
+//! Fixed-capacity output in `heapless` containers, for targets without an allocator.
+//!
+//! The conversion here works byte by byte on stack buffers sized by the capacity `N`, so it
+//! needs neither `alloc` nor a big-number backend. It is quadratic in `N`, which is fine for
+//! the keys, hashes, and identifiers these functions are meant for.
+
+use heapless::{CapacityError, String, Vec};
+
+use crate::{digit_value, is_valid_base, trailing_zeros, DecodeError, EncodeError, CHARACTERS};
+
+/// Encodes a slice of bytes like [`encode`](crate::encode) into a string of capacity `N`.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Returns
+///
+/// The encoded string, or `CapacityError` if the encoding is longer than `N` characters.
+/// [`encoded_len`](crate::encoded_len) gives a capacity that is always large enough.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode_to_arraystring, encoded_len};
+///
+/// let key = [0xAB; 16];
+/// let encoded = encode_to_arraystring::<{ encoded_len(16, 94) }>(&key, 94).unwrap();
+/// assert!(encode_to_arraystring::<4>(&key, 94).is_err());
+/// # #[cfg(feature = "alloc")]
+/// assert_eq!(encoded, base94::encode(&key, 94).as_str());
+/// ```
+pub fn encode_to_arraystring<const N: usize>(
+    data: &[u8],
+    base: u8,
+) -> Result<String<N>, CapacityError> {
+    if !is_valid_base(base) {
+        panic!("{}", EncodeError::InvalidBase { base });
+    }
+
+    let zeros = trailing_zeros(data, 0);
+    let value = &data[..data.len() - zeros];
+    // Every byte of the value takes at least one digit, so a longer value cannot fit.
+    if value.len() > N {
+        return Err(CapacityError::default());
+    }
+
+    let mut number = [0u8; N];
+    number[..value.len()].copy_from_slice(value);
+    let mut len = value.len();
+
+    let mut text = Vec::<u8, N>::new();
+    while len > 0 {
+        let mut rem = 0u32;
+        for byte in number[..len].iter_mut().rev() {
+            let cur = (rem << 8) | *byte as u32;
+            *byte = (cur / base as u32) as u8;
+            rem = cur % base as u32;
+        }
+        while len > 0 && number[len - 1] == 0 {
+            len -= 1;
+        }
+        text.push(CHARACTERS[rem as usize])
+            .map_err(|_| CapacityError::default())?;
+    }
+    text.resize(text.len() + zeros, CHARACTERS[0])?;
+
+    Ok(String::from_utf8(text).expect("CHARACTERS is ASCII"))
+}
+
+/// Decodes a string like [`decode`](crate::decode) into a vector of capacity `N`.
+///
+/// # Arguments
+///
+/// * `encoded` - A Base94-encoded string to be decoded.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `DecodeError` under the same conditions as `decode`, or
+/// `DecodeError::CapacityExceeded` if they are longer than `N` bytes.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_to_arrayvec, encode_to_arraystring, DecodeError};
+///
+/// let encoded = encode_to_arraystring::<20>(&[1, 2, 3, 0], 94).unwrap();
+/// assert_eq!(decode_to_arrayvec::<4>(&encoded, 94).unwrap(), [1, 2, 3, 0]);
+/// assert!(matches!(
+///     decode_to_arrayvec::<3>(&encoded, 94),
+///     Err(DecodeError::CapacityExceeded { capacity: 3 })
+/// ));
+/// ```
+pub fn decode_to_arrayvec<const N: usize>(
+    encoded: &str,
+    base: u8,
+) -> Result<Vec<u8, N>, DecodeError> {
+    if !is_valid_base(base) {
+        return Err(DecodeError::InvalidBase { base });
+    }
+    // Check every character first, so errors are reported in the same order as `decode`.
+    for i in 0..encoded.len() {
+        digit_value(&encoded.as_bytes()[i..], i, base)?;
+    }
+
+    let zeros = trailing_zeros(encoded.as_bytes(), CHARACTERS[0]);
+    let value = &encoded.as_bytes()[..encoded.len() - zeros];
+
+    let mut number = Vec::<u8, N>::new();
+    for i in (0..value.len()).rev() {
+        let mut carry = digit_value(&value[i..], i, base)? as u32;
+        for byte in number.iter_mut() {
+            let cur = *byte as u32 * base as u32 + carry;
+            *byte = cur as u8;
+            carry = cur >> 8;
+        }
+        if carry > 0 {
+            number
+                .push(carry as u8)
+                .map_err(|_| DecodeError::CapacityExceeded { capacity: N })?;
+        }
+    }
+    number
+        .resize(number.len() + zeros, 0)
+        .map_err(|_| DecodeError::CapacityExceeded { capacity: N })?;
+
+    Ok(number)
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_matches_encode_and_decode() {
+        for base in 2..=MAX_BASE {
+            for len in [0, 1, 5, 16, 40] {
+                let mut data = (0..len)
+                    .map(|_| rand::random::<u8>())
+                    .collect::<alloc::vec::Vec<u8>>();
+                data.resize(len + rand::random::<usize>() % 5, 0);
+                let encoded = encode_to_arraystring::<400>(&data, base).unwrap();
+                assert_eq!(encoded.as_str(), encode(&data, base));
+                let decoded = decode_to_arrayvec::<64>(&encoded, base).unwrap();
+                assert_eq!(decoded[..], decode(&encoded, base).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_exact_capacity_and_off_by_one() {
+        let data = [255; 16];
+        // The encoding of sixteen 0xFF bytes is 128 digits in base 2, 39 in base 10, 32 in
+        // base 16, 22 in base 64, and 20 in base 94.
+        macro_rules! check {
+            ($base:expr, $len:expr) => {
+                let encoded = encode_to_arraystring::<$len>(&data, $base).unwrap();
+                assert_eq!(encoded.len(), $len);
+                assert!(encode_to_arraystring::<{ $len - 1 }>(&data, $base).is_err());
+                assert_eq!(decode_to_arrayvec::<16>(&encoded, $base).unwrap()[..], data);
+                assert!(matches!(
+                    decode_to_arrayvec::<15>(&encoded, $base),
+                    Err(DecodeError::CapacityExceeded { capacity: 15 })
+                ));
+            };
+        }
+        check!(2, 128);
+        check!(10, 39);
+        check!(16, 32);
+        check!(64, 22);
+        check!(94, 20);
+    }
+
+    #[test]
+    fn test_trailing_zeros_count_against_capacity() {
+        let encoded = encode_to_arraystring::<3>(&[1, 0, 0], 94).unwrap();
+        assert_eq!(encoded, "\"!!");
+        assert!(encode_to_arraystring::<2>(&[1, 0, 0], 94).is_err());
+        assert!(matches!(
+            decode_to_arrayvec::<2>(&encoded, 94),
+            Err(DecodeError::CapacityExceeded { capacity: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_decode_errors_match_decode() {
+        assert!(matches!(
+            decode_to_arrayvec::<8>("!! ~", 94),
+            Err(DecodeError::InvalidCharacter {
+                c: ' ',
+                position: 2
+            })
+        ));
+        assert!(matches!(
+            decode_to_arrayvec::<8>("!!", 95),
+            Err(DecodeError::InvalidBase { base: 95 })
+        ));
+    }
+}
@@ -0,0 +1,349 @@
+//! Tokio adapters built on the streaming [`Encoder`] and [`Decoder`].
+//!
+//! These mirror [`EncodeWriter`](crate::EncodeWriter) and
+//! [`DecodeReader`](crate::DecodeReader) and use the same chunked block format with
+//! [`STREAM_CHUNK_SIZE`](crate::STREAM_CHUNK_SIZE) byte blocks.
+
+use std::io;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::{DecodeError, Decoder, Encoder};
+
+/// The most input bytes a single call to `poll_write` accepts.
+const MAX_WRITE: usize = 8192;
+
+/// The number of encoded bytes [`AsyncDecodeReader`] requests from its inner reader at a
+/// time.
+const READ_SIZE: usize = 8192;
+
+/// An async writer that encodes everything written to it and writes the text to an inner
+/// writer.
+///
+/// Shutting the writer down, e.g. with
+/// [`AsyncWriteExt::shutdown`](tokio::io::AsyncWriteExt::shutdown), emits the final partial
+/// block before shutting down the inner writer. Dropping it without a shutdown loses that
+/// block.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_chunked, AsyncEncodeWriter, STREAM_CHUNK_SIZE};
+/// use tokio::io::AsyncWriteExt;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut writer = AsyncEncodeWriter::new(Vec::new(), 94);
+/// writer.write_all(b"Hello, World!").await.unwrap();
+/// writer.shutdown().await.unwrap();
+///
+/// let encoded = String::from_utf8(writer.into_inner()).unwrap();
+/// let decoded = decode_chunked(&encoded, 94, STREAM_CHUNK_SIZE).unwrap();
+/// assert_eq!(decoded, b"Hello, World!");
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct AsyncEncodeWriter<W> {
+    inner: W,
+    encoder: Encoder,
+    /// Encoded text that has not been written to `inner` yet.
+    buffer: String,
+    /// How much of `buffer` has already been written.
+    written: usize,
+    /// Whether the final block has been moved into `buffer`.
+    finalized: bool,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncEncodeWriter<W> {
+    /// Creates a writer that encodes in `base` and writes the text to `inner`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the specified base is outside the valid range (2 to 94).
+    pub fn new(inner: W, base: u8) -> Self {
+        Self {
+            inner,
+            encoder: Encoder::new(base),
+            buffer: String::new(),
+            written: 0,
+            finalized: false,
+        }
+    }
+
+    /// Returns a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer. Writing to it directly corrupts the
+    /// encoded output.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the inner writer, discarding any text that has not been written yet.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes the buffered text to the inner writer.
+    fn poll_write_buffer(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.written < self.buffer.len() {
+            let pending = &self.buffer.as_bytes()[self.written..];
+            let n = ready!(Pin::new(&mut self.inner).poll_write(cx, pending))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write the encoded text",
+                )));
+            }
+            self.written += n;
+        }
+        self.buffer.clear();
+        self.written = 0;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for AsyncEncodeWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_buffer(cx))?;
+        let buf = &buf[..buf.len().min(MAX_WRITE)];
+        this.buffer.push_str(this.encoder.push(buf));
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_buffer(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_buffer(cx))?;
+        if !this.finalized {
+            this.buffer = this.encoder.finalize_in_place();
+            this.finalized = true;
+            ready!(this.poll_write_buffer(cx))?;
+        }
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
+
+/// An async reader that decodes the encoded text produced by an inner reader.
+///
+/// Decoding errors are returned as [`io::Error`]s of kind [`io::ErrorKind::InvalidData`]
+/// that wrap the [`DecodeError`](crate::DecodeError). After a decoding error, every later
+/// read returns the same error again.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode_chunked, AsyncDecodeReader, STREAM_CHUNK_SIZE};
+/// use tokio::io::AsyncReadExt;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let encoded = encode_chunked(b"Hello, World!", 94, STREAM_CHUNK_SIZE);
+/// let mut decoded = Vec::new();
+/// AsyncDecodeReader::new(encoded.as_bytes(), 94)
+///     .read_to_end(&mut decoded)
+///     .await
+///     .unwrap();
+/// assert_eq!(decoded, b"Hello, World!");
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct AsyncDecodeReader<R> {
+    inner: R,
+    decoder: Decoder,
+    /// Encoded text read from `inner`.
+    input: Vec<u8>,
+    /// Decoded bytes that have not been returned yet, starting at `consumed`.
+    output: Vec<u8>,
+    consumed: usize,
+    /// Whether the inner reader has reached the end and the final block was decoded.
+    finished: bool,
+    /// The first decoding error, after which the decoder's state is not usable.
+    error: Option<DecodeError>,
+}
+
+impl<R: AsyncRead + Unpin> AsyncDecodeReader<R> {
+    /// Creates a reader that decodes the text from `inner` in `base`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the specified base is outside the valid range (2 to 94).
+    pub fn new(inner: R, base: u8) -> Self {
+        Self {
+            inner,
+            decoder: Decoder::new(base),
+            input: vec![0; READ_SIZE],
+            output: Vec::new(),
+            consumed: 0,
+            finished: false,
+            error: None,
+        }
+    }
+
+    /// Returns a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader. Reading from it directly corrupts
+    /// the decoded output.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader, discarding any buffered data.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncDecodeReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if let Some(err) = &this.error {
+            return Poll::Ready(Err(err.clone().into()));
+        }
+        while this.consumed == this.output.len() && !this.finished && buf.remaining() > 0 {
+            let mut input = ReadBuf::new(&mut this.input);
+            ready!(Pin::new(&mut this.inner).poll_read(cx, &mut input))?;
+            let n = input.filled().len();
+
+            let decoded = if n == 0 {
+                this.finished = true;
+                this.decoder.finalize_in_place()
+            } else {
+                this.decoder.push_bytes(&this.input[..n])
+            };
+            this.output = decoded.inspect_err(|err| this.error = Some(err.clone()))?;
+            this.consumed = 0;
+        }
+
+        let n = buf.remaining().min(this.output.len() - this.consumed);
+        buf.put_slice(&this.output[this.consumed..this.consumed + n]);
+        this.consumed += n;
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{encode_chunked, STREAM_CHUNK_SIZE};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn test_duplex_round_trip() {
+        let data = (0..3_000_000)
+            .map(|_| rand::random::<u8>())
+            .collect::<Vec<u8>>();
+        // A small duplex buffer forces many partial, pending writes and reads.
+        let (client, server) = tokio::io::duplex(1000);
+
+        let writer = {
+            let data = data.clone();
+            async move {
+                let mut writer = AsyncEncodeWriter::new(client, 94);
+                for piece in data.chunks(12_345) {
+                    writer.write_all(piece).await.unwrap();
+                }
+                writer.shutdown().await.unwrap();
+            }
+        };
+        let reader = async move {
+            let mut decoded = Vec::new();
+            AsyncDecodeReader::new(server, 94)
+                .read_to_end(&mut decoded)
+                .await
+                .unwrap();
+            decoded
+        };
+
+        let ((), decoded) = tokio::join!(writer, reader);
+        assert_eq!(decoded, data);
+    }
+
+    #[tokio::test]
+    async fn test_output_matches_sync_encoding() {
+        for base in [2, 10, 94] {
+            let data = (0..10_000)
+                .map(|_| rand::random::<u8>())
+                .collect::<Vec<u8>>();
+            let mut writer = AsyncEncodeWriter::new(Vec::new(), base);
+            writer.write_all(&data).await.unwrap();
+            writer.shutdown().await.unwrap();
+            assert_eq!(
+                writer.into_inner(),
+                encode_chunked(&data, base, STREAM_CHUNK_SIZE).into_bytes()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_data_error() {
+        let mut encoded = encode_chunked(&[7; 1000], 94, STREAM_CHUNK_SIZE).into_bytes();
+        encoded[900] = b' ';
+        let error = AsyncDecodeReader::new(&encoded[..], 94)
+            .read_to_end(&mut Vec::new())
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let error = error
+            .into_inner()
+            .unwrap()
+            .downcast::<DecodeError>()
+            .unwrap();
+        assert!(matches!(
+            *error,
+            DecodeError::InvalidCharacter {
+                c: ' ',
+                position: 900
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_sticky() {
+        let mut encoded = encode_chunked(&[7; 100], 94, STREAM_CHUNK_SIZE).into_bytes();
+        encoded[45] = b' ';
+        let mut reader = AsyncDecodeReader::new(&encoded[..], 94);
+        let first = reader.read_to_end(&mut Vec::new()).await.unwrap_err();
+        let first = first
+            .into_inner()
+            .unwrap()
+            .downcast::<DecodeError>()
+            .unwrap();
+        assert!(matches!(
+            *first,
+            DecodeError::InvalidCharacter {
+                c: ' ',
+                position: 45
+            }
+        ));
+        for _ in 0..3 {
+            let again = reader.read(&mut [0; 64]).await.unwrap_err();
+            assert_eq!(again.kind(), io::ErrorKind::InvalidData);
+            let again = again
+                .into_inner()
+                .unwrap()
+                .downcast::<DecodeError>()
+                .unwrap();
+            assert_eq!(again, first);
+        }
+    }
+}
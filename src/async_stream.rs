@@ -0,0 +1,217 @@
+//! `futures` stream adapters built on the streaming [`Encoder`] and [`Decoder`].
+//!
+//! Like the tokio adapters, they use the chunked block format with
+//! [`STREAM_CHUNK_SIZE`](crate::STREAM_CHUNK_SIZE) byte blocks, so where the input items
+//! begin and end has no effect on the concatenated output.
+
+use std::io;
+
+use bytes::Bytes;
+use futures_util::stream::{self, Stream, StreamExt};
+
+use crate::{Decoder, Encoder};
+
+/// Encodes a stream of byte chunks in `base`, yielding the text of each block as soon as it
+/// is complete.
+///
+/// Items that complete no block yield nothing, so the output can have fewer items than the
+/// input. The concatenated output equals `encode_chunked(&data, base, STREAM_CHUNK_SIZE)` for
+/// the concatenated input. An error from `stream` is passed on and ends the output stream.
+///
+/// # Arguments
+///
+/// * `stream` - The bytes to encode, in chunks of any size.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_stream, encode_stream};
+/// use bytes::Bytes;
+/// use futures::{executor, stream, TryStreamExt};
+///
+/// let chunks = ["Hello", ", ", "World!"].map(|s| Ok(Bytes::from(s)));
+/// let encoded = encode_stream(stream::iter(chunks), 94);
+/// let decoded = decode_stream(encoded, 94).try_collect::<Vec<Bytes>>();
+/// assert_eq!(executor::block_on(decoded).unwrap().concat(), b"Hello, World!");
+/// ```
+pub fn encode_stream<S>(stream: S, base: u8) -> impl Stream<Item = io::Result<String>>
+where
+    S: Stream<Item = io::Result<Bytes>>,
+{
+    let encoder = Encoder::new(base);
+    stream::unfold(Some((Box::pin(stream), encoder)), |state| async move {
+        let (mut stream, mut encoder) = state?;
+        loop {
+            match stream.next().await {
+                Some(Ok(bytes)) => {
+                    let text = encoder.push(&bytes);
+                    if !text.is_empty() {
+                        let text = text.to_owned();
+                        return Some((Ok(text), Some((stream, encoder))));
+                    }
+                }
+                Some(Err(err)) => return Some((Err(err), None)),
+                None => {
+                    let text = encoder.finalize();
+                    return (!text.is_empty()).then_some((Ok(text), None));
+                }
+            }
+        }
+    })
+}
+
+/// Decodes a stream of encoded text in `base`, the reverse of [`encode_stream`], yielding
+/// the bytes of each block as soon as it is complete.
+///
+/// Items that complete no block yield nothing. An error from `stream`, or a
+/// [`DecodeError`](crate::DecodeError) converted into an `io::Error` of kind `InvalidData`,
+/// is yielded as the last item. Positions in decode errors count from the start of the
+/// concatenated text.
+///
+/// # Arguments
+///
+/// * `stream` - The encoded text, in chunks of any size.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+pub fn decode_stream<S>(stream: S, base: u8) -> impl Stream<Item = io::Result<Bytes>>
+where
+    S: Stream<Item = io::Result<String>>,
+{
+    let decoder = Decoder::new(base);
+    stream::unfold(Some((Box::pin(stream), decoder)), |state| async move {
+        let (mut stream, mut decoder) = state?;
+        loop {
+            match stream.next().await {
+                Some(Ok(text)) => match decoder.push(&text) {
+                    Ok(bytes) if bytes.is_empty() => {}
+                    Ok(bytes) => return Some((Ok(Bytes::from(bytes)), Some((stream, decoder)))),
+                    Err(err) => return Some((Err(err.into()), None)),
+                },
+                Some(Err(err)) => return Some((Err(err), None)),
+                None => {
+                    return match decoder.finalize() {
+                        Ok(bytes) if bytes.is_empty() => None,
+                        Ok(bytes) => Some((Ok(Bytes::from(bytes)), None)),
+                        Err(err) => Some((Err(err.into()), None)),
+                    };
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{encode_chunked, DecodeError, STREAM_CHUNK_SIZE};
+    use futures::executor::block_on;
+
+    const MAX_BASE: u8 = 94;
+
+    /// Splits `data` into items of the given sizes, repeated until the data runs out.
+    fn split<T>(data: &[u8], sizes: &[usize], item: impl Fn(&[u8]) -> T) -> Vec<io::Result<T>> {
+        let mut items = Vec::new();
+        let mut rest = data;
+        for &size in sizes.iter().cycle() {
+            if rest.is_empty() {
+                break;
+            }
+            let (head, tail) = rest.split_at(size.min(rest.len()));
+            items.push(Ok(item(head)));
+            rest = tail;
+        }
+        items
+    }
+
+    fn collect<T>(stream: impl Stream<Item = io::Result<T>>) -> Vec<io::Result<T>> {
+        block_on(stream.collect())
+    }
+
+    #[test]
+    fn test_round_trip_with_tiny_items() {
+        for base in 2..=MAX_BASE {
+            let data = (0..200).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let items = split(&data, &[0, 1, 0, 0, 1, 3, 1], Bytes::copy_from_slice);
+
+            let encoded = collect(encode_stream(stream::iter(items), base))
+                .into_iter()
+                .map(Result::unwrap)
+                .collect::<Vec<String>>();
+            assert!(encoded.iter().all(|text| !text.is_empty()));
+            let text = encoded.concat();
+            assert_eq!(text, encode_chunked(&data, base, STREAM_CHUNK_SIZE));
+
+            let items = split(text.as_bytes(), &[1, 0, 1, 0, 0, 2], |s: &[u8]| {
+                String::from_utf8(s.to_vec()).unwrap()
+            });
+            let decoded = collect(decode_stream(stream::iter(items), base))
+                .into_iter()
+                .map(Result::unwrap)
+                .collect::<Vec<Bytes>>();
+            assert_eq!(decoded.concat(), data);
+        }
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let encoded = collect(encode_stream(
+            stream::iter((0..3).map(|_| Ok(Bytes::new()))),
+            94,
+        ));
+        assert!(encoded.is_empty());
+        let decoded = collect(decode_stream(
+            stream::iter((0..3).map(|_| Ok(String::new()))),
+            94,
+        ));
+        assert!(decoded.is_empty());
+    }
+
+    #[test]
+    fn test_inner_error_ends_stream() {
+        let items = vec![
+            Ok(Bytes::from(vec![1; 40])),
+            Err(io::Error::other("broken pipe")),
+            Ok(Bytes::from(vec![2; 40])),
+        ];
+        let encoded = collect(encode_stream(stream::iter(items), 94));
+        assert_eq!(encoded.len(), 2);
+        assert!(encoded[0].is_ok());
+        assert_eq!(encoded[1].as_ref().unwrap_err().to_string(), "broken pipe");
+    }
+
+    #[test]
+    fn test_decode_error_ends_stream() {
+        let mut text = encode_chunked(&[0xab; 100], 94, STREAM_CHUNK_SIZE);
+        text.replace_range(90..91, " ");
+        let items = split(text.as_bytes(), &[1], |s: &[u8]| {
+            String::from_utf8(s.to_vec()).unwrap()
+        });
+        let decoded = collect(decode_stream(stream::iter(items), 94));
+        let (last, blocks) = decoded.split_last().unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks.iter().all(Result::is_ok));
+        let err = last.as_ref().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.get_ref().unwrap().downcast_ref::<DecodeError>(),
+            Some(&DecodeError::InvalidCharacter {
+                c: ' ',
+                position: 90
+            })
+        );
+
+        // A stream that ends in the middle of a block fails when it ends.
+        let truncated = text[..46].to_owned();
+        let decoded = collect(decode_stream(stream::iter([Ok(truncated)]), 94));
+        assert_eq!(decoded.len(), 2);
+        assert!(decoded[1].is_err());
+    }
+}
@@ -0,0 +1,189 @@
+//! Bitcoin's Base58 and Base58Check.
+//!
+//! Base58 treats the input as a big-endian number and prints it most significant digit
+//! first, with every leading zero byte becoming a leading `1`. This is
+//! [`encode_big_endian_with_alphabet`] with the [`BASE58_BITCOIN`] alphabet.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::presets::BASE58_BITCOIN;
+use crate::{decode_big_endian_with_alphabet, encode_big_endian_with_alphabet, DecodeError};
+
+/// Encodes `data` in Bitcoin's Base58, with one leading `1` per leading zero byte.
+///
+/// # Examples
+///
+/// ```
+/// use base94::encode_base58;
+///
+/// assert_eq!(encode_base58(b"abc"), "ZiCa");
+/// assert_eq!(encode_base58(&[0, 0, 0x61]), "112g");
+/// ```
+pub fn encode_base58(data: &[u8]) -> String {
+    encode_big_endian_with_alphabet(data, &BASE58_BITCOIN)
+}
+
+/// Decodes a string produced by [`encode_base58`] or any other Bitcoin Base58 encoder.
+///
+/// # Returns
+///
+/// The decoded bytes, or `DecodeError::InvalidCharacter` if `encoded` contains a character
+/// outside [`BASE58_BITCOIN`], such as `0`, `O`, `I`, or `l`.
+pub fn decode_base58(encoded: &str) -> Result<Vec<u8>, DecodeError> {
+    decode_big_endian_with_alphabet(encoded, &BASE58_BITCOIN)
+}
+
+/// Encodes `payload` in Base58Check: Base58 of the payload followed by the first 4 bytes of
+/// its double SHA-256 hash. Bitcoin addresses and WIF keys put a version byte at the start
+/// of the payload.
+///
+/// # Examples
+///
+/// ```
+/// use base94::encode_base58check;
+///
+/// // The address of the genesis block's coinbase output.
+/// let mut payload = vec![0x00];
+/// payload.extend_from_slice(&[
+///     0x62, 0xe9, 0x07, 0xb1, 0x5c, 0xbf, 0x27, 0xd5, 0x42, 0x53, 0x99, 0xeb, 0xf6, 0xf0, 0xfb,
+///     0x50, 0xeb, 0xb8, 0x8f, 0x18,
+/// ]);
+/// assert_eq!(encode_base58check(&payload), "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa");
+/// ```
+#[cfg(feature = "base58check")]
+pub fn encode_base58check(payload: &[u8]) -> String {
+    let mut data = Vec::with_capacity(payload.len() + 4);
+    data.extend_from_slice(payload);
+    data.extend_from_slice(&checksum(payload));
+    encode_base58(&data)
+}
+
+/// Decodes a Base58Check string and verifies its checksum.
+///
+/// # Returns
+///
+/// The payload without the checksum, or a `DecodeError` if:
+/// - `encoded` contains a character outside [`BASE58_BITCOIN`].
+/// - The decoded data is shorter than the checksum, with `DecodeError::InvalidLength`.
+/// - The checksum does not match, with `DecodeError::ChecksumMismatch`.
+#[cfg(feature = "base58check")]
+pub fn decode_base58check(encoded: &str) -> Result<Vec<u8>, DecodeError> {
+    let mut data = decode_base58(encoded)?;
+    let Some(split) = data.len().checked_sub(4) else {
+        return Err(DecodeError::InvalidLength {
+            length: encoded.len(),
+        });
+    };
+    let expected = u32::from_be_bytes(data[split..].try_into().expect("4 bytes"));
+    let actual = u32::from_be_bytes(checksum(&data[..split]));
+    if expected != actual {
+        return Err(DecodeError::ChecksumMismatch { expected, actual });
+    }
+    data.truncate(split);
+    Ok(data)
+}
+
+/// Returns the first 4 bytes of the double SHA-256 hash of `payload`.
+#[cfg(feature = "base58check")]
+fn checksum(payload: &[u8]) -> [u8; 4] {
+    use sha2::{Digest, Sha256};
+
+    let hash = Sha256::digest(Sha256::digest(payload));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// From Bitcoin Core's `base58_encode_decode.json`.
+    const VECTORS: &[(&[u8], &str)] = &[
+        (b"", ""),
+        (&[0x61], "2g"),
+        (&[0x62, 0x62, 0x62], "a3gV"),
+        (&[0x63, 0x63, 0x63], "aPEr"),
+        (b"simply a long string", "2cFupjhnEsSn59qHXstmK2ffpLv2"),
+        (
+            &[
+                0x00, 0xeb, 0x15, 0x23, 0x1d, 0xfc, 0xeb, 0x60, 0x92, 0x58, 0x86, 0xb6, 0x7d, 0x06,
+                0x52, 0x99, 0x92, 0x59, 0x15, 0xae, 0xb1, 0x72, 0xc0, 0x66, 0x47,
+            ],
+            "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L",
+        ),
+        (&[0x51, 0x6b, 0x6f, 0xcd, 0x0f], "ABnLTmg"),
+        (
+            &[0xbf, 0x4f, 0x89, 0x00, 0x1e, 0x67, 0x02, 0x74, 0xdd],
+            "3SEo3LWLoPntC",
+        ),
+        (&[0x57, 0x2e, 0x47, 0x94], "3EFU7m"),
+        (
+            &[0xec, 0xac, 0x89, 0xca, 0xd9, 0x39, 0x23, 0xc0, 0x23, 0x21],
+            "EJDM8drfXA6uyA",
+        ),
+        (&[0x10, 0xc8, 0x51, 0x1e], "Rt5zm"),
+        (&[0; 10], "1111111111"),
+    ];
+
+    #[test]
+    fn test_vectors() {
+        for &(data, encoded) in VECTORS {
+            assert_eq!(encode_base58(data), encoded);
+            assert_eq!(decode_base58(encoded).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_leading_zeros() {
+        for zeros in 0..20 {
+            for len in [0, 1, 16, 17, 50] {
+                let mut data = vec![0; zeros];
+                data.extend((0..len).map(|_| rand::random::<u8>().max(1)));
+                data.extend((0..len).map(|_| rand::random::<u8>()));
+                let encoded = encode_base58(&data);
+                assert_eq!(encoded.bytes().take_while(|&c| c == b'1').count(), zeros);
+                assert_eq!(decode_base58(&encoded).unwrap(), data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_character() {
+        for (encoded, c, position) in [("0", '0', 0), ("2gO", 'O', 2), ("aI", 'I', 1)] {
+            assert!(matches!(
+                decode_base58(encoded),
+                Err(DecodeError::InvalidCharacter { c: found, position: at })
+                    if found == c && at == position
+            ));
+        }
+    }
+
+    #[cfg(feature = "base58check")]
+    #[test]
+    fn test_base58check() {
+        let genesis = decode_base58check("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa").unwrap();
+        assert_eq!(genesis.len(), 21);
+        assert_eq!(genesis[0], 0);
+        assert_eq!(genesis[1..3], [0x62, 0xe9]);
+        assert_eq!(
+            encode_base58check(&genesis),
+            "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"
+        );
+
+        for len in 0..50 {
+            let payload = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let encoded = encode_base58check(&payload);
+            assert_eq!(decode_base58check(&encoded).unwrap(), payload);
+        }
+
+        assert!(matches!(
+            decode_base58check("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb"),
+            Err(DecodeError::ChecksumMismatch { expected, actual: 0xc29b7d93 })
+                if expected != 0xc29b7d93
+        ));
+        assert!(matches!(
+            decode_base58check("2g"),
+            Err(DecodeError::InvalidLength { length: 2 })
+        ));
+    }
+}
@@ -0,0 +1,213 @@
+//! Encoding and decoding between `bytes` buffers, built on the streaming [`Encoder`] and
+//! [`Decoder`].
+//!
+//! The functions walk the chunks of a [`Buf`] one at a time, so chained or otherwise
+//! non-contiguous buffers are never flattened into one slice first. Like the streaming types,
+//! they use the chunked block format with [`STREAM_CHUNK_SIZE`](crate::STREAM_CHUNK_SIZE)
+//! byte blocks.
+
+use bytes::{Buf, BufMut, BytesMut};
+
+use crate::{is_valid_base, DecodeError, Decoder, Encoder};
+
+/// Encodes the remaining bytes of `src` into `dst` in the streaming format.
+///
+/// The text written equals `encode_chunked(&data, base, STREAM_CHUNK_SIZE)` for the bytes of
+/// `src` laid end to end. `src` is consumed.
+///
+/// # Arguments
+///
+/// * `src` - The buffer to encode, which may consist of any number of chunks.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+/// * `dst` - The buffer the encoded text is appended to.
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94), or if
+/// `dst` runs out of space.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_buf, encode_buf};
+/// use bytes::{Buf, Bytes, BytesMut};
+///
+/// let data = Bytes::from_static(b"Hello, ").chain(Bytes::from_static(b"World!"));
+/// let mut encoded = BytesMut::new();
+/// encode_buf(data, 94, &mut encoded);
+///
+/// let mut decoded = BytesMut::new();
+/// decode_buf(encoded.freeze(), 94, &mut decoded).unwrap();
+/// assert_eq!(decoded, &b"Hello, World!"[..]);
+/// ```
+pub fn encode_buf(mut src: impl Buf, base: u8, dst: &mut impl BufMut) {
+    let mut encoder = Encoder::new(base);
+    while src.has_remaining() {
+        let chunk = src.chunk();
+        let len = chunk.len();
+        dst.put_slice(encoder.push(chunk).as_bytes());
+        src.advance(len);
+    }
+    dst.put_slice(encoder.finalize().as_bytes());
+}
+
+/// Decodes the remaining streaming-format text of `src` and appends the bytes to `dst`.
+///
+/// `src` is consumed. The text does not need to be valid UTF-8: bytes that are not ASCII
+/// are reported as invalid characters, with U+FFFD standing for a character that is split
+/// between two chunks.
+///
+/// # Arguments
+///
+/// * `src` - The encoded text, which may consist of any number of chunks.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+/// * `dst` - The buffer the decoded bytes are appended to.
+///
+/// # Returns
+///
+/// `Ok(())`, or the `DecodeError` that `decode_chunked` would report for the whole text,
+/// with positions counted from the start of `src`. On error, `dst` is left as it was.
+pub fn decode_buf(mut src: impl Buf, base: u8, dst: &mut BytesMut) -> Result<(), DecodeError> {
+    if !is_valid_base(base) {
+        return Err(DecodeError::InvalidBase { base });
+    }
+
+    let start = dst.len();
+    let mut decoder = Decoder::new(base);
+    let result = (|| {
+        while src.has_remaining() {
+            let chunk = src.chunk();
+            let len = chunk.len();
+            dst.extend_from_slice(&decoder.push_bytes(chunk)?);
+            src.advance(len);
+        }
+        dst.extend_from_slice(&decoder.finalize()?);
+        Ok(())
+    })();
+    if result.is_err() {
+        dst.truncate(start);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode_chunked, encode_chunked, STREAM_CHUNK_SIZE};
+    use bytes::Bytes;
+
+    const MAX_BASE: u8 = 94;
+
+    /// Splits `data` into a chain of separately allocated segments of the given sizes,
+    /// repeated until the data runs out.
+    fn chained(data: &[u8], sizes: &[usize]) -> Box<dyn Buf> {
+        let mut buf: Box<dyn Buf> = Box::new(Bytes::new());
+        let mut rest = data;
+        for &size in sizes.iter().cycle() {
+            if rest.is_empty() {
+                break;
+            }
+            let (segment, tail) = rest.split_at(size.min(rest.len()));
+            buf = Box::new(buf.chain(Bytes::copy_from_slice(segment)));
+            rest = tail;
+        }
+        buf
+    }
+
+    #[test]
+    fn test_round_trip_over_chained_buffers() {
+        for base in 2..=MAX_BASE {
+            let data = (0..300).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let src = chained(&data, &[1, 7, 33, 2, 65]);
+            assert!(src.chunk().len() < src.remaining());
+
+            let mut encoded = BytesMut::new();
+            encode_buf(src, base, &mut encoded);
+            let expected = encode_chunked(&data, base, STREAM_CHUNK_SIZE);
+            assert_eq!(encoded, expected.as_bytes());
+
+            let mut decoded = BytesMut::from(&b"prefix"[..]);
+            decode_buf(chained(&encoded, &[3, 1, 50, 11]), base, &mut decoded).unwrap();
+            assert_eq!(&decoded[..6], b"prefix");
+            assert_eq!(&decoded[6..], &data[..]);
+        }
+    }
+
+    #[test]
+    fn test_encode_into_vec() {
+        let mut encoded = Vec::new();
+        encode_buf(chained(b"Hello, World!", &[5, 3]), 94, &mut encoded);
+        assert_eq!(
+            encoded,
+            encode_chunked(b"Hello, World!", 94, STREAM_CHUNK_SIZE).as_bytes()
+        );
+
+        let mut empty = Vec::new();
+        encode_buf(Bytes::new(), 94, &mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_errors_use_stream_positions() {
+        let mut encoded = encode_chunked(&[0xab; 100], 94, STREAM_CHUNK_SIZE).into_bytes();
+        encoded[90] = b' ';
+        let mut decoded = BytesMut::from(&b"kept"[..]);
+        let error = decode_buf(chained(&encoded, &[13, 4]), 94, &mut decoded).unwrap_err();
+        assert_eq!(
+            error,
+            decode_chunked(
+                core::str::from_utf8(&encoded).unwrap(),
+                94,
+                STREAM_CHUNK_SIZE
+            )
+            .unwrap_err()
+        );
+        assert_eq!(error.position(), Some(90));
+        assert_eq!(decoded, &b"kept"[..]);
+
+        // A full block of 40 characters, then 6, which no final block length produces.
+        let truncated = &encode_chunked(&[1; 40], 94, STREAM_CHUNK_SIZE)[..46];
+        assert_eq!(
+            decode_buf(chained(truncated.as_bytes(), &[9]), 94, &mut decoded),
+            Err(decode_chunked(truncated, 94, STREAM_CHUNK_SIZE).unwrap_err())
+        );
+        assert_eq!(decoded, &b"kept"[..]);
+    }
+
+    #[test]
+    fn test_split_multibyte_character() {
+        let text = "!!\u{e9}!!".as_bytes();
+        let error = decode_buf(chained(text, &[3]), 94, &mut BytesMut::new()).unwrap_err();
+        assert_eq!(
+            error,
+            DecodeError::InvalidCharacter {
+                c: char::REPLACEMENT_CHARACTER,
+                position: 2
+            }
+        );
+        let error = decode_buf(chained(text, &[5]), 94, &mut BytesMut::new()).unwrap_err();
+        assert_eq!(
+            error,
+            DecodeError::InvalidCharacter {
+                c: '\u{e9}',
+                position: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_base() {
+        for base in [0, 1, 95] {
+            assert_eq!(
+                decode_buf(Bytes::new(), base, &mut BytesMut::new()),
+                Err(DecodeError::InvalidBase { base })
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_encode_invalid_base() {
+        encode_buf(Bytes::new(), 95, &mut BytesMut::new());
+    }
+}
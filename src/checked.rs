@@ -0,0 +1,184 @@
+//! Encoding with a CRC-32 suffix that catches copying mistakes.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{
+    decode_digits, encode, is_valid_base, len, parse_digits, wipe, DecodeError, CHARACTERS,
+};
+
+/// Encodes a slice of bytes like [`encode`], followed by the CRC-32 of `data`.
+///
+/// The checksum is written least significant digit first in exactly
+/// [`encoded_len(4, base)`](crate::encoded_len) characters, so the boundary between the
+/// data and the checksum depends only on the base.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_checked, encode, encode_checked, encoded_len, DecodeError};
+///
+/// let encoded = encode_checked(b"Hello, World!", 94);
+/// assert_eq!(encoded.len(), encode(b"Hello, World!", 94).len() + encoded_len(4, 94));
+/// assert_eq!(decode_checked(&encoded, 94).unwrap(), b"Hello, World!");
+///
+/// let mut typo = encoded.into_bytes();
+/// typo[3] = if typo[3] == b'x' { b'y' } else { b'x' };
+/// let typo = String::from_utf8(typo).unwrap();
+/// assert!(matches!(decode_checked(&typo, 94), Err(DecodeError::ChecksumMismatch { .. })));
+/// ```
+pub fn encode_checked(data: &[u8], base: u8) -> String {
+    let mut out = encode(data, base);
+    let mut crc = crc32(data);
+    for _ in 0..checksum_width(base) {
+        out.push(CHARACTERS[(crc % base as u32) as usize] as char);
+        crc /= base as u32;
+    }
+    out
+}
+
+/// Decodes a string produced by [`encode_checked`] and verifies its checksum.
+///
+/// # Arguments
+///
+/// * `encoded` - The string to decode.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `DecodeError` if:
+/// - `encoded` is invalid for `base` as in [`decode`](crate::decode).
+/// - `encoded` is shorter than the checksum, with `DecodeError::TruncatedInput`.
+/// - The checksum suffix does not fit in 32 bits, with `DecodeError::Overflow`.
+/// - The checksum does not match the data, with `DecodeError::ChecksumMismatch`.
+pub fn decode_checked(encoded: &str, base: u8) -> Result<Vec<u8>, DecodeError> {
+    if !is_valid_base(base) {
+        return Err(DecodeError::InvalidBase { base });
+    }
+    let Some(split) = encoded.len().checked_sub(checksum_width(base)) else {
+        return Err(DecodeError::TruncatedInput);
+    };
+
+    let mut digits = parse_digits(encoded.as_bytes(), base)?;
+    let expected = digits[split..].iter().rev().try_fold(0u32, |crc, &digit| {
+        crc.checked_mul(base as u32)?.checked_add(digit as u32)
+    });
+    let data = decode_digits(&digits[..split], base);
+    wipe(&mut digits);
+
+    let expected = expected.ok_or(DecodeError::Overflow { bits: 32 })?;
+    let actual = crc32(&data);
+    if expected != actual {
+        return Err(DecodeError::ChecksumMismatch { expected, actual });
+    }
+    Ok(data)
+}
+
+/// Returns the number of digits in `base` that every CRC-32 value fits in.
+fn checksum_width(base: u8) -> usize {
+    len::max_digits(32, base)
+}
+
+/// Computes the CRC-32 (IEEE 802.3) of `data`.
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                crc >> 1 ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn test_checksum_width() {
+        for base in 2..=MAX_BASE {
+            let width = checksum_width(base);
+            assert!((base as u64).pow(width as u32) > u32::MAX as u64);
+            assert!((base as u64).pow(width as u32 - 1) <= u32::MAX as u64);
+        }
+        assert_eq!(checksum_width(2), 32);
+        assert_eq!(checksum_width(94), 5);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for base in 2..=MAX_BASE {
+            for data in [&b"Hello, World!"[..], &[], &[0], &[1, 0, 0], &[255; 40]] {
+                let encoded = encode_checked(data, base);
+                assert_eq!(
+                    encoded.len(),
+                    encode(data, base).len() + checksum_width(base)
+                );
+                assert_eq!(decode_checked(&encoded, base).unwrap(), data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_flipped_character() {
+        for base in 2..=MAX_BASE {
+            let data = (0..20).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let encoded = encode_checked(&data, base);
+            for position in 0..encoded.len() {
+                let mut flipped = encoded.clone().into_bytes();
+                let digit = crate::digit_index(flipped[position]).unwrap();
+                flipped[position] = CHARACTERS[((digit + 1) % base) as usize];
+                let flipped = String::from_utf8(flipped).unwrap();
+                assert!(matches!(
+                    decode_checked(&flipped, base),
+                    Err(DecodeError::ChecksumMismatch { .. } | DecodeError::Overflow { bits: 32 })
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            decode_checked("abcd", 94),
+            Err(DecodeError::TruncatedInput)
+        ));
+        assert!(matches!(
+            decode_checked("~~~~~", 94),
+            Err(DecodeError::Overflow { bits: 32 })
+        ));
+        assert!(matches!(
+            decode_checked("ab c!!!!!", 94),
+            Err(DecodeError::InvalidCharacter {
+                c: ' ',
+                position: 2
+            })
+        ));
+        assert!(matches!(
+            decode_checked("!!!!!", 95),
+            Err(DecodeError::InvalidBase { base: 95 })
+        ));
+    }
+}
@@ -0,0 +1,429 @@
+//! Block-based encoding that runs in linear time.
+//!
+//! The whole-number encoding in [`encode`](crate::encode) treats the entire input as one big
+//! integer, which makes it superlinear in the input length. The chunked format instead splits
+//! the input into blocks of `chunk_size` bytes and encodes every block independently as a
+//! fixed-width group of digits, so the cost per block is constant.
+//!
+//! A full block of `chunk_size` bytes always takes `block_width(chunk_size)` digits, the
+//! smallest width that can hold any value of that many bytes. The final block may be shorter
+//! than `chunk_size`; it is encoded with the width for its own length. For every base in the
+//! valid range the width grows strictly with the byte length, so the length of the final
+//! group identifies the length of the final block and decoding is unambiguous.
+//!
+//! Chunked output is not compatible with the whole-number format, and both sides must agree
+//! on the same base and chunk size.
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::len::{digit_bits, max_digits};
+use crate::{from_digits, is_valid_base, parse_digits, to_digits, DecodeError, CHARACTERS};
+
+/// Encodes a slice of bytes using the chunked block format.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+/// * `chunk_size` - The number of input bytes per block, e.g. 32 or 64.
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94) or if
+/// `chunk_size` is zero.
+///
+/// # Returns
+///
+/// The concatenated fixed-width encodings of every block.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_chunked, encode_chunked};
+///
+/// let data = b"Hello, World!";
+/// let encoded = encode_chunked(data, 94, 4);
+/// assert_eq!(decode_chunked(&encoded, 94, 4).unwrap(), data);
+/// ```
+pub fn encode_chunked(data: &[u8], base: u8, chunk_size: usize) -> String {
+    assert!(
+        is_valid_base(base),
+        "Invalid base {}, must be in the range 2..=94",
+        base
+    );
+    assert!(chunk_size > 0, "Chunk size must be greater than 0");
+
+    let full_width = block_width(chunk_size, base);
+    let mut out = String::with_capacity(data.len() / chunk_size * full_width + full_width);
+    encode_blocks(data, base, chunk_size, full_width, &mut out);
+    out
+}
+
+/// Decodes a string produced by [`encode_chunked`] back to its original bytes.
+///
+/// # Arguments
+///
+/// * `encoded` - A string produced by `encode_chunked`.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+/// * `chunk_size` - The chunk size used for encoding.
+///
+/// # Panics
+///
+/// This function panics if `chunk_size` is zero.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `DecodeError` if the input contains invalid characters, its length
+/// cannot be split into blocks, or a block holds a value too large for its byte length.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_chunked, encode_chunked};
+///
+/// let encoded = encode_chunked(&[0, 1, 2, 3, 4], 16, 2);
+/// assert_eq!(decode_chunked(&encoded, 16, 2).unwrap(), [0, 1, 2, 3, 4]);
+/// ```
+pub fn decode_chunked(encoded: &str, base: u8, chunk_size: usize) -> Result<Vec<u8>, DecodeError> {
+    assert!(chunk_size > 0, "Chunk size must be greater than 0");
+    let digits = parse_digits(encoded.as_bytes(), base)?;
+
+    let (full_width, last_len) = layout(digits.len(), base, chunk_size)?;
+
+    let mut out = Vec::with_capacity(digits.len() / full_width * chunk_size + last_len);
+    decode_blocks(&digits, base, chunk_size, full_width, last_len, 0, &mut out)?;
+    Ok(out)
+}
+
+/// The number of blocks handed to a single rayon task by the parallel functions.
+#[cfg(feature = "parallel")]
+const BLOCKS_PER_TASK: usize = 1024;
+
+/// Encodes a slice of bytes using the chunked block format, spreading the blocks across
+/// rayon's thread pool.
+///
+/// The output is identical to [`encode_chunked`] with the same arguments, regardless of the
+/// number of threads.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+/// * `chunk_size` - The number of input bytes per block, e.g. 32 or 64.
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94) or if
+/// `chunk_size` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode_chunked, encode_chunked_parallel};
+///
+/// let data = vec![42; 100_000];
+/// assert_eq!(encode_chunked_parallel(&data, 94, 32), encode_chunked(&data, 94, 32));
+/// ```
+#[cfg(feature = "parallel")]
+pub fn encode_chunked_parallel(data: &[u8], base: u8, chunk_size: usize) -> String {
+    use rayon::prelude::*;
+
+    assert!(
+        is_valid_base(base),
+        "Invalid base {}, must be in the range 2..=94",
+        base
+    );
+    assert!(chunk_size > 0, "Chunk size must be greater than 0");
+
+    let full_width = block_width(chunk_size, base);
+    // Every task starts on a block boundary, so the pieces concatenate to the sequential
+    // output.
+    let task_size = chunk_size.saturating_mul(BLOCKS_PER_TASK);
+    data.par_chunks(task_size)
+        .map(|part| {
+            let mut out = String::with_capacity(part.len() / chunk_size * full_width + full_width);
+            encode_blocks(part, base, chunk_size, full_width, &mut out);
+            out
+        })
+        .collect::<Vec<String>>()
+        .concat()
+}
+
+/// Decodes a string produced by [`encode_chunked`] or [`encode_chunked_parallel`], spreading
+/// the blocks across rayon's thread pool.
+///
+/// The result, including the error reported for invalid input, is identical to
+/// [`decode_chunked`] with the same arguments.
+///
+/// # Arguments
+///
+/// * `encoded` - A string produced by `encode_chunked`.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+/// * `chunk_size` - The chunk size used for encoding.
+///
+/// # Panics
+///
+/// This function panics if `chunk_size` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_chunked_parallel, encode_chunked_parallel};
+///
+/// let data = vec![42; 100_000];
+/// let encoded = encode_chunked_parallel(&data, 94, 32);
+/// assert_eq!(decode_chunked_parallel(&encoded, 94, 32).unwrap(), data);
+/// ```
+#[cfg(feature = "parallel")]
+pub fn decode_chunked_parallel(
+    encoded: &str,
+    base: u8,
+    chunk_size: usize,
+) -> Result<Vec<u8>, DecodeError> {
+    use rayon::prelude::*;
+
+    assert!(chunk_size > 0, "Chunk size must be greater than 0");
+    let digits = parse_digits(encoded.as_bytes(), base)?;
+    let (full_width, last_len) = layout(digits.len(), base, chunk_size)?;
+
+    let task_width = full_width * BLOCKS_PER_TASK;
+    let parts = digits
+        .par_chunks(task_width)
+        .enumerate()
+        .map(|(i, part)| {
+            let mut out = Vec::with_capacity(chunk_size * BLOCKS_PER_TASK);
+            decode_blocks(
+                part,
+                base,
+                chunk_size,
+                full_width,
+                last_len,
+                i * task_width,
+                &mut out,
+            )
+            .map(|()| out)
+        })
+        .collect::<Vec<_>>();
+
+    // Reporting the first failing part keeps the error the same as the sequential one.
+    let mut out = Vec::with_capacity(digits.len() / full_width * chunk_size + last_len);
+    for part in parts {
+        out.extend_from_slice(&part?);
+    }
+    Ok(out)
+}
+
+/// Encodes the consecutive blocks of `data` into `out`. Every block but the last must be
+/// `chunk_size` bytes long.
+pub(crate) fn encode_blocks(
+    data: &[u8],
+    base: u8,
+    chunk_size: usize,
+    full_width: usize,
+    out: &mut String,
+) {
+    for block in data.chunks(chunk_size) {
+        let width = if block.len() == chunk_size {
+            full_width
+        } else {
+            block_width(block.len(), base)
+        };
+        let digits = to_digits(block, base);
+        out.extend(
+            digits
+                .iter()
+                .map(|&digit| CHARACTERS[digit as usize] as char),
+        );
+        out.extend(core::iter::repeat_n(
+            CHARACTERS[0] as char,
+            width - digits.len(),
+        ));
+    }
+}
+
+/// Returns the width of a full block and the byte length of the final partial block for an
+/// encoding of `length` digits.
+pub(crate) fn layout(
+    length: usize,
+    base: u8,
+    chunk_size: usize,
+) -> Result<(usize, usize), DecodeError> {
+    let full_width = block_width(chunk_size, base);
+    let last_width = length % full_width;
+    let last_len = if last_width == 0 {
+        0
+    } else {
+        block_len(last_width, base).ok_or(DecodeError::InvalidLength { length })?
+    };
+    Ok((full_width, last_len))
+}
+
+/// Decodes the consecutive digit groups in `digits` into `out`. `offset` is the position of
+/// the first digit within the whole encoding, used for error positions.
+pub(crate) fn decode_blocks(
+    digits: &[u8],
+    base: u8,
+    chunk_size: usize,
+    full_width: usize,
+    last_len: usize,
+    offset: usize,
+    out: &mut Vec<u8>,
+) -> Result<(), DecodeError> {
+    for (i, group) in digits.chunks(full_width).enumerate() {
+        let len = if group.len() == full_width {
+            chunk_size
+        } else {
+            last_len
+        };
+        let bytes = from_digits(group, base);
+        if bytes.len() > len {
+            return Err(DecodeError::BlockOverflow {
+                position: offset + i * full_width,
+            });
+        }
+        out.extend_from_slice(&bytes);
+        out.resize(out.len() + len - bytes.len(), 0);
+    }
+    Ok(())
+}
+
+/// Returns the smallest number of digits in `base` that can represent every `len`-byte value,
+/// i.e. the smallest `width` with `base^width >= 256^len`.
+pub(crate) fn block_width(len: usize, base: u8) -> usize {
+    let bits = 8 * len as u64;
+    let fits = |width: usize| {
+        // `base^width` is the number whose only nonzero digit is a 1 at position `width`.
+        let mut digits = vec![0; width + 1];
+        digits[width] = 1;
+        let power = from_digits(&digits, base);
+        let top = power[power.len() - 1];
+        (power.len() as u64 - 1) * 8 + (8 - top.leading_zeros() as u64) > bits
+    };
+
+    // Start from the fixed-point estimate and correct it exactly.
+    let mut width = max_digits(bits as u128, base);
+    while width > 0 && fits(width - 1) {
+        width -= 1;
+    }
+    while !fits(width) {
+        width += 1;
+    }
+    width
+}
+
+/// Finds the byte length whose block width is exactly `width`, if there is one.
+fn block_len(width: usize, base: u8) -> Option<usize> {
+    let estimate = (digit_bits(width, base) / 8) as usize;
+    (estimate.saturating_sub(1)..=estimate + 1)
+        .find(|&len| len > 0 && block_width(len, base) == width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_encode_decode_chunked() {
+        for base in 2..=MAX_BASE {
+            for chunk_size in [1, 7, 32, 64] {
+                for len in [
+                    0,
+                    1,
+                    chunk_size - 1,
+                    chunk_size,
+                    chunk_size + 1,
+                    3 * chunk_size,
+                ] {
+                    let data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+                    let encoded = encode_chunked(&data, base, chunk_size);
+                    let decoded = decode_chunked(&encoded, base, chunk_size).unwrap();
+                    assert_eq!(decoded, data);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_decode_chunked_zeros_and_max() {
+        for base in 2..=MAX_BASE {
+            for data in [vec![0; 100], vec![255; 100]] {
+                let encoded = encode_chunked(&data, base, 32);
+                assert_eq!(decode_chunked(&encoded, base, 32).unwrap(), data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_block_width_is_strictly_increasing() {
+        for base in 2..=MAX_BASE {
+            for len in 1..=64 {
+                assert!(block_width(len, base) > block_width(len - 1, base));
+                assert_eq!(block_len(block_width(len, base), base), Some(len));
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_matches_sequential() {
+        // Several tasks per call are slow to check for every base.
+        for base in [2, 10, 16, 58, 64, 85, MAX_BASE] {
+            for chunk_size in [1, 7, 32] {
+                let len = chunk_size * BLOCKS_PER_TASK * 3 + rand::random::<usize>() % 100;
+                let data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+                let encoded = encode_chunked_parallel(&data, base, chunk_size);
+                assert_eq!(encoded, encode_chunked(&data, base, chunk_size));
+                assert_eq!(
+                    decode_chunked_parallel(&encoded, base, chunk_size).unwrap(),
+                    data
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_reports_first_error() {
+        let data = vec![7; 32 * BLOCKS_PER_TASK * 4];
+        let mut encoded = encode_chunked(&data, 94, 32).into_bytes();
+        let width = block_width(32, 94);
+        // Overflow one block in the second task and one in the fourth.
+        for block in [BLOCKS_PER_TASK + 3, 3 * BLOCKS_PER_TASK + 1] {
+            encoded[block * width..(block + 1) * width].fill(b'~');
+        }
+        let encoded = String::from_utf8(encoded).unwrap();
+
+        let expected = (BLOCKS_PER_TASK + 3) * width;
+        assert!(matches!(
+            decode_chunked(&encoded, 94, 32),
+            Err(DecodeError::BlockOverflow { position }) if position == expected
+        ));
+        assert!(matches!(
+            decode_chunked_parallel(&encoded, 94, 32),
+            Err(DecodeError::BlockOverflow { position }) if position == expected
+        ));
+    }
+
+    #[test]
+    fn test_decode_chunked_invalid_length() {
+        // A full base-94 block of 4 bytes is 5 digits; a single byte needs 2 digits and three
+        // bytes need 4, but no byte length needs exactly 1 digit.
+        assert!(matches!(
+            decode_chunked("!!!!!!", 94, 4),
+            Err(DecodeError::InvalidLength { length: 6 })
+        ));
+    }
+
+    #[test]
+    fn test_decode_chunked_block_overflow() {
+        // "~~" is 94^2 - 1, which does not fit in a single byte.
+        assert!(matches!(
+            decode_chunked("!!!!!~~", 94, 4),
+            Err(DecodeError::BlockOverflow { position: 5 })
+        ));
+    }
+}
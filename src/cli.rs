@@ -1,49 +1,928 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
 use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, IsTerminal, Read, Write};
+use std::process::ExitCode;
 
-use clap::{CommandFactory, Parser, ValueEnum};
+use clap::{Parser, ValueEnum};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use sha2::{Digest, Sha256};
 
 #[derive(Parser)]
 #[clap(version, author, about)]
 struct Cli {
-    /// Whether to encode or decode the input.
+    /// Whether to encode or decode the input, or to check that it decodes without writing
+    /// the result anywhere.
     operation: Operation,
 
-    /// The input file to encode or decode.
-    input: String,
+    /// The input file to encode or decode. Reads standard input if omitted or `-`.
+    input: Option<String>,
 
-    /// The output file to write the result to.
-    output: String,
+    /// The output file to write the result to. Writes to standard output if omitted or `-`.
+    /// `verify` takes none and prints its report to standard output.
+    output: Option<String>,
 
     /// The base to use for encoding or decoding. Must be between 2 and 94 (inclusive).
     #[clap(short, long, default_value = "94")]
     base: u8,
+
+    /// The format of the input when decoding. `auto` detects armored and tagged input in its
+    /// first 64 KiB and decodes anything else as plain text in the base.
+    #[clap(short, long, value_enum, default_value = "auto")]
+    format: Format,
+
+    /// Break encoded output into lines of COLS characters. By default it is one line.
+    /// Decoding ignores line breaks, so wrapped input needs no option.
+    #[clap(short, long, value_name = "COLS", value_parser = clap::value_parser!(u64).range(1..))]
+    wrap: Option<u64>,
+
+    /// Use `\r\n` line breaks for `--wrap` and `--newline` instead of `\n`.
+    #[clap(long)]
+    crlf: bool,
+
+    /// End encoded output with a newline. Output to a terminal always gets one.
+    #[clap(short, long)]
+    newline: bool,
+
+    /// When decoding, skip every character that is not a digit of the base instead of
+    /// failing, and decode the rest as plain text. Only characters outside the alphabet of
+    /// the base can be told apart from the data.
+    #[clap(short, long)]
+    ignore_garbage: bool,
+
+    /// Read the whole input into memory and encode it as one number, as `base94::encode`
+    /// does, instead of streaming it through the chunked format block by block. Decoding
+    /// with it reads that format back.
+    #[clap(long)]
+    whole_file: bool,
+
+    /// The number of bytes read from the input and buffered for the output at a time.
+    #[clap(long, value_name = "BYTES", default_value = "65536", value_parser = clap::value_parser!(u64).range(1..))]
+    buffer_size: u64,
+
+    /// Decode the input exactly as given, without removing a leading byte order mark or
+    /// the line breaks in it.
+    #[clap(long)]
+    strict: bool,
+
+    /// With `verify`, also print the SHA-256 digest of the decoded data.
+    #[clap(long)]
+    sha256: bool,
+
+    /// With `verify`, check that the decoded data is identical to FILE, and report the first
+    /// byte that differs if not.
+    #[clap(long, value_name = "FILE")]
+    against: Option<String>,
+
+    /// Show a progress bar on standard error, if it is a terminal. It is shown without this
+    /// flag for input files of at least 16 MiB.
+    #[clap(short, long)]
+    progress: bool,
+
+    /// Never show a progress bar.
+    #[clap(short, long, conflicts_with = "progress")]
+    quiet: bool,
+
+    /// Print details to standard error, such as how many characters `--ignore-garbage`
+    /// skipped.
+    #[clap(short, long)]
+    verbose: bool,
+}
+
+#[derive(Clone, ValueEnum)]
+enum Format {
+    Auto,
+    Plain,
+    Tagged,
+    Armored,
 }
 
 #[derive(Clone, ValueEnum)]
 enum Operation {
     Encode,
     Decode,
+    Verify,
+}
+
+/// Inputs at least this long that never use the highest digit of `--base` were most likely
+/// encoded in a smaller base; shorter ones often miss it by chance.
+const SMALLER_BASE_WARNING_LEN: usize = 512;
+
+/// Warns on stderr if the characters in `input` do not look like they were encoded in `base`.
+fn check_base(input: &[u8], base: u8) {
+    let Some(detected) = std::str::from_utf8(input)
+        .ok()
+        .and_then(base94::detect_base)
+    else {
+        return;
+    };
+    if detected > base {
+        eprintln!(
+            "warning: the input uses digits of base {} or higher, but the base is {}",
+            detected, base
+        );
+    } else if detected < base && input.len() >= SMALLER_BASE_WARNING_LEN {
+        eprintln!(
+            "warning: the input only uses digits of base {}, but the base is {}; \
+             decoding with a larger base than was used gives wrong data",
+            detected, base
+        );
+    }
+}
+
+/// Returns `err` with its position, if it has one, replaced by `position`.
+fn at_position(err: base94::DecodeError, position: usize) -> base94::DecodeError {
+    use base94::DecodeError;
+    match err {
+        DecodeError::InvalidCharacter { c, .. } => DecodeError::InvalidCharacter { c, position },
+        DecodeError::DigitOutOfRange { c, base, .. } => {
+            DecodeError::DigitOutOfRange { c, position, base }
+        }
+        DecodeError::BlockOverflow { .. } => DecodeError::BlockOverflow { position },
+        DecodeError::InvalidPadding { .. } => DecodeError::InvalidPadding { position },
+        err => err,
+    }
+}
+
+/// What is removed from the text of the input before it is decoded.
+#[derive(Clone, Copy)]
+enum Removed {
+    Nothing,
+    /// The whitespace around the text.
+    Whitespace,
+    /// The whitespace around the text and the line breaks within it, so wrapped output like
+    /// that of `encode --wrap` decodes as one string.
+    LineBreaks,
+}
+
+impl Removed {
+    fn apply(self, text: &str) -> Cow<'_, str> {
+        match self {
+            Removed::Nothing => Cow::Borrowed(text),
+            Removed::Whitespace => Cow::Borrowed(text.trim()),
+            Removed::LineBreaks => Cow::Owned(unwrap_lines(text)),
+        }
+    }
+
+    /// Returns the byte offset in `text` of the byte at `position` of `self.apply(text)`.
+    fn position_in(self, text: &str, position: usize) -> usize {
+        let (start, line_breaks) = match self {
+            Removed::Nothing => return position,
+            Removed::Whitespace => (text.len() - text.trim_start().len(), false),
+            Removed::LineBreaks => (text.len() - text.trim_start().len(), true),
+        };
+        let mut kept = 0;
+        let mut end = start;
+        for (i, &b) in text.as_bytes().iter().enumerate().skip(start) {
+            if line_breaks && matches!(b, b'\r' | b'\n') {
+                continue;
+            }
+            if kept == position {
+                return i;
+            }
+            kept += 1;
+            end = i + 1;
+        }
+        end
+    }
+}
+
+/// Renders a decode error in `removed.apply(text)` with the part of `text` it points at.
+/// `text` is the input from byte `offset` on, and the position shown is the offset in the
+/// input.
+fn with_source(
+    err: base94::DecodeError,
+    text: &str,
+    offset: usize,
+    removed: Removed,
+) -> Box<dyn std::error::Error> {
+    let Some(position) = err.position() else {
+        return err.into();
+    };
+    let err = at_position(err, offset + removed.position_in(text, position));
+    err.display_with_window(text, offset, 0, 0)
+        .to_string()
+        .into()
+}
+
+/// Removes the whitespace around `text` and the line breaks within it.
+fn unwrap_lines(text: &str) -> String {
+    text.trim().replace(['\r', '\n'], "")
+}
+
+/// Decodes `input`, the input from byte `offset` on, in `format`, falling back to plain text
+/// in `base`. Unless `strict` is set, line breaks are ignored.
+fn decode(
+    input: &[u8],
+    offset: usize,
+    base: u8,
+    format: &Format,
+    strict: bool,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let text = match (format, std::str::from_utf8(input)) {
+        (Format::Plain | Format::Auto, Err(_)) => {
+            check_base(input, base);
+            return base94::decode_bytes(input, base).map_err(|err| match err.position() {
+                Some(position) => at_position(err, offset + position).into(),
+                None => err.into(),
+            });
+        }
+        (_, Ok(text)) => text,
+        (_, Err(err)) => return Err(err.into()),
+    };
+    let lines = if strict {
+        Removed::Whitespace
+    } else {
+        Removed::LineBreaks
+    };
+    let plain = |removed: Removed| {
+        let input = removed.apply(text);
+        check_base(input.as_bytes(), base);
+        base94::decode(&input, base).map_err(|err| with_source(err, text, offset, removed))
+    };
+    Ok(match format {
+        Format::Plain if strict => plain(Removed::Nothing)?,
+        Format::Plain => plain(Removed::LineBreaks)?,
+        Format::Tagged => {
+            base94::decode_tagged(&lines.apply(text))
+                .map_err(|err| with_source(err, text, offset, lines))?
+                .0
+        }
+        Format::Armored => base94::decode_armored(text)?.1,
+        _ => {
+            // Errors in an armored body point into the body, not the whole text.
+            let armored = text.contains("-----BEGIN ");
+            let removed = if armored { Removed::Nothing } else { lines };
+            let prepared = removed.apply(text);
+            let decoded = base94::decode_any(&prepared, Some(base)).map_err(|err| {
+                if armored {
+                    err.into()
+                } else {
+                    with_source(err, text, offset, removed)
+                }
+            })?;
+            if let base94::Container::Plain { .. } = decoded.container {
+                check_base(prepared.as_bytes(), base);
+            }
+            decoded.data
+        }
+    })
+}
+
+/// Decodes `input` as plain text in `base`, skipping the characters that are not digits of
+/// the base. Unless `strict` is set, line breaks are not counted as skipped.
+fn decode_ignoring_garbage(
+    input: &[u8],
+    base: u8,
+    strict: bool,
+    verbose: bool,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    // Invalid UTF-8 becomes U+FFFD, which is skipped like any other garbage.
+    let text = String::from_utf8_lossy(input);
+    let text = if strict {
+        text.into_owned()
+    } else {
+        unwrap_lines(&text)
+    };
+    let (decoded, skipped) = base94::decode_lossy(&text, base);
+    report_skipped(
+        skipped as u64,
+        skipped == text.chars().count(),
+        base,
+        verbose,
+    )?;
+    Ok(decoded)
+}
+
+/// Reports the `skipped` characters of `--ignore-garbage`, failing if `all` of the input was
+/// skipped.
+fn report_skipped(
+    skipped: u64,
+    all: bool,
+    base: u8,
+    verbose: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if verbose {
+        eprintln!(
+            "skipped {} characters that are not digits in base {}",
+            skipped, base
+        );
+    }
+    if skipped > 0 && all {
+        return Err(format!("the input has no digits of base {}", base).into());
+    }
+    Ok(())
+}
+
+/// The UTF-8 byte order mark, which Notepad and other Windows editors start files with.
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// How much of the input is read ahead to look for a byte order mark and, with
+/// `--format auto`, for armored and tagged input, however small `--buffer-size` is.
+const PEEK_LEN: usize = 64 * 1024;
+
+/// Returns whether `head`, the start of the input, is armored or tagged, which `--format auto`
+/// decodes as a whole.
+fn is_container(head: &[u8]) -> bool {
+    is_tagged(head) || is_armored(head)
+}
+
+/// Returns whether `head` starts like a tagged string, with `~` and three decimal digits.
+fn is_tagged(head: &[u8]) -> bool {
+    let start = head
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(head.len());
+    head[start..]
+        .strip_prefix(b"~")
+        .and_then(|version| version.get(..3))
+        .is_some_and(|version| version.iter().all(u8::is_ascii_digit))
+}
+
+/// Returns whether `head` contains the start of an armor block.
+fn is_armored(head: &[u8]) -> bool {
+    head.windows(11).any(|window| window == b"-----BEGIN ")
+}
+
+/// A writer that breaks the text written to it into lines of `width` characters, with no
+/// line break after the last one.
+struct Wrap<W> {
+    inner: W,
+    width: Option<usize>,
+    line_ending: &'static [u8],
+    column: usize,
+}
+
+impl<W: Write> Write for Wrap<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let Some(width) = self.width else {
+            return self.inner.write(buf);
+        };
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.column == width {
+            self.inner.write_all(self.line_ending)?;
+            self.column = 0;
+        }
+        let written = self
+            .inner
+            .write(&buf[..buf.len().min(width - self.column)])?;
+        self.column += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A reader that drops the `\r` and `\n` characters of the text of an inner reader.
+struct SkipLineBreaks<R>(R);
+
+impl<R: Read> Read for SkipLineBreaks<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let read = self.0.read(buf)?;
+            if read == 0 {
+                return Ok(0);
+            }
+            let mut kept = 0;
+            for i in 0..read {
+                if !matches!(buf[i], b'\r' | b'\n') {
+                    buf[kept] = buf[i];
+                    kept += 1;
+                }
+            }
+            if kept > 0 {
+                return Ok(kept);
+            }
+        }
+    }
+}
+
+/// A reader that drops the bytes of an inner reader that are not digits of `base`, the
+/// streaming counterpart of `base94::decode_lossy`.
+struct SkipGarbage<R> {
+    inner: R,
+    base: u8,
+    /// The number of characters dropped, counting a multi-byte UTF-8 character once.
+    skipped: u64,
+    /// The number of digits kept.
+    kept: u64,
+}
+
+impl<R: Read> Read for SkipGarbage<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let read = self.inner.read(buf)?;
+            if read == 0 {
+                return Ok(0);
+            }
+            let mut kept = 0;
+            for i in 0..read {
+                let c = buf[i];
+                if base94::digit_index(c).is_some_and(|digit| digit < self.base) {
+                    buf[kept] = c;
+                    kept += 1;
+                } else if !(0x80..0xc0).contains(&c) {
+                    self.skipped += 1;
+                }
+            }
+            self.kept += kept as u64;
+            if kept > 0 {
+                return Ok(kept);
+            }
+        }
+    }
+}
+
+/// How many of the last bytes read are kept to find where in the input a streaming decode
+/// error is. The decoder only ever reads a few kilobytes ahead.
+const RECENT_LEN: usize = 64 * 1024;
+
+/// A reader that keeps the last bytes of an inner reader, so that the position of a decode
+/// error, which counts the bytes left after dropping line breaks and garbage, can be turned
+/// back into an offset in the input.
+struct Recent<R> {
+    inner: R,
+    /// Whether `\r` and `\n` are dropped before decoding.
+    line_breaks: bool,
+    /// The base whose non-digits `--ignore-garbage` drops before decoding.
+    garbage: Option<u8>,
+    window: VecDeque<u8>,
+    /// The number of bytes before `window`.
+    offset: u64,
+    /// The number of bytes before `window` that were not dropped.
+    passed: u64,
+    /// The number of `\n` before `window`.
+    line: u64,
+    /// The number of characters between the last `\n` before `window` and `window`.
+    column: u64,
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args = Cli::parse();
-    if args.base < 2 || args.base > 94 {
-        Cli::command()
-            .error(
-                clap::error::ErrorKind::InvalidValue,
-                "Base must be between 2 and 94 (inclusive)",
+impl<R> Recent<R> {
+    fn new(inner: R, line_breaks: bool, garbage: Option<u8>) -> Self {
+        Self {
+            inner,
+            line_breaks,
+            garbage,
+            window: VecDeque::new(),
+            offset: 0,
+            passed: 0,
+            line: 0,
+            column: 0,
+        }
+    }
+
+    /// Returns whether `b` reaches the decoder.
+    fn passes(&self, b: u8) -> bool {
+        !(self.line_breaks && matches!(b, b'\r' | b'\n'))
+            && self
+                .garbage
+                .is_none_or(|base| base94::digit_index(b).is_some_and(|digit| digit < base))
+    }
+
+    /// Returns the index in the window of the byte at `position` of what reached the
+    /// decoder, if it is still in the window.
+    fn locate(&self, position: u64) -> Option<usize> {
+        let mut passed = self.passed;
+        let mut end = 0;
+        for (i, &b) in self.window.iter().enumerate() {
+            if self.passes(b) {
+                if passed == position {
+                    return Some(i);
+                }
+                passed += 1;
+                end = i + 1;
+            }
+        }
+        (passed == position).then_some(end)
+    }
+
+    /// Moves the position of a decode error in `err` to the input, the bytes after the
+    /// first `offset` of which were read through `self`, and renders it with the window.
+    fn locate_error(
+        &mut self,
+        err: Box<dyn std::error::Error>,
+        offset: usize,
+    ) -> Box<dyn std::error::Error> {
+        let Some(decode_err) = err
+            .downcast_ref::<io::Error>()
+            .and_then(io::Error::get_ref)
+            .and_then(|err| err.downcast_ref::<base94::DecodeError>())
+        else {
+            return err;
+        };
+        let Some(index) = decode_err
+            .position()
+            .and_then(|position| self.locate(position as u64))
+        else {
+            return err;
+        };
+        let start = offset as u64 + self.offset;
+        let decode_err = at_position(decode_err.clone(), (start + index as u64) as usize);
+
+        // The window can start and end inside a character.
+        let window = self.window.make_contiguous();
+        let skip = window.iter().take_while(|&&b| b & 0xc0 == 0x80).count();
+        let text = match std::str::from_utf8(&window[skip..]) {
+            Ok(text) => text,
+            Err(err) => std::str::from_utf8(&window[skip..skip + err.valid_up_to()])
+                .expect("the prefix is valid"),
+        };
+        if index < skip || index > skip + text.len() {
+            return decode_err.into();
+        }
+        decode_err
+            .display_with_window(
+                text,
+                (start + skip as u64) as usize,
+                self.line as usize,
+                self.column as usize,
             )
-            .exit();
+            .to_string()
+            .into()
+    }
+}
+
+impl<R: Read> Read for Recent<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.window.extend(&buf[..read]);
+        let excess = self.window.len().saturating_sub(RECENT_LEN);
+        for i in 0..excess {
+            let b = self.window[i];
+            self.passed += u64::from(self.passes(b));
+            if b == b'\n' {
+                self.line += 1;
+                self.column = 0;
+            } else if b & 0xc0 != 0x80 {
+                self.column += 1;
+            }
+        }
+        self.window.drain(..excess);
+        self.offset += excess as u64;
+        Ok(read)
+    }
+}
+
+/// Input files at least this large get a progress bar without `--progress`.
+const PROGRESS_MIN_LEN: u64 = 16 * 1024 * 1024;
+
+/// Creates the progress bar for reading `len` bytes of input, or an unknown amount if `len`
+/// is `None`. It is hidden unless standard error is a terminal and `--progress` is given or
+/// the input is large.
+fn progress_bar(args: &Cli, len: Option<u64>) -> ProgressBar {
+    let wanted = args.progress || len.is_some_and(|len| len >= PROGRESS_MIN_LEN);
+    if args.quiet || !wanted || !io::stderr().is_terminal() {
+        return ProgressBar::hidden();
+    }
+
+    let (bar, template) = match len {
+        Some(len) => (
+            ProgressBar::new(len),
+            "{wide_bar} {binary_bytes}/{binary_total_bytes} {binary_bytes_per_sec} ETA {eta}",
+        ),
+        None => (
+            ProgressBar::new_spinner(),
+            "{spinner} {binary_bytes} {binary_bytes_per_sec} {elapsed}",
+        ),
+    };
+    bar.set_draw_target(ProgressDrawTarget::stderr());
+    bar.set_style(ProgressStyle::with_template(template).expect("the templates are valid"));
+    bar
+}
+
+/// Returns the path in `arg`, or `None` for standard input or output.
+fn path(arg: &Option<String>) -> Option<&str> {
+    arg.as_deref().filter(|&path| path != "-")
+}
+
+/// Encodes `input` in the chunked format, or as one number with `--whole-file`.
+fn run_encode(
+    args: &Cli,
+    mut input: impl Read,
+    output: impl Write,
+    terminal: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let line_ending = if args.crlf {
+        base94::LineEnding::CrLf
+    } else {
+        base94::LineEnding::Lf
+    };
+    let mut writer = Wrap {
+        inner: output,
+        width: args
+            .wrap
+            .map(|width| usize::try_from(width).unwrap_or(usize::MAX)),
+        line_ending: line_ending.as_str().as_bytes(),
+        column: 0,
+    };
+
+    if args.whole_file {
+        let mut data = Vec::new();
+        input.read_to_end(&mut data)?;
+        writer.write_all(base94::try_encode(&data, args.base)?.as_bytes())?;
+    } else {
+        let mut encoder = base94::EncodeWriter::new(writer, args.base);
+        io::copy(&mut input, &mut encoder)?;
+        writer = encoder.finish()?;
+    }
+
+    if args.newline || terminal {
+        writer.inner.write_all(writer.line_ending)?;
+    }
+    Ok(())
+}
+
+/// Decodes `input`, streaming plain text in the chunked format and reading anything else
+/// whole.
+fn run_decode(
+    args: &Cli,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // What is found at the start must not depend on how many bytes the first read returns.
+    let mut head = Vec::new();
+    input
+        .by_ref()
+        .take(PEEK_LEN as u64)
+        .read_to_end(&mut head)?;
+    let bom = if !args.strict && head.starts_with(BOM) {
+        BOM.len()
+    } else {
+        0
+    };
+    let head = &head[bom..];
+    let mut input = head.chain(input);
+
+    let whole = args.whole_file
+        || match (args.ignore_garbage, &args.format) {
+            (true, Format::Tagged | Format::Armored) => {
+                return Err("--ignore-garbage only applies to plain input".into())
+            }
+            (true, _) | (false, Format::Plain) => false,
+            (false, Format::Auto) => is_container(head),
+            (false, _) => true,
+        };
+    if !whole {
+        check_stream_base(args, head);
+        return decode_stream(args, input, bom, output);
+    }
+
+    let mut data = Vec::new();
+    input.read_to_end(&mut data)?;
+    let decoded = if args.ignore_garbage {
+        decode_ignoring_garbage(&data, args.base, args.strict, args.verbose)?
+    } else {
+        match decode(&data, bom, args.base, &args.format, args.strict) {
+            // Plain text starts like a tagged string about once in 80,000 times, so the
+            // output of `encode` has to decode even then.
+            Err(_)
+                if matches!(args.format, Format::Auto)
+                    && is_tagged(&data)
+                    && !is_armored(&data) =>
+            {
+                if args.verbose {
+                    eprintln!(
+                        "the input starts like a tagged string but is not one, \
+                         decoding it as plain text"
+                    );
+                }
+                if !args.whole_file {
+                    check_stream_base(args, &data[..data.len().min(PEEK_LEN)]);
+                    return decode_stream(args, &data[..], bom, output);
+                }
+                decode(&data, bom, args.base, &Format::Plain, args.strict)?
+            }
+            result => result?,
+        }
+    };
+    output.write_all(&decoded)?;
+    Ok(())
+}
+
+/// Runs [`check_base`] on `head`, the start of input that is decoded as it is read, without
+/// the line breaks the decoder does not see.
+fn check_stream_base(args: &Cli, head: &[u8]) {
+    if args.ignore_garbage {
+        return;
+    }
+    if args.strict {
+        check_base(head, args.base);
+    } else {
+        let digits = head
+            .iter()
+            .copied()
+            .filter(|b| !matches!(b, b'\r' | b'\n'))
+            .collect::<Vec<u8>>();
+        check_base(&digits, args.base);
     }
+}
 
-    let input = fs::read(&args.input)?;
+/// Decodes plain text in the chunked format as it is read. `input` is the input from byte
+/// `offset` on.
+fn decode_stream(
+    args: &Cli,
+    input: impl Read,
+    offset: usize,
+    output: impl Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut recent = Recent::new(
+        input,
+        !args.strict,
+        args.ignore_garbage.then_some(args.base),
+    );
+    copy_decoded(args, &mut recent, output).map_err(|err| recent.locate_error(err, offset))
+}
 
-    let output = match args.operation {
-        Operation::Encode => base94::encode(&input, args.base).as_bytes().to_vec(),
-        Operation::Decode => base94::decode(std::str::from_utf8(&input)?, args.base)?,
+/// Decodes plain text in the chunked format from `input` to `output`.
+fn copy_decoded(
+    args: &Cli,
+    input: impl Read,
+    mut output: impl Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Line breaks are dropped first, so `--ignore-garbage` does not count them.
+    let input: Box<dyn Read + '_> = if args.strict {
+        Box::new(input)
+    } else {
+        Box::new(SkipLineBreaks(input))
     };
+    if args.ignore_garbage {
+        let garbage = SkipGarbage {
+            inner: input,
+            base: args.base,
+            skipped: 0,
+            kept: 0,
+        };
+        let mut reader = base94::DecodeReader::new(garbage, args.base);
+        io::copy(&mut reader, &mut output)?;
+        let garbage = reader.get_ref();
+        report_skipped(garbage.skipped, garbage.kept == 0, args.base, args.verbose)?;
+    } else {
+        io::copy(
+            &mut base94::DecodeReader::new(input, args.base),
+            &mut output,
+        )?;
+    }
+    Ok(())
+}
+
+/// A writer that checks decoded data instead of storing it: it counts and hashes the bytes,
+/// and compares them to a reference file.
+struct Verifier<R> {
+    len: u64,
+    sha256: Option<Sha256>,
+    reference: Option<R>,
+    /// The offset of the first byte that differs from the reference.
+    mismatch: Option<u64>,
+}
+
+impl<R: BufRead> Verifier<R> {
+    /// Compares `buf`, the decoded bytes from offset `self.len` on, to the reference, and
+    /// records the offset of the first difference.
+    fn compare(&mut self, mut buf: &[u8]) -> io::Result<()> {
+        let Some(reference) = &mut self.reference else {
+            return Ok(());
+        };
+        let mut offset = self.len;
+        while !buf.is_empty() {
+            let expected = reference.fill_buf()?;
+            let n = expected.len().min(buf.len());
+            let differs = (0..n).position(|i| expected[i] != buf[i]);
+            if let Some(i) = differs.or((n == 0).then_some(0)) {
+                self.mismatch = Some(offset + i as u64);
+                return Err(io::Error::other(
+                    "the decoded data differs from the reference",
+                ));
+            }
+            reference.consume(n);
+            buf = &buf[n..];
+            offset += n as u64;
+        }
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Write for Verifier<R> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.compare(buf)?;
+        if let Some(sha256) = &mut self.sha256 {
+            sha256.update(buf);
+        }
+        self.len += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
 
-    fs::write(&args.output, output)?;
+/// Decodes `input` like `decode` but discards the bytes, and prints their length, their
+/// digest with `--sha256`, and whether they match the file of `--against`.
+fn run_verify(args: &Cli, input: impl BufRead) -> Result<(), Box<dyn std::error::Error>> {
+    let reference = match &args.against {
+        Some(path) => Some(BufReader::new(fs::File::open(path)?)),
+        None => None,
+    };
+    let mut verifier = Verifier {
+        len: 0,
+        sha256: args.sha256.then(Sha256::new),
+        reference,
+        mismatch: None,
+    };
+    let decoded = run_decode(args, input, &mut verifier);
 
+    let against = args.against.as_deref().unwrap_or_default();
+    if let Some(offset) = verifier.mismatch {
+        return Err(format!(
+            "the decoded data differs from {} at byte offset {}",
+            against, offset
+        )
+        .into());
+    }
+    decoded?;
+    if let Some(reference) = &mut verifier.reference {
+        if !reference.fill_buf()?.is_empty() {
+            return Err(format!(
+                "the decoded data ends at byte offset {}, before the end of {}",
+                verifier.len, against
+            )
+            .into());
+        }
+    }
+
+    println!("decoded {} bytes", verifier.len);
+    if let Some(sha256) = verifier.sha256 {
+        let digest = sha256.finalize();
+        let hex = digest
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+        println!("sha256 {}", hex);
+    }
+    if args.against.is_some() {
+        println!("identical to {}", against);
+    }
     Ok(())
 }
+
+fn run(args: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    if !(2..=94).contains(&args.base) {
+        return Err(base94::EncodeError::InvalidBase { base: args.base }.into());
+    }
+    let buffer_size = usize::try_from(args.buffer_size).unwrap_or(usize::MAX);
+
+    // Standard input and output are read and written as raw bytes, with no newline
+    // translation on any platform.
+    let (input, len): (Box<dyn Read>, _) = match path(&args.input) {
+        Some(path) => {
+            let file = fs::File::open(path)?;
+            let len = file.metadata()?.len();
+            (Box::new(file), Some(len))
+        }
+        None => (Box::new(io::stdin().lock()), None),
+    };
+    // The bar counts the input as the conversion reads it.
+    let progress = progress_bar(&args, len);
+    let input = BufReader::with_capacity(buffer_size, progress.wrap_read(input));
+    let verify = matches!(args.operation, Operation::Verify);
+    if verify && args.output.is_some() {
+        return Err("verify takes no output file".into());
+    }
+    if !verify && (args.sha256 || args.against.is_some()) {
+        return Err("--sha256 and --against only apply to verify".into());
+    }
+    let destination = path(&args.output);
+    let terminal = destination.is_none() && io::stdout().is_terminal();
+    let output: Box<dyn Write> = match destination {
+        Some(path) => Box::new(fs::File::create(path)?),
+        None => Box::new(io::stdout().lock()),
+    };
+    let mut output = BufWriter::with_capacity(buffer_size, output);
+
+    let result = match args.operation {
+        Operation::Encode => run_encode(&args, input, &mut output, terminal),
+        Operation::Decode => run_decode(&args, input, &mut output),
+        Operation::Verify => run_verify(&args, input),
+    }
+    .and_then(|()| Ok(output.flush()?));
+    match result {
+        Ok(()) => progress.finish(),
+        // Clear the bar so the error message is not appended to it.
+        Err(_) => progress.finish_and_clear(),
+    }
+    result
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
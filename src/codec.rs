@@ -0,0 +1,148 @@
+//! Encoding with a base that is fixed at compile time.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::len::digit_bits;
+use crate::{
+    decode_digits_with, encode_digits_with, from_digits, native, parse_digits, to_digits, wipe,
+    DecodeError, CHARACTERS,
+};
+
+/// The largest numbers, in bits, that are converted by the code specialized on `BASE`. Above
+/// this size the `backend-num` backend converts by divide and conquer, which is faster. The
+/// other backends run the specialized code at every size, so there is no limit.
+#[cfg(all(
+    feature = "backend-num",
+    not(feature = "backend-native"),
+    not(feature = "zeroize")
+))]
+const SPECIALIZED_BITS: Option<u128> = Some(crate::num_backend::SPLIT_BITS as u128);
+#[cfg(not(all(
+    feature = "backend-num",
+    not(feature = "backend-native"),
+    not(feature = "zeroize")
+)))]
+const SPECIALIZED_BITS: Option<u128> = None;
+
+/// An encoder and decoder for a base that is known at compile time.
+///
+/// The base is checked when the codec is created, so an invalid base is a compile error
+/// instead of a runtime panic:
+///
+/// ```compile_fail
+/// let codec = base94::Codec::<95>::new();
+/// ```
+///
+/// Every method is monomorphized for `BASE`, turning the divisions in the hot loop into
+/// multiplications by a constant. Inputs too large for that to pay off, where the default
+/// backend switches to divide and conquer, take the same conversion as the free functions.
+/// The output is identical to the free functions with the same base, which remain available
+/// when the base is only known at runtime.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode, Codec};
+///
+/// const CODEC: Codec<94> = Codec::new();
+///
+/// let encoded = CODEC.encode(b"Hello, World!");
+/// assert_eq!(encoded, encode(b"Hello, World!", 94));
+/// assert_eq!(CODEC.decode(&encoded).unwrap(), b"Hello, World!");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Codec<const BASE: u8> {
+    _private: (),
+}
+
+impl<const BASE: u8> Codec<BASE> {
+    /// Evaluated for every `BASE` that is instantiated, failing compilation if it is invalid.
+    const VALID_BASE: () = assert!(BASE >= 2 && BASE <= 94, "Base must be in the range 2..=94");
+
+    /// Creates the codec, checking `BASE` at compile time.
+    pub const fn new() -> Self {
+        let () = Self::VALID_BASE;
+        Self { _private: () }
+    }
+
+    /// Returns the base of this codec.
+    pub const fn base(&self) -> u8 {
+        BASE
+    }
+
+    /// Encodes a slice of bytes, like [`encode`](crate::encode) with `BASE`.
+    pub fn encode(&self, data: &[u8]) -> String {
+        let mut digits = encode_digits_with(data, |data| {
+            if SPECIALIZED_BITS.is_none_or(|max| data.len() as u128 * 8 <= max) {
+                native::to_digits(data, BASE as u32)
+            } else {
+                to_digits(data, BASE)
+            }
+        });
+        let encoded = digits
+            .iter()
+            .map(|&digit| CHARACTERS[digit as usize] as char)
+            .collect();
+        wipe(&mut digits);
+        encoded
+    }
+
+    /// Decodes a string, like [`decode`](crate::decode) with `BASE`.
+    pub fn decode(&self, encoded: &str) -> Result<Vec<u8>, DecodeError> {
+        let mut digits = parse_digits(encoded.as_bytes(), BASE)?;
+        let bytes = decode_digits_with(&digits, |digits| {
+            if SPECIALIZED_BITS.is_none_or(|max| digit_bits(digits.len(), BASE) <= max) {
+                native::from_digits(digits, BASE as u32)
+            } else {
+                from_digits(digits, BASE)
+            }
+        });
+        wipe(&mut digits);
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+
+    fn check<const BASE: u8>() {
+        let codec = Codec::<BASE>::new();
+        // Large enough for the divide-and-conquer conversion of `backend-num`.
+        let large = (0..4096).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+        for data in [
+            &b"Hello, World!"[..],
+            &[],
+            &[0],
+            &[1, 0, 0],
+            &[255; 100],
+            &large,
+        ] {
+            let encoded = codec.encode(data);
+            assert_eq!(encoded, encode(data, BASE));
+            assert_eq!(codec.decode(&encoded).unwrap(), data);
+            assert_eq!(decode(&encoded, BASE).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_codec_matches_free_functions() {
+        check::<2>();
+        check::<10>();
+        check::<16>();
+        check::<50>();
+        check::<64>();
+        check::<85>();
+        check::<94>();
+    }
+
+    #[test]
+    fn test_codec_decode_errors() {
+        assert!(matches!(
+            Codec::<10>::new().decode("!#*~"),
+            Err(DecodeError::DigitOutOfRange { position: 3, .. })
+        ));
+    }
+}
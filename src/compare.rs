@@ -0,0 +1,156 @@
+//! Comparing encoded values without decoding them.
+
+use core::cmp::Ordering;
+
+use crate::{digit_value, is_valid_base, DecodeError, CHARACTERS};
+
+/// Compares the numbers that two strings encoded in `base` stand for, without decoding or
+/// allocating.
+///
+/// The strings are validated, stripped of their trailing zero digits, and compared by
+/// length, then digit by digit from the most significant end. Since trailing zero digits
+/// only stand for trailing zero bytes, strings that differ only in them compare as
+/// [`Ordering::Equal`], as do the numbers of the bytes they decode to.
+///
+/// For the output of [`encode_sortable`](crate::encode_sortable), plain string comparison
+/// gives the same order directly.
+///
+/// # Arguments
+///
+/// * `a` - The first encoded string.
+/// * `b` - The second encoded string.
+/// * `base` - The base both strings are encoded in.
+///
+/// # Returns
+///
+/// The ordering of the value of `a` relative to that of `b`, or the `DecodeError` that
+/// [`decode`](crate::decode) would report for the first invalid string. Error positions
+/// count from the start of that string.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{cmp_encoded, encode};
+/// use std::cmp::Ordering;
+///
+/// let small = encode(&1000u32.to_le_bytes(), 50);
+/// let large = encode(&70000u32.to_le_bytes(), 50);
+/// assert_eq!(cmp_encoded(&small, &large, 50).unwrap(), Ordering::Less);
+/// assert_eq!(cmp_encoded("A", "A!!", 94).unwrap(), Ordering::Equal);
+/// ```
+pub fn cmp_encoded(a: &str, b: &str, base: u8) -> Result<Ordering, DecodeError> {
+    if !is_valid_base(base) {
+        return Err(DecodeError::InvalidBase { base });
+    }
+
+    let a = significant(a.as_bytes(), base)?;
+    let b = significant(b.as_bytes(), base)?;
+    // The alphabet is in ascending ASCII order, so characters compare like their digits.
+    Ok(a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev())))
+}
+
+/// Validates `encoded` and returns it without its trailing zero digits.
+fn significant(encoded: &[u8], base: u8) -> Result<&[u8], DecodeError> {
+    let mut len = 0;
+    for (i, &c) in encoded.iter().enumerate() {
+        digit_value(&encoded[i..], i, base)?;
+        if c != CHARACTERS[0] {
+            len = i + 1;
+        }
+    }
+    Ok(&encoded[..len])
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+
+    const MAX_BASE: u8 = 94;
+
+    /// Compares the little-endian numbers `a` and `b`.
+    fn cmp_numbers(a: &[u8], b: &[u8]) -> Ordering {
+        let trim = |bytes: &[u8]| bytes.len() - bytes.iter().rev().take_while(|&&b| b == 0).count();
+        let (a, b) = (&a[..trim(a)], &b[..trim(b)]);
+        a.len()
+            .cmp(&b.len())
+            .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+    }
+
+    fn random(len: usize) -> Vec<u8> {
+        (0..len).map(|_| rand::random::<u8>()).collect()
+    }
+
+    #[test]
+    fn test_matches_decoded_comparison() {
+        for base in 2..=MAX_BASE {
+            for _ in 0..50 {
+                let (mut a, mut b) = (random(rand::random::<usize>() % 20), random(20));
+                if rand::random::<bool>() {
+                    b = a.clone();
+                    if let Some(byte) = b.first_mut() {
+                        *byte = rand::random();
+                    }
+                }
+                if rand::random::<bool>() {
+                    a.extend([0; 3]);
+                }
+                let (x, y) = (encode(&a, base), encode(&b, base));
+                let expected = cmp_numbers(&decode(&x, base).unwrap(), &decode(&y, base).unwrap());
+                assert_eq!(cmp_encoded(&x, &y, base).unwrap(), expected);
+                assert_eq!(cmp_encoded(&y, &x, base).unwrap(), expected.reverse());
+                assert_eq!(cmp_encoded(&x, &x, base).unwrap(), Ordering::Equal);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sorts_integers() {
+        for base in 2..=MAX_BASE {
+            let mut values = (0..100)
+                .map(|_| rand::random::<u64>() >> (rand::random::<u32>() % 64))
+                .collect::<Vec<u64>>();
+            let mut encoded = values
+                .iter()
+                .map(|n| encode(&n.to_le_bytes(), base))
+                .collect::<Vec<_>>();
+            values.sort();
+            encoded.sort_by(|a, b| cmp_encoded(a, b, base).unwrap());
+            let decoded = encoded
+                .iter()
+                .map(|s| {
+                    let mut bytes = [0; 8];
+                    bytes.copy_from_slice(&decode(s, base).unwrap());
+                    u64::from_le_bytes(bytes)
+                })
+                .collect::<Vec<u64>>();
+            assert_eq!(decoded, values);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            cmp_encoded("!#", "ab c", 94),
+            Err(DecodeError::InvalidCharacter {
+                c: ' ',
+                position: 2
+            })
+        ));
+        assert!(matches!(
+            cmp_encoded("!+", "", 10),
+            Err(DecodeError::DigitOutOfRange {
+                position: 1,
+                base: 10,
+                ..
+            })
+        ));
+        assert!(matches!(
+            cmp_encoded("", "", 95),
+            Err(DecodeError::InvalidBase { base: 95 })
+        ));
+        assert_eq!(cmp_encoded("", "!!!", 2).unwrap(), Ordering::Equal);
+    }
+}
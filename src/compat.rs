@@ -0,0 +1,161 @@
+//! Codecs for related encodings from other ecosystems.
+//!
+//! These are separate algorithms from the positional conversion used by the rest of the
+//! crate, implemented here so that data can be exchanged with existing tools without extra
+//! dependencies. Their output is not interchangeable with [`encode`](crate::encode).
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use thiserror::Error;
+
+pub mod ascii85;
+pub mod base32;
+pub mod base45;
+pub mod base64;
+pub mod base91;
+pub mod hex;
+pub mod z85;
+
+/// Errors that can occur while decoding the codecs of this module, or encoding unaligned
+/// data as strict Z85. [`base91`] reports [`DecodeError`](crate::DecodeError) instead.
+///
+/// Positions are byte offsets into the encoded text.
+#[derive(Error, Debug)]
+pub enum CompatError {
+    #[error("Invalid character '{c}' at position {position}")]
+    InvalidCharacter { c: u8, position: usize },
+    #[error("Group starting at position {position} holds a value too large for its length")]
+    GroupOverflow { position: usize },
+    #[error("Group starting at position {position} is incomplete")]
+    TruncatedGroup { position: usize },
+    #[error("Length {length} is not a multiple of {multiple}")]
+    InvalidLength { length: usize, multiple: usize },
+    #[error("Invalid padding at position {position}")]
+    InvalidPadding { position: usize },
+    #[error("Character at position {position} has nonzero unused bits")]
+    TrailingBits { position: usize },
+    #[error("Missing '~>' after '<~'")]
+    UnterminatedFrame,
+}
+
+/// Marks bytes that are not digits in the tables built by [`digit_index`].
+const NOT_A_DIGIT: u8 = u8::MAX;
+
+/// Builds a reverse lookup table mapping every byte to its index in `symbols`, or
+/// [`NOT_A_DIGIT`].
+const fn digit_index(symbols: &[u8]) -> [u8; 256] {
+    let mut table = [NOT_A_DIGIT; 256];
+    let mut i = 0;
+    while i < symbols.len() {
+        table[symbols[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+/// The padding character of the RFC 4648 encodings.
+const PAD: u8 = b'=';
+
+/// Writes `data` as characters of `BITS` bits each, most significant bit first, zero-filling
+/// the last character. With `pad`, appends `=` until the length is a multiple of `group`.
+fn encode_bits<const BITS: u32>(data: &[u8], symbols: &[u8], group: usize, pad: bool) -> String {
+    let mask = (1 << BITS) - 1;
+    let mut out = String::with_capacity((data.len() * 8).div_ceil(BITS as usize) + group);
+    let mut buffer = 0u32;
+    let mut count = 0;
+
+    for &byte in data {
+        buffer = buffer << 8 | byte as u32;
+        count += 8;
+        while count >= BITS {
+            count -= BITS;
+            out.push(symbols[(buffer >> count & mask) as usize] as char);
+        }
+    }
+    if count > 0 {
+        out.push(symbols[(buffer << (BITS - count) & mask) as usize] as char);
+    }
+
+    if pad {
+        while !out.len().is_multiple_of(group) {
+            out.push(PAD as char);
+        }
+    }
+    out
+}
+
+/// Reads text written by [`encode_bits`], with or without padding if `pad` is set.
+///
+/// Padding, if present, must be exactly what [`encode_bits`] would write, and the unused bits
+/// of the last character must be zero, so every byte string has a single accepted encoding
+/// of each form.
+fn decode_bits<const BITS: u32>(
+    encoded: &str,
+    index: &[u8; 256],
+    group: usize,
+    pad: bool,
+) -> Result<Vec<u8>, CompatError> {
+    let bytes = encoded.as_bytes();
+    let len = match pad {
+        true => bytes.iter().rposition(|&c| c != PAD).map_or(0, |i| i + 1),
+        false => bytes.len(),
+    };
+    let partial = len % group;
+    let padding = bytes.len() - len;
+    if padding > 0 && (partial == 0 || padding != group - partial) {
+        return Err(CompatError::InvalidPadding { position: len });
+    }
+    if (partial as u32 * BITS) % 8 >= BITS {
+        return Err(CompatError::TruncatedGroup {
+            position: len - partial,
+        });
+    }
+
+    let mut out = Vec::with_capacity(len * BITS as usize / 8);
+    let mut buffer = 0u32;
+    let mut count = 0;
+    for (position, &c) in bytes[..len].iter().enumerate() {
+        let digit = match index[c as usize] {
+            NOT_A_DIGIT => return Err(CompatError::InvalidCharacter { c, position }),
+            digit => digit,
+        };
+        buffer = buffer << BITS | digit as u32;
+        count += BITS;
+        if count >= 8 {
+            count -= 8;
+            out.push((buffer >> count) as u8);
+        }
+    }
+
+    if buffer & ((1 << count) - 1) != 0 {
+        return Err(CompatError::TrailingBits { position: len - 1 });
+    }
+    Ok(out)
+}
+
+/// Writes 1 to 4 bytes as a base 85 group of one more character than there are bytes,
+/// padding a partial group with zero bytes and dropping the surplus characters.
+fn encode_group(bytes: &[u8], symbols: &[u8; 85], out: &mut String) {
+    let mut padded = [0; 4];
+    padded[..bytes.len()].copy_from_slice(bytes);
+    let mut value = u32::from_be_bytes(padded);
+
+    let mut digits = [0; 5];
+    for digit in digits.iter_mut().rev() {
+        *digit = symbols[(value % 85) as usize];
+        value /= 85;
+    }
+    out.extend(digits[..=bytes.len()].iter().map(|&c| c as char));
+}
+
+/// Reads a base 85 group of 2 to 5 digit values starting at `position`, padding a partial
+/// group with the highest digit so that the surplus bytes can be dropped.
+fn decode_group(digits: &[u8], position: usize, out: &mut Vec<u8>) -> Result<(), CompatError> {
+    let value = (0..5).fold(0u64, |value, i| {
+        value * 85 + digits.get(i).copied().unwrap_or(84) as u64
+    });
+    let value = u32::try_from(value).map_err(|_| CompatError::GroupOverflow { position })?;
+    out.extend_from_slice(&value.to_be_bytes()[..digits.len() - 1]);
+    Ok(())
+}
@@ -0,0 +1,182 @@
+//! Adobe's Ascii85, as used by PostScript and PDF.
+//!
+//! Every 4 bytes become 5 characters from `!` to `u`, with `z` standing for a group of four
+//! zero bytes. A final partial group of `n` bytes is written as `n + 1` characters. Framed
+//! output wraps the text in `<~` and `~>`, as PostScript expects.
+//!
+//! # Examples
+//!
+//! ```
+//! use base94::compat::ascii85;
+//!
+//! assert_eq!(ascii85::encode(b"Man "), "9jqo^");
+//! assert_eq!(ascii85::encode_framed(&[0; 4]), "<~z~>");
+//! assert_eq!(ascii85::decode("<~9jqo^~>").unwrap(), b"Man ");
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{decode_group, encode_group, CompatError};
+
+/// The Ascii85 alphabet, in digit order.
+pub const ALPHABET: &[u8; 85] =
+    b"!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu";
+
+/// Stands for a whole group of zero bytes.
+const ZERO_GROUP: u8 = b'z';
+
+/// Encodes `data` as Ascii85 without the `<~ ~>` frame.
+pub fn encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(4) * 5);
+    for group in data.chunks(4) {
+        if group == [0; 4] {
+            out.push(ZERO_GROUP as char);
+        } else {
+            encode_group(group, ALPHABET, &mut out);
+        }
+    }
+    out
+}
+
+/// Encodes `data` as Ascii85 wrapped in `<~` and `~>`.
+pub fn encode_framed(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(4) * 5 + 4);
+    out.push_str("<~");
+    out.push_str(&encode(data));
+    out.push_str("~>");
+    out
+}
+
+/// Decodes Ascii85 text, with or without the `<~ ~>` frame.
+///
+/// A trailing `~>` is accepted on its own, as found in PDF streams, but a leading `<~`
+/// requires one. Whitespace is ignored anywhere in the text.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `CompatError` if:
+/// - `encoded` contains a character outside the alphabet, or `z` inside a group.
+/// - A group exceeds 32 bits, or the final group has a single character.
+/// - `encoded` starts with `<~` but does not end with `~>`.
+pub fn decode(encoded: &str) -> Result<Vec<u8>, CompatError> {
+    let (body, offset) = match encoded.strip_prefix("<~") {
+        Some(rest) => match rest.trim_end().strip_suffix("~>") {
+            Some(body) => (body, 2),
+            None => return Err(CompatError::UnterminatedFrame),
+        },
+        None => match encoded.trim_end().strip_suffix("~>") {
+            Some(body) => (body, 0),
+            None => (encoded, 0),
+        },
+    };
+
+    let mut out = Vec::with_capacity(body.len() / 5 * 4 + 4);
+    let mut group = [0; 5];
+    let mut len = 0;
+    let mut start = 0;
+
+    for (i, c) in body.bytes().enumerate() {
+        let position = offset + i;
+        match c {
+            _ if c.is_ascii_whitespace() => continue,
+            ZERO_GROUP if len == 0 => out.extend_from_slice(&[0; 4]),
+            b'!'..=b'u' => {
+                if len == 0 {
+                    start = position;
+                }
+                group[len] = c - b'!';
+                len += 1;
+                if len == group.len() {
+                    decode_group(&group, start, &mut out)?;
+                    len = 0;
+                }
+            }
+            _ => return Err(CompatError::InvalidCharacter { c, position }),
+        }
+    }
+
+    match len {
+        0 => {}
+        1 => return Err(CompatError::TruncatedGroup { position: start }),
+        _ => decode_group(&group[..len], start, &mut out)?,
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Outputs of Adobe's encoder, as reproduced by Python's `base64.a85encode`.
+    const VECTORS: &[(&[u8], &str)] = &[
+        (b"", ""),
+        (b"\0", "!!"),
+        (b".", "/c"),
+        (b"Man ", "9jqo^"),
+        (b"Man is distinguished", "9jqo^BlbD-BleB1DJ+*+F(f,q"),
+        (b"Hello, World!", "87cURD_*#4DfTZ)+T"),
+        (&[0xff; 4], "s8W-!"),
+        (&[0; 9], "zz!!"),
+    ];
+
+    #[test]
+    fn test_vectors() {
+        for &(data, encoded) in VECTORS {
+            assert_eq!(encode(data), encoded);
+            assert_eq!(encode_framed(data), format!("<~{}~>", encoded));
+            assert_eq!(decode(encoded).unwrap(), data);
+            assert_eq!(decode(&format!("<~{}~>", encoded)).unwrap(), data);
+            assert_eq!(decode(&format!("{}~>\n", encoded)).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for len in 0..100 {
+            let mut data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            if len > 8 {
+                data[4..8].fill(0);
+            }
+            assert_eq!(decode(&encode(&data)).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_ignores_whitespace() {
+        assert_eq!(
+            decode("<~9jqo^ BlbD-\nBleB1DJ+*+F(\r\nf,q~>").unwrap(),
+            b"Man is distinguished"
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            decode("<~9jqo^ v~>"),
+            Err(CompatError::InvalidCharacter {
+                c: b'v',
+                position: 8
+            })
+        ));
+        assert!(matches!(
+            decode("9jzo^"),
+            Err(CompatError::InvalidCharacter {
+                c: b'z',
+                position: 2
+            })
+        ));
+        assert!(matches!(
+            decode("9jqo^s8W-\""),
+            Err(CompatError::GroupOverflow { position: 5 })
+        ));
+        assert!(matches!(
+            decode("9jqo^ B"),
+            Err(CompatError::TruncatedGroup { position: 6 })
+        ));
+        assert!(matches!(
+            decode("<~9jqo^"),
+            Err(CompatError::UnterminatedFrame)
+        ));
+    }
+}
@@ -0,0 +1,122 @@
+//! RFC 4648 Base32.
+//!
+//! Every 5 bytes become 8 characters of `A-Z` and `2-7`, padded with `=` to a multiple of 8.
+//!
+//! # Examples
+//!
+//! ```
+//! use base94::compat::base32;
+//!
+//! assert_eq!(base32::encode(b"foobar"), "MZXW6YTBOI======");
+//! assert_eq!(base32::decode("MZXW6YTBOI").unwrap(), b"foobar");
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{decode_bits, digit_index, encode_bits, CompatError};
+
+/// The Base32 alphabet, in digit order.
+pub const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Reverse lookup table mapping every byte to its index in [`ALPHABET`].
+static DIGIT_INDEX: [u8; 256] = digit_index(ALPHABET);
+
+/// Encodes `data` as padded Base32.
+pub fn encode(data: &[u8]) -> String {
+    encode_bits::<5>(data, ALPHABET, 8, true)
+}
+
+/// Encodes `data` as Base32 without padding.
+pub fn encode_unpadded(data: &[u8]) -> String {
+    encode_bits::<5>(data, ALPHABET, 8, false)
+}
+
+/// Decodes Base32 text, with or without padding.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `CompatError` if:
+/// - `encoded` contains a character outside [`ALPHABET`], such as a lower-case letter.
+/// - The padding is not exactly what [`encode`] writes.
+/// - The last group has a length that no input produces, or nonzero unused bits.
+pub fn decode(encoded: &str) -> Result<Vec<u8>, CompatError> {
+    decode_bits::<5>(encoded, &DIGIT_INDEX, 8, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The examples of RFC 4648, section 10.
+    const VECTORS: &[(&[u8], &str)] = &[
+        (b"", ""),
+        (b"f", "MY======"),
+        (b"fo", "MZXQ===="),
+        (b"foo", "MZXW6==="),
+        (b"foob", "MZXW6YQ="),
+        (b"fooba", "MZXW6YTB"),
+        (b"foobar", "MZXW6YTBOI======"),
+    ];
+
+    #[test]
+    fn test_vectors() {
+        for &(data, encoded) in VECTORS {
+            let unpadded = encoded.trim_end_matches('=');
+            assert_eq!(encode(data), encoded);
+            assert_eq!(encode_unpadded(data), unpadded);
+            assert_eq!(decode(encoded).unwrap(), data);
+            assert_eq!(decode(unpadded).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for len in 0..100 {
+            let mut data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            data.insert(0, 0);
+            let encoded = encode(&data);
+            assert_eq!(encoded.len(), data.len().div_ceil(5) * 8);
+            assert_eq!(decode(&encoded).unwrap(), data);
+            assert_eq!(decode(&encode_unpadded(&data)).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            decode("MZXW6YQ"),
+            Ok(bytes) if bytes == b"foob"
+        ));
+        assert!(matches!(
+            decode("MZXW6YQ=="),
+            Err(CompatError::InvalidPadding { position: 7 })
+        ));
+        assert!(matches!(
+            decode("MZXW6YTB========"),
+            Err(CompatError::InvalidPadding { position: 8 })
+        ));
+        assert!(matches!(
+            decode("MZX====="),
+            Err(CompatError::TruncatedGroup { position: 0 })
+        ));
+        assert!(matches!(
+            decode("MZ"),
+            Err(CompatError::TrailingBits { position: 1 })
+        ));
+        assert!(matches!(
+            decode("mzxq"),
+            Err(CompatError::InvalidCharacter {
+                c: b'm',
+                position: 0
+            })
+        ));
+        assert!(matches!(
+            decode("MZ=Q"),
+            Err(CompatError::InvalidCharacter {
+                c: b'=',
+                position: 2
+            })
+        ));
+    }
+}
@@ -0,0 +1,148 @@
+//! [RFC 9285](https://www.rfc-editor.org/rfc/rfc9285) Base45, as used in QR codes such as EU
+//! digital COVID certificates.
+//!
+//! Every 2 bytes become 3 characters of the QR code alphanumeric set, least significant
+//! first, and a final single byte becomes 2 characters.
+//!
+//! # Examples
+//!
+//! ```
+//! use base94::compat::base45;
+//!
+//! assert_eq!(base45::encode(b"AB"), "BB8");
+//! assert_eq!(base45::decode("%69 VD92EX0").unwrap(), b"Hello!!");
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{digit_index, CompatError, NOT_A_DIGIT};
+
+/// The Base45 alphabet, in digit order.
+pub const ALPHABET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// Reverse lookup table mapping every byte to its index in [`ALPHABET`].
+static DIGIT_INDEX: [u8; 256] = digit_index(ALPHABET);
+
+/// Encodes `data` as Base45.
+pub fn encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(2) * 3);
+    for group in data.chunks(2) {
+        let (mut n, len) = match *group {
+            [a, b] => (u16::from_be_bytes([a, b]), 3),
+            [a] => (a as u16, 2),
+            _ => unreachable!("chunks(2) yields one or two bytes"),
+        };
+        for _ in 0..len {
+            out.push(ALPHABET[(n % 45) as usize] as char);
+            n /= 45;
+        }
+    }
+    out
+}
+
+/// Decodes Base45 text.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `CompatError` if:
+/// - `encoded` contains a character outside [`ALPHABET`].
+/// - A group of 3 characters exceeds 16 bits, or a final group of 2 exceeds 8 bits.
+/// - The final group has a single character.
+pub fn decode(encoded: &str) -> Result<Vec<u8>, CompatError> {
+    let mut out = Vec::with_capacity(encoded.len() / 3 * 2 + 1);
+
+    for (index, group) in encoded.as_bytes().chunks(3).enumerate() {
+        let start = index * 3;
+        if group.len() == 1 {
+            return Err(CompatError::TruncatedGroup { position: start });
+        }
+        let mut n = 0u32;
+        for (i, &c) in group.iter().enumerate().rev() {
+            let digit = match DIGIT_INDEX[c as usize] {
+                NOT_A_DIGIT => {
+                    return Err(CompatError::InvalidCharacter {
+                        c,
+                        position: start + i,
+                    })
+                }
+                digit => digit,
+            };
+            n = n * 45 + digit as u32;
+        }
+
+        let overflow = CompatError::GroupOverflow { position: start };
+        if group.len() == 3 {
+            let n = u16::try_from(n).map_err(|_| overflow)?;
+            out.extend_from_slice(&n.to_be_bytes());
+        } else {
+            out.push(u8::try_from(n).map_err(|_| overflow)?);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The examples of RFC 9285.
+    const VECTORS: &[(&[u8], &str)] = &[
+        (b"", ""),
+        (b"AB", "BB8"),
+        (b"Hello!!", "%69 VD92EX0"),
+        (b"base-45", "UJCLQE7W581"),
+        (b"ietf!", "QED8WEX0"),
+    ];
+
+    #[test]
+    fn test_vectors() {
+        for &(data, encoded) in VECTORS {
+            assert_eq!(encode(data), encoded);
+            assert_eq!(decode(encoded).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for len in 0..100 {
+            let data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let encoded = encode(&data);
+            assert_eq!(encoded.len(), len / 2 * 3 + len % 2 * 2);
+            assert_eq!(decode(&encoded).unwrap(), data);
+        }
+        assert_eq!(encode(&[0xff, 0xff]), "FGW");
+        assert_eq!(encode(&[0xff]), "U5");
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            decode("BB8A"),
+            Err(CompatError::TruncatedGroup { position: 3 })
+        ));
+        assert!(matches!(
+            decode("BB8a"),
+            Err(CompatError::TruncatedGroup { position: 3 })
+        ));
+        assert!(matches!(
+            decode("BB8aB"),
+            Err(CompatError::InvalidCharacter {
+                c: b'a',
+                position: 3
+            })
+        ));
+        assert!(matches!(
+            decode("BB8GGW"),
+            Err(CompatError::GroupOverflow { position: 3 })
+        ));
+        assert!(matches!(
+            decode("BB8V5"),
+            Err(CompatError::GroupOverflow { position: 3 })
+        ));
+        assert!(matches!(
+            decode(":::"),
+            Err(CompatError::GroupOverflow { position: 0 })
+        ));
+    }
+}
@@ -0,0 +1,161 @@
+//! RFC 4648 Base64, in its standard and URL-safe alphabets.
+//!
+//! Every 3 bytes become 4 characters, padded with `=` to a multiple of 4. The URL-safe
+//! alphabet replaces `+` and `/` with `-` and `_`.
+//!
+//! # Examples
+//!
+//! ```
+//! use base94::compat::base64;
+//!
+//! assert_eq!(base64::encode(b"foobar"), "Zm9vYmFy");
+//! assert_eq!(base64::encode(&[0xfb, 0xff]), "+/8=");
+//! assert_eq!(base64::encode_url_safe(&[0xfb, 0xff]), "-_8=");
+//! assert_eq!(base64::decode_url_safe("-_8").unwrap(), [0xfb, 0xff]);
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{decode_bits, digit_index, encode_bits, CompatError};
+
+/// The standard Base64 alphabet, in digit order.
+pub const STANDARD: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The URL and filename safe Base64 alphabet, in digit order.
+pub const URL_SAFE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Reverse lookup table for [`STANDARD`].
+static STANDARD_INDEX: [u8; 256] = digit_index(STANDARD);
+
+/// Reverse lookup table for [`URL_SAFE`].
+static URL_SAFE_INDEX: [u8; 256] = digit_index(URL_SAFE);
+
+/// Encodes `data` as padded Base64 with the [`STANDARD`] alphabet.
+pub fn encode(data: &[u8]) -> String {
+    encode_bits::<6>(data, STANDARD, 4, true)
+}
+
+/// Encodes `data` as padded Base64 with the [`URL_SAFE`] alphabet.
+pub fn encode_url_safe(data: &[u8]) -> String {
+    encode_bits::<6>(data, URL_SAFE, 4, true)
+}
+
+/// Encodes `data` as Base64 with the [`URL_SAFE`] alphabet and no padding, the form used by
+/// JWTs and most URLs.
+pub fn encode_url_safe_unpadded(data: &[u8]) -> String {
+    encode_bits::<6>(data, URL_SAFE, 4, false)
+}
+
+/// Decodes Base64 text in the [`STANDARD`] alphabet, with or without padding.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `CompatError` if:
+/// - `encoded` contains a character outside [`STANDARD`].
+/// - The padding is not exactly what [`encode`] writes.
+/// - The last group has a single character, or nonzero unused bits.
+pub fn decode(encoded: &str) -> Result<Vec<u8>, CompatError> {
+    decode_bits::<6>(encoded, &STANDARD_INDEX, 4, true)
+}
+
+/// Decodes Base64 text in the [`URL_SAFE`] alphabet, with or without padding.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `CompatError` under the same conditions as [`decode`].
+pub fn decode_url_safe(encoded: &str) -> Result<Vec<u8>, CompatError> {
+    decode_bits::<6>(encoded, &URL_SAFE_INDEX, 4, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The examples of RFC 4648, section 10.
+    const VECTORS: &[(&[u8], &str)] = &[
+        (b"", ""),
+        (b"f", "Zg=="),
+        (b"fo", "Zm8="),
+        (b"foo", "Zm9v"),
+        (b"foob", "Zm9vYg=="),
+        (b"fooba", "Zm9vYmE="),
+        (b"foobar", "Zm9vYmFy"),
+    ];
+
+    #[test]
+    fn test_vectors() {
+        for &(data, encoded) in VECTORS {
+            let unpadded = encoded.trim_end_matches('=');
+            assert_eq!(encode(data), encoded);
+            assert_eq!(encode_url_safe(data), encoded);
+            assert_eq!(encode_url_safe_unpadded(data), unpadded);
+            assert_eq!(decode(encoded).unwrap(), data);
+            assert_eq!(decode(unpadded).unwrap(), data);
+            assert_eq!(decode_url_safe(encoded).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_alphabets_differ_in_two_characters() {
+        let data = [0xfb, 0xef, 0xff];
+        assert_eq!(encode(&data), "++//");
+        assert_eq!(encode_url_safe(&data), "--__");
+        assert!(matches!(
+            decode("--__"),
+            Err(CompatError::InvalidCharacter {
+                c: b'-',
+                position: 0
+            })
+        ));
+        assert!(matches!(
+            decode_url_safe("++//"),
+            Err(CompatError::InvalidCharacter {
+                c: b'+',
+                position: 0
+            })
+        ));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for len in 0..100 {
+            let mut data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            data.insert(0, 0);
+            let encoded = encode(&data);
+            assert_eq!(encoded.len(), data.len().div_ceil(3) * 4);
+            assert_eq!(decode(&encoded).unwrap(), data);
+            assert_eq!(
+                decode_url_safe(&encode_url_safe_unpadded(&data)).unwrap(),
+                data
+            );
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            decode("Zg="),
+            Err(CompatError::InvalidPadding { position: 2 })
+        ));
+        assert!(matches!(
+            decode("Zm9v===="),
+            Err(CompatError::InvalidPadding { position: 4 })
+        ));
+        assert!(matches!(
+            decode("Zm9vY"),
+            Err(CompatError::TruncatedGroup { position: 4 })
+        ));
+        assert!(matches!(
+            decode("Zh=="),
+            Err(CompatError::TrailingBits { position: 1 })
+        ));
+        assert!(matches!(
+            decode("Zm 9"),
+            Err(CompatError::InvalidCharacter {
+                c: b' ',
+                position: 2
+            })
+        ));
+    }
+}
@@ -0,0 +1,163 @@
+//! Joachim Henke's [basE91](https://base91.sourceforge.net/) encoding.
+//!
+//! basE91 packs the input into 13- or 14-bit groups and writes each group as two characters
+//! of a 91-character alphabet, so it runs in linear time and expands data by at most 1.23
+//! characters per byte. The output is identical to the reference C implementation and its
+//! ports.
+//!
+//! # Examples
+//!
+//! ```
+//! use base94::compat::base91;
+//!
+//! assert_eq!(base91::encode(b"test"), "fPNKd");
+//! assert_eq!(base91::decode("fPNKd").unwrap(), b"test");
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{digit_index, NOT_A_DIGIT};
+use crate::{invalid_character, DecodeError};
+
+/// The basE91 alphabet, in digit order.
+pub const ALPHABET: &[u8; 91] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789!#$%&()*+,./:;<=>?@[]^_`{|}~\"";
+
+/// Reverse lookup table mapping every byte to its index in [`ALPHABET`].
+static DIGIT_INDEX: [u8; 256] = digit_index(ALPHABET);
+
+/// Encodes `data` as basE91.
+///
+/// # Examples
+///
+/// ```
+/// use base94::compat::base91;
+///
+/// assert_eq!(base91::encode(b"Hello, World!"), ">OwJh>}AQ;r@@Y?F");
+/// ```
+pub fn encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len() * 16 / 13 + 2);
+    let mut bits = 0u32;
+    let mut count = 0;
+
+    for &byte in data {
+        bits |= (byte as u32) << count;
+        count += 8;
+        if count > 13 {
+            // A 13-bit group is used unless its value is small enough that the two
+            // characters could carry a fourteenth bit.
+            let mut value = bits & 8191;
+            if value > 88 {
+                bits >>= 13;
+                count -= 13;
+            } else {
+                value = bits & 16383;
+                bits >>= 14;
+                count -= 14;
+            }
+            out.push(ALPHABET[(value % 91) as usize] as char);
+            out.push(ALPHABET[(value / 91) as usize] as char);
+        }
+    }
+
+    if count > 0 {
+        out.push(ALPHABET[(bits % 91) as usize] as char);
+        if count > 7 || bits > 90 {
+            out.push(ALPHABET[(bits / 91) as usize] as char);
+        }
+    }
+    out
+}
+
+/// Decodes basE91 text.
+///
+/// Unlike the reference implementation, which silently skips characters outside the
+/// alphabet, this rejects them, so text with line breaks has to be cleaned up first.
+///
+/// # Returns
+///
+/// The decoded bytes, or `DecodeError::InvalidCharacter` with its byte offset if `encoded`
+/// contains a character outside [`ALPHABET`].
+pub fn decode(encoded: &str) -> Result<Vec<u8>, DecodeError> {
+    let mut out = Vec::with_capacity(encoded.len() * 14 / 16 + 1);
+    let mut pending = None;
+    let mut bits = 0u32;
+    let mut count = 0;
+
+    for (position, c) in encoded.bytes().enumerate() {
+        let digit = match DIGIT_INDEX[c as usize] {
+            NOT_A_DIGIT => {
+                return Err(invalid_character(&encoded.as_bytes()[position..], position))
+            }
+            digit => digit as u32,
+        };
+        let Some(low) = pending.take() else {
+            pending = Some(digit);
+            continue;
+        };
+
+        let value = low + digit * 91;
+        bits |= value << count;
+        count += if value & 8191 > 88 { 13 } else { 14 };
+        while count > 7 {
+            out.push(bits as u8);
+            bits >>= 8;
+            count -= 8;
+        }
+    }
+
+    if let Some(low) = pending {
+        out.push((bits | low << count) as u8);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Outputs of the reference implementation.
+    const VECTORS: &[(&[u8], &str)] = &[
+        (b"", ""),
+        (b"a", "GB"),
+        (b"test", "fPNKd"),
+        (b"Hello, World!", ">OwJh>}AQ;r@@Y?F"),
+        (b"\0", "AA"),
+        (b"\0\0\0\0", "AAAAA"),
+        (&[0xff; 8], "B\"B\"B\"B\"At"),
+        (
+            &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+            ":C#(:C?hVB$MSiVEwndB",
+        ),
+    ];
+
+    #[test]
+    fn test_vectors() {
+        for &(data, encoded) in VECTORS {
+            assert_eq!(encode(data), encoded);
+            assert_eq!(decode(encoded).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for len in 0..200 {
+            let data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let encoded = encode(&data);
+            assert!(encoded.len() <= data.len() * 16 / 13 + 2);
+            assert_eq!(decode(&encoded).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_invalid_character() {
+        for (encoded, c, position) in [("fP NKd", ' ', 2), ("fPNK'", '\'', 4), ("-", '-', 0)] {
+            assert!(matches!(
+                decode(encoded),
+                Err(DecodeError::InvalidCharacter { c: found, position: at })
+                    if found == c && at == position
+            ));
+        }
+    }
+}
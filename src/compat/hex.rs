@@ -0,0 +1,111 @@
+//! Hexadecimal (RFC 4648 Base16).
+//!
+//! # Examples
+//!
+//! ```
+//! use base94::compat::hex;
+//!
+//! assert_eq!(hex::encode(&[0xde, 0xad, 0xbe, 0xef]), "deadbeef");
+//! assert_eq!(hex::decode("DEADbeef").unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{decode_bits, digit_index, encode_bits, CompatError};
+
+/// The lower-case hex digits, in digit order.
+pub const LOWER: &[u8; 16] = b"0123456789abcdef";
+
+/// The upper-case hex digits, in digit order, as RFC 4648 specifies.
+pub const UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Reverse lookup table accepting digits of either case.
+static DIGIT_INDEX: [u8; 256] = {
+    let mut table = digit_index(LOWER);
+    let mut i = 10;
+    while i < UPPER.len() {
+        table[UPPER[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+/// Encodes `data` as lower-case hex.
+pub fn encode(data: &[u8]) -> String {
+    encode_bits::<4>(data, LOWER, 2, false)
+}
+
+/// Encodes `data` as upper-case hex.
+pub fn encode_upper(data: &[u8]) -> String {
+    encode_bits::<4>(data, UPPER, 2, false)
+}
+
+/// Decodes hex digits of either case.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `CompatError` if `encoded` contains a character that is not a hex
+/// digit or has an odd length.
+pub fn decode(encoded: &str) -> Result<Vec<u8>, CompatError> {
+    decode_bits::<4>(encoded, &DIGIT_INDEX, 2, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The examples of RFC 4648, section 10.
+    const VECTORS: &[(&[u8], &str)] = &[
+        (b"", ""),
+        (b"f", "66"),
+        (b"fo", "666F"),
+        (b"foo", "666F6F"),
+        (b"foob", "666F6F62"),
+        (b"fooba", "666F6F6261"),
+        (b"foobar", "666F6F626172"),
+    ];
+
+    #[test]
+    fn test_vectors() {
+        for &(data, encoded) in VECTORS {
+            assert_eq!(encode_upper(data), encoded);
+            assert_eq!(encode(data), encoded.to_lowercase());
+            assert_eq!(decode(encoded).unwrap(), data);
+            assert_eq!(decode(&encoded.to_lowercase()).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for len in 0..100 {
+            let mut data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            data.insert(0, 0);
+            let encoded = encode(&data);
+            assert_eq!(encoded.len(), data.len() * 2);
+            assert_eq!(decode(&encoded).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            decode("666"),
+            Err(CompatError::TruncatedGroup { position: 2 })
+        ));
+        assert!(matches!(
+            decode("66g6"),
+            Err(CompatError::InvalidCharacter {
+                c: b'g',
+                position: 2
+            })
+        ));
+        assert!(matches!(
+            decode("6="),
+            Err(CompatError::InvalidCharacter {
+                c: b'=',
+                position: 1
+            })
+        ));
+    }
+}
@@ -0,0 +1,193 @@
+//! ZeroMQ's [Z85](https://rfc.zeromq.org/spec/32/) encoding.
+//!
+//! Every 4 bytes become 5 characters from an alphabet that avoids quotes and backslashes.
+//! The specification only covers data whose length is a multiple of 4, which [`encode`]
+//! and [`decode`] enforce. [`encode_padded`] and [`decode_padded`] accept any length by
+//! writing a final partial group of `n` bytes as `n + 1` characters, as Ascii85 does; their
+//! output equals the strict form whenever the length is aligned.
+//!
+//! # Examples
+//!
+//! ```
+//! use base94::compat::z85;
+//!
+//! let data = [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b];
+//! assert_eq!(z85::encode(&data).unwrap(), "HelloWorld");
+//! assert_eq!(z85::decode("HelloWorld").unwrap(), data);
+//! assert_eq!(z85::decode_padded(&z85::encode_padded(b"abc")).unwrap(), b"abc");
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{decode_group, digit_index, encode_group, CompatError, NOT_A_DIGIT};
+
+/// The Z85 alphabet, in digit order.
+pub const ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+/// Reverse lookup table mapping every byte to its index in [`ALPHABET`].
+static DIGIT_INDEX: [u8; 256] = digit_index(ALPHABET);
+
+/// Encodes `data` as Z85.
+///
+/// # Returns
+///
+/// The encoded text, or `CompatError::InvalidLength` if the length of `data` is not a
+/// multiple of 4.
+pub fn encode(data: &[u8]) -> Result<String, CompatError> {
+    if !data.len().is_multiple_of(4) {
+        return Err(CompatError::InvalidLength {
+            length: data.len(),
+            multiple: 4,
+        });
+    }
+    Ok(encode_padded(data))
+}
+
+/// Decodes Z85 text.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `CompatError` if the length of `encoded` is not a multiple of 5,
+/// it contains a character outside [`ALPHABET`], or a group exceeds 32 bits.
+pub fn decode(encoded: &str) -> Result<Vec<u8>, CompatError> {
+    if !encoded.len().is_multiple_of(5) {
+        return Err(CompatError::InvalidLength {
+            length: encoded.len(),
+            multiple: 5,
+        });
+    }
+    decode_padded(encoded)
+}
+
+/// Encodes `data` of any length as Z85, shortening the final group if it is partial.
+pub fn encode_padded(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(4) * 5);
+    for group in data.chunks(4) {
+        encode_group(group, ALPHABET, &mut out);
+    }
+    out
+}
+
+/// Decodes Z85 text produced by [`encode_padded`].
+///
+/// # Returns
+///
+/// The decoded bytes, or a `CompatError` if `encoded` contains a character outside
+/// [`ALPHABET`], a group exceeds 32 bits, or the final group has a single character.
+pub fn decode_padded(encoded: &str) -> Result<Vec<u8>, CompatError> {
+    let mut out = Vec::with_capacity(encoded.len() / 5 * 4 + 4);
+    let mut digits = [0; 5];
+
+    for (index, group) in encoded.as_bytes().chunks(5).enumerate() {
+        let start = index * 5;
+        if group.len() == 1 {
+            return Err(CompatError::TruncatedGroup { position: start });
+        }
+        for (i, &c) in group.iter().enumerate() {
+            digits[i] = match DIGIT_INDEX[c as usize] {
+                NOT_A_DIGIT => {
+                    return Err(CompatError::InvalidCharacter {
+                        c,
+                        position: start + i,
+                    })
+                }
+                digit => digit,
+            };
+        }
+        decode_group(&digits[..group.len()], start, &mut out)?;
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example from the Z85 specification, and outputs of the reference implementation.
+    const VECTORS: &[(&[u8], &str)] = &[
+        (b"", ""),
+        (
+            &[0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b],
+            "HelloWorld",
+        ),
+        (&[0; 4], "00000"),
+        (&[0xff; 4], "%nSc0"),
+        (
+            &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+            "009c61o!#m2NH?C3>iWS",
+        ),
+    ];
+
+    /// Unaligned inputs, matching Python's unpadded `base64.b85encode` in the Z85 alphabet.
+    const PADDED_VECTORS: &[(&[u8], &str)] = &[
+        (b"a", "ve"),
+        (b"abcde", "vpA.SwD"),
+        (b"Hello, World!", "nm=QNz.92jz/PV8aP"),
+    ];
+
+    #[test]
+    fn test_vectors() {
+        for &(data, encoded) in VECTORS {
+            assert_eq!(encode(data).unwrap(), encoded);
+            assert_eq!(encode_padded(data), encoded);
+            assert_eq!(decode(encoded).unwrap(), data);
+            assert_eq!(decode_padded(encoded).unwrap(), data);
+        }
+        for &(data, encoded) in PADDED_VECTORS {
+            assert_eq!(encode_padded(data), encoded);
+            assert_eq!(decode_padded(encoded).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for len in 0..100 {
+            let data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let encoded = encode_padded(&data);
+            assert_eq!(decode_padded(&encoded).unwrap(), data);
+            if len % 4 == 0 {
+                assert_eq!(encode(&data).unwrap(), encoded);
+                assert_eq!(decode(&encoded).unwrap(), data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            encode(b"abc"),
+            Err(CompatError::InvalidLength {
+                length: 3,
+                multiple: 4
+            })
+        ));
+        assert!(matches!(
+            decode("HelloWorl"),
+            Err(CompatError::InvalidLength {
+                length: 9,
+                multiple: 5
+            })
+        ));
+        assert!(matches!(
+            decode("Hello World"),
+            Err(CompatError::InvalidLength { .. })
+        ));
+        assert!(matches!(
+            decode("Hello\"orld"),
+            Err(CompatError::InvalidCharacter {
+                c: b'"',
+                position: 5
+            })
+        ));
+        assert!(matches!(
+            decode("Hello%nSc1"),
+            Err(CompatError::GroupOverflow { position: 5 })
+        ));
+        assert!(matches!(
+            decode_padded("HelloW"),
+            Err(CompatError::TruncatedGroup { position: 5 })
+        ));
+    }
+}
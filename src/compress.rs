@@ -0,0 +1,159 @@
+//! Deflating data before encoding it, for text and other compressible payloads.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::io::{Read, Write};
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+
+use crate::{decode, encode, DecodeError};
+
+/// The header of a payload that holds the data as is.
+const STORED: u8 = 0;
+
+/// The header of a payload that holds a raw deflate stream of the data.
+const DEFLATED: u8 = 1;
+
+/// Compresses `data` with deflate and encodes it in `base`.
+///
+/// The encoded payload starts with a header byte that says whether the rest is a deflate
+/// stream or the data itself. Data that would not get smaller, such as random or already
+/// compressed bytes, is stored as is, so the payload is at most one byte longer than `data`.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+/// * `level` - The deflate level, from 0 (fastest) to 9 (smallest output).
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_compressed, encode, encode_compressed};
+///
+/// let text = "All work and no play makes Jack a dull boy. ".repeat(20);
+/// let encoded = encode_compressed(text.as_bytes(), 94, 9);
+/// assert!(encoded.len() < encode(text.as_bytes(), 94).len() / 10);
+/// assert_eq!(decode_compressed(&encoded, 94).unwrap(), text.as_bytes());
+/// ```
+pub fn encode_compressed(data: &[u8], base: u8, level: u32) -> String {
+    let mut encoder = DeflateEncoder::new(vec![DEFLATED], Compression::new(level));
+    encoder
+        .write_all(data)
+        .expect("writing to a Vec cannot fail");
+    let mut payload = encoder.finish().expect("writing to a Vec cannot fail");
+
+    if payload.len() > data.len() {
+        payload.clear();
+        payload.push(STORED);
+        payload.extend_from_slice(data);
+    }
+    encode(&payload, base)
+}
+
+/// Decodes a string produced by [`encode_compressed`] and decompresses it if needed.
+///
+/// # Arguments
+///
+/// * `encoded` - A string produced by [`encode_compressed`].
+/// * `base` - The base used for encoding. Must match the base used for encoding.
+///
+/// # Returns
+///
+/// The original data, or a `DecodeError` if:
+/// - `encoded` is invalid in `base`, under the same conditions as [`decode`].
+/// - `encoded` is empty, with `DecodeError::TruncatedInput`.
+/// - The header byte names no known method, with `DecodeError::UnknownCompression`.
+/// - The deflate stream is corrupt, truncated, or followed by extra bytes, with
+///   `DecodeError::CorruptCompressedData`.
+pub fn decode_compressed(encoded: &str, base: u8) -> Result<Vec<u8>, DecodeError> {
+    let payload = decode(encoded, base)?;
+    let (&method, body) = payload.split_first().ok_or(DecodeError::TruncatedInput)?;
+
+    match method {
+        STORED => Ok(body.to_vec()),
+        DEFLATED => {
+            let mut decoder = DeflateDecoder::new(body);
+            let mut data = Vec::new();
+            decoder
+                .read_to_end(&mut data)
+                .map_err(|_| DecodeError::CorruptCompressedData)?;
+            if decoder.total_in() != body.len() as u64 {
+                return Err(DecodeError::CorruptCompressedData);
+            }
+            Ok(data)
+        }
+        method => Err(DecodeError::UnknownCompression { method }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_round_trip() {
+        let random = (0..1000).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+        let text = "Hello, World! ".repeat(100).into_bytes();
+        for base in 2..=MAX_BASE {
+            for data in [&[][..], &[0], &[0; 1000], &text, &random] {
+                for level in [0, 1, 6, 9] {
+                    let encoded = encode_compressed(data, base, level);
+                    assert_eq!(decode_compressed(&encoded, base).unwrap(), data);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_compressible_data_shrinks() {
+        let text = "Hello, World! ".repeat(100).into_bytes();
+        let payload = decode(&encode_compressed(&text, 94, 9), 94).unwrap();
+        assert_eq!(payload[0], DEFLATED);
+        assert!(payload.len() < text.len() / 10);
+    }
+
+    #[test]
+    fn test_stored_fallback() {
+        let random = (0..1000).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+        for data in [&[][..], b"a", &random] {
+            for level in [0, 9] {
+                let payload = decode(&encode_compressed(data, 94, level), 94).unwrap();
+                assert_eq!(payload[0], STORED);
+                assert_eq!(&payload[1..], data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            decode_compressed("", 94),
+            Err(DecodeError::TruncatedInput)
+        ));
+        assert!(matches!(
+            decode_compressed(&encode(&[7, 1, 2], 94), 94),
+            Err(DecodeError::UnknownCompression { method: 7 })
+        ));
+
+        let text = "Hello, World! ".repeat(100).into_bytes();
+        let payload = decode(&encode_compressed(&text, 94, 9), 94).unwrap();
+        let corrupt = [&[DEFLATED, 0xff][..], &payload[1..]].concat();
+        let truncated = &payload[..payload.len() - 2];
+        let extended = [&payload[..], b"extra"].concat();
+        for payload in [&corrupt[..], truncated, &extended] {
+            assert!(matches!(
+                decode_compressed(&encode(payload, 94), 94),
+                Err(DecodeError::CorruptCompressedData)
+            ));
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Configurable decoding for text that has been wrapped or reformatted.
+
+use alloc::vec::Vec;
+
+use crate::{decode_digits, digit_value, is_valid_base, DecodeError};
+
+/// Options for [`decode_with_config`].
+///
+/// The default configuration is strict and behaves exactly like [`decode`](crate::decode).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_with_config, encode, DecodeConfig};
+///
+/// let wrapped = encode(b"Hello, World!", 94).replace('D', "D\r\n");
+/// let config = DecodeConfig::new().ignore_whitespace(true);
+/// assert_eq!(decode_with_config(&wrapped, 94, config).unwrap(), b"Hello, World!");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DecodeConfig {
+    ignore_whitespace: bool,
+    ignore_spaces: bool,
+}
+
+impl DecodeConfig {
+    /// Creates a strict configuration that accepts only digits.
+    pub const fn new() -> Self {
+        Self {
+            ignore_whitespace: false,
+            ignore_spaces: false,
+        }
+    }
+
+    /// Skips `\n`, `\r`, and `\t` anywhere in the input, as left behind by line wrapping,
+    /// email transport, or terminal pastes.
+    pub const fn ignore_whitespace(mut self, ignore: bool) -> Self {
+        self.ignore_whitespace = ignore;
+        self
+    }
+
+    /// Also skips `' '`. This is separate from [`ignore_whitespace`](Self::ignore_whitespace)
+    /// because space is a printable character that an alphabet may use as a digit.
+    pub const fn ignore_spaces(mut self, ignore: bool) -> Self {
+        self.ignore_spaces = ignore;
+        self
+    }
+
+    /// Returns whether the byte `c` should be skipped under this configuration.
+    pub(crate) fn is_ignored(&self, c: u8) -> bool {
+        match c {
+            b'\n' | b'\r' | b'\t' => self.ignore_whitespace,
+            b' ' => self.ignore_spaces,
+            _ => false,
+        }
+    }
+}
+
+/// Decodes `encoded` like [`decode`](crate::decode), skipping the characters that `config`
+/// marks as ignorable.
+///
+/// # Arguments
+///
+/// * `encoded` - The string to decode.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+/// * `config` - Which characters to skip.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `DecodeError` under the same conditions as `decode`. Skipped
+/// characters still count towards error positions, so positions refer to `encoded` as given.
+pub fn decode_with_config(
+    encoded: &str,
+    base: u8,
+    config: DecodeConfig,
+) -> Result<Vec<u8>, DecodeError> {
+    if !is_valid_base(base) {
+        return Err(DecodeError::InvalidBase { base });
+    }
+
+    let digits = encoded
+        .bytes()
+        .enumerate()
+        .filter(|&(_, c)| !config.is_ignored(c))
+        .map(|(i, _)| digit_value(&encoded.as_bytes()[i..], i, base))
+        .collect::<Result<Vec<u8>, DecodeError>>()?;
+
+    Ok(decode_digits(&digits, base))
+}
+
+/// Decodes `encoded` like [`decode`](crate::decode), skipping `\n`, `\r`, and `\t`.
+///
+/// This accepts output that has been wrapped at a fixed column or has passed through a
+/// channel that rewrites line endings. Spaces are still rejected; use
+/// [`decode_with_config`] with [`DecodeConfig::ignore_spaces`] to skip them too.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_ignoring_whitespace, encode};
+///
+/// let encoded = encode(b"Hello, World!", 94);
+/// let (head, tail) = encoded.split_at(8);
+/// let wrapped = format!("{}\n{}\n", head, tail);
+/// assert_eq!(decode_ignoring_whitespace(&wrapped, 94).unwrap(), b"Hello, World!");
+/// ```
+pub fn decode_ignoring_whitespace(encoded: &str, base: u8) -> Result<Vec<u8>, DecodeError> {
+    decode_with_config(encoded, base, DecodeConfig::new().ignore_whitespace(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+
+    const MAX_BASE: u8 = 94;
+
+    /// Inserts `line_ending` after every `width` characters of `encoded`.
+    fn wrap(encoded: &str, width: usize, line_ending: &str) -> String {
+        encoded
+            .as_bytes()
+            .chunks(width)
+            .map(|line| std::str::from_utf8(line).unwrap())
+            .collect::<Vec<&str>>()
+            .join(line_ending)
+    }
+
+    #[test]
+    fn test_decode_wrapped_output() {
+        let data = (0..200).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+        for base in 2..=MAX_BASE {
+            let encoded = encode(&data, base);
+            for width in [1, 7, 64, 76] {
+                for line_ending in ["\n", "\r\n", "\t"] {
+                    let wrapped = wrap(&encoded, width, line_ending);
+                    assert_eq!(decode_ignoring_whitespace(&wrapped, base).unwrap(), data);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_config_is_strict() {
+        let encoded = format!("{}\n", encode(b"Hello", 94));
+        assert_eq!(
+            decode_with_config(&encoded, 94, DecodeConfig::default())
+                .unwrap_err()
+                .to_string(),
+            decode(&encoded, 94).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_spaces_are_opt_in() {
+        let encoded = wrap(&encode(b"Hello", 94), 2, " ");
+        assert!(matches!(
+            decode_ignoring_whitespace(&encoded, 94),
+            Err(DecodeError::InvalidCharacter {
+                c: ' ',
+                position: 2
+            })
+        ));
+        let config = DecodeConfig::new()
+            .ignore_whitespace(true)
+            .ignore_spaces(true);
+        assert_eq!(decode_with_config(&encoded, 94, config).unwrap(), b"Hello");
+    }
+
+    #[test]
+    fn test_invalid_characters_still_rejected() {
+        assert!(matches!(
+            decode_ignoring_whitespace("ab\n\u{7f}", 94),
+            Err(DecodeError::InvalidCharacter {
+                c: '\u{7f}',
+                position: 3
+            })
+        ));
+    }
+}
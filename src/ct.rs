@@ -0,0 +1,188 @@
+//! Decoding in constant time with respect to the encoded characters.
+//!
+//! [`decode`](crate::decode) looks characters up in a table, converts the digits with
+//! arithmetic whose running time depends on their values, and stops at the first invalid
+//! character. When the encoded text is a secret, each of these can leak information through
+//! timing. [`decode_ct`] instead maps characters to digits arithmetically, always processes
+//! the whole input, and reports only whether it succeeded.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{is_valid_base, wipe, DecodeError, CHARACTERS};
+
+/// Returns 1 if `a < b` and 0 otherwise, without branching. Both must be below `2^31`.
+#[inline(always)]
+fn ct_lt(a: u32, b: u32) -> u32 {
+    a.wrapping_sub(b) >> 31
+}
+
+/// Returns 1 if `x` is zero and 0 otherwise, without branching.
+#[inline(always)]
+fn ct_is_zero(x: u32) -> u32 {
+    x.wrapping_sub(1) >> 31
+}
+
+/// Decodes a Base94-encoded string like [`decode`](crate::decode), in time that depends only
+/// on the length of `encoded` and not on its characters.
+///
+/// Every character is checked and converted with arithmetic instead of a table lookup, the
+/// whole input is processed even after an invalid character, and the number conversion
+/// always runs over the full width the input length allows. The price is weaker error
+/// reporting and speed:
+///
+/// - An invalid or out-of-range character is reported as `DecodeError::InvalidInput`,
+///   without the character or its position.
+/// - The conversion takes time quadratic in the input length, so this is meant for short
+///   secrets such as keys and API tokens rather than bulk data.
+///
+/// The base and the length of the input and output are treated as public. This function
+/// avoids data-dependent branches and memory accesses, but cannot stop the compiler or CPU
+/// from introducing them; review the generated code if that matters for your target.
+///
+/// # Arguments
+///
+/// * `encoded` - A Base94-encoded string to be decoded.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+///
+/// # Returns
+///
+/// The decoded bytes, `DecodeError::InvalidBase` if the base is outside the valid range
+/// (2 to 94), or `DecodeError::InvalidInput` if any character is not a digit in `base`.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode, decode_ct, encode, DecodeError};
+///
+/// let encoded = encode(b"secret token", 94);
+/// assert_eq!(decode_ct(&encoded, 94).unwrap(), decode(&encoded, 94).unwrap());
+/// assert!(matches!(decode_ct("ab\u{7f}cd", 94), Err(DecodeError::InvalidInput)));
+/// ```
+pub fn decode_ct(encoded: &str, base: u8) -> Result<Vec<u8>, DecodeError> {
+    if !is_valid_base(base) {
+        return Err(DecodeError::InvalidBase { base });
+    }
+
+    let encoded = encoded.as_bytes();
+    let mut valid = 1;
+    let mut digits = encoded
+        .iter()
+        .map(|&c| {
+            let first = CHARACTERS[0] as u32;
+            let in_range = (1 ^ ct_lt(c as u32, first)) & ct_lt(c as u32, first + base as u32);
+            let digit = (c as u32).wrapping_sub(first);
+            valid &= in_range;
+            (digit & in_range.wrapping_neg()) as u8
+        })
+        .collect::<Vec<u8>>();
+
+    // Trailing zero digits stand for trailing zero bytes.
+    let mut zero_run = 1;
+    let mut zero_digits = 0;
+    for &digit in digits.iter().rev() {
+        zero_run &= ct_is_zero(digit as u32);
+        zero_digits += zero_run as usize;
+    }
+
+    // Each digit is smaller than 256, so `digits.len()` bytes always hold the value.
+    let mut limbs = vec![0u32; digits.len().div_ceil(4)];
+    for &digit in digits.iter().rev() {
+        let mut carry = digit as u64;
+        for limb in limbs.iter_mut() {
+            let cur = *limb as u64 * base as u64 + carry;
+            *limb = cur as u32;
+            carry = cur >> 32;
+        }
+    }
+    wipe(&mut digits);
+
+    let mut bytes = Vec::with_capacity(limbs.len() * 4);
+    for limb in &limbs {
+        bytes.extend_from_slice(&limb.to_le_bytes());
+    }
+    wipe(&mut limbs);
+
+    let mut zero_run = 1;
+    let mut zero_bytes = 0;
+    for &byte in bytes.iter().rev() {
+        zero_run &= ct_is_zero(byte as u32);
+        zero_bytes += zero_run as usize;
+    }
+    // The value occupies at most `encoded.len() - zero_digits` bytes, so the zero bytes
+    // that follow it make up the trailing zeros of the output.
+    bytes.truncate(bytes.len() - zero_bytes + zero_digits);
+
+    if valid == 1 {
+        Ok(bytes)
+    } else {
+        wipe(&mut bytes);
+        Err(DecodeError::InvalidInput)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_decode_ct_matches_decode() {
+        for base in 2..=MAX_BASE {
+            for len in [0, 1, 2, 3, 4, 5, 16, 17, 33, 100] {
+                let mut data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+                for zeros in [0, 1, 5] {
+                    data.extend(vec![0; zeros]);
+                    let encoded = encode(&data, base);
+                    assert_eq!(decode_ct(&encoded, base).unwrap(), data);
+                }
+                data.fill(0);
+                assert_eq!(decode_ct(&encode(&data, base), base).unwrap(), data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_ct_accepts_every_string() {
+        for base in 2..=MAX_BASE {
+            let encoded = (0..40)
+                .map(|_| CHARACTERS[rand::random::<usize>() % base as usize] as char)
+                .collect::<String>();
+            assert_eq!(
+                decode_ct(&encoded, base).unwrap(),
+                decode(&encoded, base).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_ct_rejects_invalid_characters() {
+        for base in 2..=MAX_BASE {
+            let mut encoded = encode(b"Hello, World!", base);
+            encoded.insert(3, ' ');
+            assert!(matches!(
+                decode_ct(&encoded, base),
+                Err(DecodeError::InvalidInput)
+            ));
+            encoded.remove(3);
+            encoded.push('é');
+            assert!(matches!(
+                decode_ct(&encoded, base),
+                Err(DecodeError::InvalidInput)
+            ));
+        }
+        for base in 2..MAX_BASE {
+            let encoded = (CHARACTERS[base as usize] as char).to_string();
+            assert!(matches!(
+                decode_ct(&encoded, base),
+                Err(DecodeError::InvalidInput)
+            ));
+        }
+        assert!(matches!(
+            decode_ct("abc", 95),
+            Err(DecodeError::InvalidBase { base: 95 })
+        ));
+    }
+}
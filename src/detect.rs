@@ -0,0 +1,152 @@
+//! Guessing the base of a string that was encoded without recording it.
+
+use core::ops::RangeInclusive;
+
+use crate::digit_index;
+
+/// Character statistics of an encoded string, as returned by [`analyze`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Analysis {
+    /// How often each digit occurs, indexed by digit.
+    histogram: [usize; 94],
+    /// The first character outside the alphabet and its position.
+    invalid: Option<(u8, usize)>,
+}
+
+impl Analysis {
+    /// Returns the bases that `encoded` could be a valid string of, or `None` if it contains
+    /// a character outside the alphabet.
+    ///
+    /// The range always ends at 94, since a string that is valid in one base is valid in
+    /// every larger base.
+    pub fn bases(&self) -> Option<RangeInclusive<u8>> {
+        Some(self.min_base()?..=94)
+    }
+
+    /// Returns the smallest base that `encoded` is valid in, or `None` if it contains a
+    /// character outside the alphabet. See [`detect_base`].
+    pub fn min_base(&self) -> Option<u8> {
+        if self.invalid.is_some() {
+            return None;
+        }
+        let max_digit = self.histogram.iter().rposition(|&count| count > 0);
+        Some(max_digit.map_or(2, |digit| digit as u8 + 1).max(2))
+    }
+
+    /// Returns how often each digit occurs, indexed by digit value.
+    pub fn histogram(&self) -> &[usize; 94] {
+        &self.histogram
+    }
+
+    /// Returns how often the digit `c` occurs, or 0 if it is not part of the alphabet.
+    pub fn count(&self, c: u8) -> usize {
+        digit_index(c).map_or(0, |digit| self.histogram[digit as usize])
+    }
+
+    /// Returns the first character outside the alphabet and its position, if any.
+    pub fn first_invalid(&self) -> Option<(u8, usize)> {
+        self.invalid
+    }
+}
+
+/// Counts the characters of `encoded` to find the bases it could have been encoded in.
+///
+/// # Examples
+///
+/// ```
+/// use base94::analyze;
+///
+/// let analysis = analyze("!!##");
+/// assert_eq!(analysis.bases(), Some(3..=94));
+/// assert_eq!(analysis.count(b'!'), 2);
+/// assert_eq!(analysis.count(b'"'), 0);
+/// ```
+pub fn analyze(encoded: &str) -> Analysis {
+    let mut analysis = Analysis {
+        histogram: [0; 94],
+        invalid: None,
+    };
+    for (position, &c) in encoded.as_bytes().iter().enumerate() {
+        match digit_index(c) {
+            Some(digit) => analysis.histogram[digit as usize] += 1,
+            None => {
+                analysis.invalid.get_or_insert((c, position));
+            }
+        }
+    }
+    analysis
+}
+
+/// Returns the smallest base that `encoded` is a valid string of: one more than its largest
+/// digit, and at least 2. Returns `None` if it contains a character outside the alphabet.
+///
+/// This is only a lower bound. The true base can be anything from the result up to 94,
+/// and short strings often use only a few small digits. A string decodes without error in
+/// every base at least as large as the result, but only the base it was encoded with gives
+/// back the original data, so use this to reject or warn about a base that is too small,
+/// not to pick one. [`encode_tagged`](crate::encode_tagged) records the base instead.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{detect_base, encode};
+///
+/// assert_eq!(detect_base(&encode(&[0xff; 32], 10)), Some(10));
+/// assert!(detect_base(&encode(b"Hello", 94)).unwrap() <= 94);
+/// assert_eq!(detect_base("!!"), Some(2));
+/// assert_eq!(detect_base("no spaces"), None);
+/// ```
+pub fn detect_base(encoded: &str) -> Option<u8> {
+    analyze(encoded).min_base()
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::encode;
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_never_above_true_base() {
+        for base in 2..=MAX_BASE {
+            for len in [0, 1, 5, 50, 500] {
+                let data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+                let encoded = encode(&data, base);
+                let detected = detect_base(&encoded).unwrap();
+                assert!(detected <= base, "{} > {}", detected, base);
+                assert!(analyze(&encoded).bases().unwrap().contains(&base));
+            }
+        }
+    }
+
+    #[test]
+    fn test_detects_base_of_long_input() {
+        for base in 2..=MAX_BASE {
+            let data = (0..2000).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            assert_eq!(detect_base(&encode(&data, base)), Some(base));
+        }
+    }
+
+    #[test]
+    fn test_histogram() {
+        let analysis = analyze("!!\"~~~");
+        assert_eq!(analysis.histogram()[0], 2);
+        assert_eq!(analysis.histogram()[1], 1);
+        assert_eq!(analysis.histogram()[93], 3);
+        assert_eq!(analysis.histogram().iter().sum::<usize>(), 6);
+        assert_eq!(analysis.count(b'~'), 3);
+        assert_eq!(analysis.count(b' '), 0);
+        assert_eq!(analysis.first_invalid(), None);
+        assert_eq!(analysis.min_base(), Some(94));
+    }
+
+    #[test]
+    fn test_invalid_characters() {
+        let analysis = analyze("ab c\u{7f}");
+        assert_eq!(analysis.first_invalid(), Some((b' ', 2)));
+        assert_eq!(analysis.bases(), None);
+        assert_eq!(analysis.count(b'a'), 1);
+        assert_eq!(detect_base(""), Some(2));
+    }
+}
@@ -0,0 +1,167 @@
+//! Encoding straight into a [`fmt::Write`] sink, without an intermediate `String`.
+
+use core::fmt;
+
+use crate::{is_valid_base, to_digits, trailing_zeros, EncodeError, CHARACTERS};
+
+/// Encodes a slice of bytes like [`encode`](crate::encode) and writes the text to `out`.
+///
+/// Inputs whose value fits in 16 bytes are converted entirely on the stack; larger ones
+/// allocate only the digit buffer of the conversion.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+/// * `out` - The sink the encoded text is appended to.
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Returns
+///
+/// The first error returned by `out`, if any.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode, encode_to_fmt};
+///
+/// let mut line = String::from("token=");
+/// encode_to_fmt(b"Hello, World!", 94, &mut line).unwrap();
+/// assert_eq!(line, format!("token={}", encode(b"Hello, World!", 94)));
+/// ```
+pub fn encode_to_fmt<W: fmt::Write + ?Sized>(data: &[u8], base: u8, out: &mut W) -> fmt::Result {
+    if !is_valid_base(base) {
+        panic!("{}", EncodeError::InvalidBase { base });
+    }
+
+    let zeros = trailing_zeros(data, 0);
+    let value = &data[..data.len() - zeros];
+    if value.len() <= 16 {
+        let mut bytes = [0; 16];
+        bytes[..value.len()].copy_from_slice(value);
+        // 16 bytes take at most 128 digits, in base 2.
+        let mut text = [0; 128];
+        let digits = u128_to_digits_on_stack(u128::from_le_bytes(bytes), base, &mut text);
+        write_ascii(out, digits)?;
+    } else {
+        let mut digits = to_digits(value, base);
+        for digit in &mut digits {
+            *digit = CHARACTERS[*digit as usize];
+        }
+        write_ascii(out, &digits)?;
+    }
+
+    let run = [CHARACTERS[0]; 64];
+    let mut remaining = zeros;
+    while remaining > 0 {
+        let n = remaining.min(run.len());
+        write_ascii(out, &run[..n])?;
+        remaining -= n;
+    }
+    Ok(())
+}
+
+/// Returns a value that formats as the encoding of `data`, for use with `format!` and
+/// friends. The data is encoded again every time the value is formatted.
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{display, encode};
+///
+/// let key = [1, 2, 3, 4];
+/// assert_eq!(format!("key={}", display(&key, 94)), format!("key={}", encode(&key, 94)));
+/// ```
+pub fn display(data: &[u8], base: u8) -> Display<'_> {
+    if !is_valid_base(base) {
+        panic!("{}", EncodeError::InvalidBase { base });
+    }
+    Display { data, base }
+}
+
+/// The [`fmt::Display`] wrapper returned by [`display`].
+#[derive(Clone, Copy, Debug)]
+pub struct Display<'a> {
+    data: &'a [u8],
+    base: u8,
+}
+
+impl fmt::Display for Display<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        encode_to_fmt(self.data, self.base, f)
+    }
+}
+
+/// Writes the characters of `n` in `base`, least significant first, into `text` and returns
+/// the part that was used.
+fn u128_to_digits_on_stack(mut n: u128, base: u8, text: &mut [u8; 128]) -> &[u8] {
+    let mut len = 0;
+    while n > 0 {
+        text[len] = CHARACTERS[(n % base as u128) as usize];
+        n /= base as u128;
+        len += 1;
+    }
+    &text[..len]
+}
+
+fn write_ascii<W: fmt::Write + ?Sized>(out: &mut W, text: &[u8]) -> fmt::Result {
+    out.write_str(core::str::from_utf8(text).expect("CHARACTERS is ASCII"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{encode, u128_to_digits};
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_encode_to_fmt_matches_encode() {
+        for base in 2..=MAX_BASE {
+            for len in [0, 1, 15, 16, 17, 100] {
+                let mut data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+                data.resize(len + rand::random::<usize>() % 100, 0);
+
+                let mut out = String::new();
+                encode_to_fmt(&data, base, &mut out).unwrap();
+                assert_eq!(out, encode(&data, base));
+                assert_eq!(display(&data, base).to_string(), out);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stack_digits_match_u128_to_digits() {
+        for base in 2..=MAX_BASE {
+            for n in [0, 1, u64::MAX as u128, u128::MAX, rand::random::<u128>()] {
+                let mut text = [0; 128];
+                let expected = u128_to_digits(n, base)
+                    .into_iter()
+                    .map(|digit| CHARACTERS[digit as usize])
+                    .collect::<Vec<u8>>();
+                assert_eq!(u128_to_digits_on_stack(n, base, &mut text), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_display_respects_format_string() {
+        assert_eq!(
+            format!("token={}!", display(b"ab", 94)),
+            format!("token={}!", encode(b"ab", 94))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_display_invalid_base() {
+        display(b"ab", 95);
+    }
+}
@@ -0,0 +1,586 @@
+//! String types that always hold valid encoded text.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use core::str::FromStr;
+
+use crate::{decode, encode, validate, DecodeError};
+
+/// An encoded string together with its base, validated at construction.
+///
+/// A `Base94String` can only be created by [`encode`](Self::encode) or by parsing text that
+/// is valid in its base, so [`decode`](Self::decode) cannot fail. Equality, ordering, and
+/// hashing compare the encoded text only, like the `String` it wraps.
+///
+/// # Examples
+///
+/// ```
+/// use base94::Base94String;
+///
+/// let encoded = Base94String::encode(b"Hello, World!", 94);
+/// assert_eq!(encoded.decode(), b"Hello, World!");
+///
+/// let parsed: Base94String = encoded.to_string().parse().unwrap();
+/// assert_eq!(parsed, encoded);
+/// assert!(Base94String::parse("ab\u{7f}", 94).is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Base94String {
+    encoded: String,
+    base: u8,
+}
+
+impl Base94String {
+    /// Encodes `data` in `base`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the specified base is outside the valid range (2 to 94).
+    pub fn encode(data: &[u8], base: u8) -> Self {
+        Self {
+            encoded: encode(data, base),
+            base,
+        }
+    }
+
+    /// Wraps `encoded` after checking that every character is a digit in `base`.
+    ///
+    /// # Returns
+    ///
+    /// The wrapped string, or the `DecodeError` that [`decode`](crate::decode) would report
+    /// for `encoded`.
+    pub fn parse(encoded: &str, base: u8) -> Result<Self, DecodeError> {
+        validate(encoded, base)?;
+        Ok(Self {
+            encoded: String::from(encoded),
+            base,
+        })
+    }
+
+    /// Takes ownership of `encoded` after checking it like [`parse`](Self::parse).
+    #[cfg(feature = "serde")]
+    fn from_string(encoded: String, base: u8) -> Result<Self, DecodeError> {
+        validate(&encoded, base)?;
+        Ok(Self { encoded, base })
+    }
+
+    /// Returns the base the text is encoded in.
+    pub fn base(&self) -> u8 {
+        self.base
+    }
+
+    /// Returns the encoded text.
+    pub fn as_str(&self) -> &str {
+        &self.encoded
+    }
+
+    /// Decodes the text back into the original bytes.
+    pub fn decode(&self) -> Vec<u8> {
+        decode(&self.encoded, self.base).expect("validated at construction")
+    }
+
+    /// Returns the encoded text as a plain `String`.
+    pub fn into_string(mut self) -> String {
+        core::mem::take(&mut self.encoded)
+    }
+}
+
+impl fmt::Display for Base94String {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encoded)
+    }
+}
+
+/// Parses base 94 text. Use [`Base94String::parse`] for other bases.
+impl FromStr for Base94String {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, 94)
+    }
+}
+
+impl Deref for Base94String {
+    type Target = EncodedStr;
+
+    fn deref(&self) -> &EncodedStr {
+        EncodedStr::new_unchecked(&self.encoded)
+    }
+}
+
+impl Borrow<EncodedStr> for Base94String {
+    fn borrow(&self) -> &EncodedStr {
+        self
+    }
+}
+
+impl AsRef<str> for Base94String {
+    fn as_ref(&self) -> &str {
+        &self.encoded
+    }
+}
+
+impl From<Base94String> for String {
+    fn from(encoded: Base94String) -> Self {
+        encoded.into_string()
+    }
+}
+
+impl PartialEq for Base94String {
+    fn eq(&self, other: &Self) -> bool {
+        self.encoded == other.encoded
+    }
+}
+
+impl Eq for Base94String {}
+
+impl PartialOrd for Base94String {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Base94String {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.encoded.cmp(&other.encoded)
+    }
+}
+
+impl Hash for Base94String {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.encoded.hash(state);
+    }
+}
+
+/// Borrowed encoded text, validated at construction, in the way `str` is to `String`.
+///
+/// An `&EncodedStr` is a `&str` that has been checked against a base, so functions can
+/// accept validated text without taking ownership. Being a plain `str` underneath, it
+/// cannot remember that base: [`decode`](Self::decode) and
+/// [`to_encoded_string`](Self::to_encoded_string) take it again. For the same reason there
+/// is no `ToOwned` impl, which would have to invent a base; [`Base94String`] still derefs
+/// to and borrows as `EncodedStr`, so a `HashMap<Base94String, _>` can be queried with an
+/// `&EncodedStr`.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{Base94String, EncodedStr};
+///
+/// fn byte_len(encoded: &EncodedStr) -> usize {
+///     encoded.decode(94).unwrap().len()
+/// }
+///
+/// let owned = Base94String::encode(b"Hello, World!", 94);
+/// assert_eq!(byte_len(&owned), 13);
+/// assert_eq!(byte_len(EncodedStr::new(owned.as_str(), 94).unwrap()), 13);
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct EncodedStr {
+    text: str,
+}
+
+impl EncodedStr {
+    /// Borrows `encoded` after checking that every character is a digit in `base`.
+    ///
+    /// # Returns
+    ///
+    /// The borrowed text, or the `DecodeError` that [`decode`](crate::decode) would report
+    /// for `encoded`.
+    pub fn new(encoded: &str, base: u8) -> Result<&Self, DecodeError> {
+        validate(encoded, base)?;
+        Ok(Self::new_unchecked(encoded))
+    }
+
+    /// Wraps `encoded` without validating it.
+    fn new_unchecked(encoded: &str) -> &Self {
+        // SAFETY: `EncodedStr` is `repr(transparent)` over `str`.
+        unsafe { &*(encoded as *const str as *const Self) }
+    }
+
+    /// Returns the encoded text.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Decodes the text from `base`.
+    ///
+    /// # Returns
+    ///
+    /// The decoded bytes, or a `DecodeError` if `base` is not one the text is valid in. This
+    /// cannot fail for the base the text was checked against.
+    pub fn decode(&self, base: u8) -> Result<Vec<u8>, DecodeError> {
+        decode(&self.text, base)
+    }
+
+    /// Copies the text into a [`Base94String`] with `base`.
+    ///
+    /// # Returns
+    ///
+    /// The owned string, or a `DecodeError` if `base` is not one the text is valid in.
+    pub fn to_encoded_string(&self, base: u8) -> Result<Base94String, DecodeError> {
+        Base94String::parse(&self.text, base)
+    }
+}
+
+impl Deref for EncodedStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl AsRef<str> for EncodedStr {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl fmt::Display for EncodedStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// Serializes the encoded text as a plain string. The base is not written.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Base94String {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.encoded)
+    }
+}
+
+/// Deserializes a plain string in base 94, validating it like [`Base94String::parse`], so
+/// untrusted input never produces an invalid instance. Text in another base should be
+/// stored together with its base and parsed with [`Base94String::parse`] instead.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Base94String {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(Base94StringVisitor)
+    }
+}
+
+/// Validates borrowed strings before copying them, and owned strings without copying.
+#[cfg(feature = "serde")]
+struct Base94StringVisitor;
+
+#[cfg(feature = "serde")]
+impl serde::de::Visitor<'_> for Base94StringVisitor {
+    type Value = Base94String;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a base 94 encoded string")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, encoded: &str) -> Result<Base94String, E> {
+        Base94String::parse(encoded, 94).map_err(E::custom)
+    }
+
+    fn visit_string<E: serde::de::Error>(self, encoded: String) -> Result<Base94String, E> {
+        Base94String::from_string(encoded, 94).map_err(E::custom)
+    }
+}
+
+/// Stores the encoded text as SQLite `TEXT`. The base is not stored.
+#[cfg(feature = "rusqlite")]
+impl rusqlite::types::ToSql for Base94String {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(self.encoded.as_str().into())
+    }
+}
+
+/// Reads base 94 `TEXT`, validating it like [`Base94String::parse`]. Invalid text is
+/// reported as `FromSqlError::Other` holding the [`DecodeError`], and values of other types
+/// as `FromSqlError::InvalidType`. A nullable column reads as `Option<Base94String>`.
+///
+/// # Examples
+///
+/// ```
+/// use base94::Base94String;
+/// use rusqlite::Connection;
+///
+/// let db = Connection::open_in_memory().unwrap();
+/// db.execute("CREATE TABLE blobs (data TEXT)", ()).unwrap();
+/// db.execute("INSERT INTO blobs VALUES (?1)", [Base94String::encode(b"\0\x01\xff", 94)])
+///     .unwrap();
+///
+/// let stored: Base94String = db.query_row("SELECT data FROM blobs", (), |row| row.get(0)).unwrap();
+/// assert_eq!(stored.decode(), b"\0\x01\xff");
+/// ```
+#[cfg(feature = "rusqlite")]
+impl rusqlite::types::FromSql for Base94String {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        Self::parse(value.as_str()?, 94)
+            .map_err(|err| rusqlite::types::FromSqlError::Other(std::boxed::Box::new(err)))
+    }
+}
+
+/// Wipes the encoded text, leaving an empty string that is still valid.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Base94String {
+    fn zeroize(&mut self) {
+        self.encoded.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Base94String {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Base94String {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_encode_and_decode() {
+        for base in 2..=MAX_BASE {
+            let data = (0..50).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let encoded = Base94String::encode(&data, base);
+            assert_eq!(encoded.as_str(), encode(&data, base));
+            assert_eq!(encoded.base(), base);
+            assert_eq!(encoded.decode(), data);
+
+            let parsed = Base94String::parse(&encoded, base).unwrap();
+            assert_eq!(parsed, encoded);
+            assert_eq!(parsed.decode(), data);
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_text() {
+        assert!(matches!(
+            Base94String::parse("ab\u{7f}", 94),
+            Err(DecodeError::InvalidCharacter {
+                c: '\u{7f}',
+                position: 2
+            })
+        ));
+        assert!(matches!(
+            Base94String::parse("AB", 10),
+            Err(DecodeError::DigitOutOfRange { position: 0, .. })
+        ));
+        assert!(matches!(
+            Base94String::parse("", 95),
+            Err(DecodeError::InvalidBase { base: 95 })
+        ));
+        assert!(matches!(
+            "ab ".parse::<Base94String>(),
+            Err(DecodeError::InvalidCharacter { c: ' ', .. })
+        ));
+    }
+
+    #[test]
+    fn test_string_traits() {
+        let encoded = Base94String::encode(b"Hello, World!", 94);
+        let text = encode(b"Hello, World!", 94);
+        assert_eq!(encoded.to_string(), text);
+        assert_eq!(encoded.len(), text.len());
+        assert_eq!(AsRef::<str>::as_ref(&encoded), text);
+        assert_eq!(text.parse::<Base94String>().unwrap(), encoded);
+        assert_eq!(String::from(encoded), text);
+    }
+
+    #[test]
+    fn test_comparisons_use_the_text() {
+        let low = Base94String::parse("!!", 94).unwrap();
+        let high = Base94String::parse("!\"", 3).unwrap();
+        assert!(low < high);
+        assert_eq!(low, Base94String::parse("!!", 2).unwrap());
+    }
+
+    #[test]
+    fn test_encoded_str_construction() {
+        for base in 2..=MAX_BASE {
+            let text = encode(b"Hello, World!", base);
+            let encoded = EncodedStr::new(&text, base).unwrap();
+            assert_eq!(encoded.as_str(), text);
+            assert_eq!(encoded.decode(base).unwrap(), b"Hello, World!");
+            assert_eq!(
+                encoded.to_encoded_string(base).unwrap(),
+                Base94String::encode(b"Hello, World!", base)
+            );
+        }
+
+        assert!(matches!(
+            EncodedStr::new("ab\u{7f}", 94),
+            Err(DecodeError::InvalidCharacter {
+                c: '\u{7f}',
+                position: 2
+            })
+        ));
+        assert!(matches!(
+            EncodedStr::new("AB", 10),
+            Err(DecodeError::DigitOutOfRange { position: 0, .. })
+        ));
+        assert!(matches!(
+            EncodedStr::new("AB", 95),
+            Err(DecodeError::InvalidBase { base: 95 })
+        ));
+        assert!(EncodedStr::new("AB", 94)
+            .unwrap()
+            .to_encoded_string(10)
+            .is_err());
+    }
+
+    #[test]
+    fn test_owned_and_borrowed_round_trip() {
+        use std::collections::{BTreeSet, HashSet};
+
+        let owned = Base94String::encode(b"Hello, World!", 94);
+        let borrowed: &EncodedStr = owned.borrow();
+        assert_eq!(borrowed, EncodedStr::new(owned.as_str(), 94).unwrap());
+        assert_eq!(borrowed.to_encoded_string(owned.base()).unwrap(), owned);
+        assert_eq!(&**borrowed, owned.as_str());
+
+        let hashed = HashSet::from([owned.clone()]);
+        let sorted = BTreeSet::from([owned.clone()]);
+        assert!(hashed.contains(borrowed));
+        assert!(sorted.contains(borrowed));
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize_clears_text() {
+        use zeroize::{Zeroize, ZeroizeOnDrop};
+
+        fn assert_zeroize_on_drop<T: Zeroize + ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<Base94String>();
+
+        let mut encoded = Base94String::encode(b"secret", 94);
+        encoded.zeroize();
+        assert_eq!(encoded.as_str(), "");
+        assert!(encoded.decode().is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let encoded = Base94String::encode(b"Hello, World!", 94);
+        let json = serde_json::to_string(&encoded).unwrap();
+        assert_eq!(json, serde_json::to_string(encoded.as_str()).unwrap());
+        let parsed: Base94String = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, encoded);
+        assert_eq!(parsed.base(), 94);
+        assert_eq!(parsed.decode(), b"Hello, World!");
+
+        let empty: Base94String = serde_json::from_str(r#""""#).unwrap();
+        assert_eq!(empty.as_str(), "");
+        assert!(empty.decode().is_empty());
+
+        let value = serde_json::to_value(&encoded).unwrap();
+        assert_eq!(
+            serde_json::from_value::<Base94String>(value).unwrap(),
+            encoded
+        );
+
+        let bytes = bincode::serialize(&encoded).unwrap();
+        assert_eq!(
+            bincode::deserialize::<Base94String>(&bytes).unwrap(),
+            encoded
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_rejects_invalid_text() {
+        let error = serde_json::from_str::<Base94String>(r#""ab c""#)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("position 2"), "{}", error);
+
+        // `serde_json::Value` hands over owned strings, which take the other visitor path.
+        let error = serde_json::from_value::<Base94String>("ab c".into())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("position 2"), "{}", error);
+
+        assert!(serde_json::from_str::<Base94String>("42").is_err());
+    }
+
+    /// Round-trips binary data through an in-memory database and reads it back decoded.
+    #[cfg(feature = "rusqlite")]
+    #[test]
+    fn test_rusqlite_query_decoded() {
+        use rusqlite::Connection;
+
+        let db = Connection::open_in_memory().unwrap();
+        db.execute("CREATE TABLE blobs (id INTEGER PRIMARY KEY, data TEXT)", ())
+            .unwrap();
+        let rows = (0..20)
+            .map(|len| (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>())
+            .collect::<Vec<_>>();
+        for data in &rows {
+            db.execute(
+                "INSERT INTO blobs (data) VALUES (?1)",
+                [Base94String::encode(data, 94)],
+            )
+            .unwrap();
+        }
+        db.execute("INSERT INTO blobs (data) VALUES (NULL)", ())
+            .unwrap();
+
+        let mut query = db.prepare("SELECT data FROM blobs ORDER BY id").unwrap();
+        let query_decoded = query
+            .query_map((), |row| row.get::<_, Option<Base94String>>(0))
+            .unwrap()
+            .map(|row| row.unwrap().map(|encoded| encoded.decode()))
+            .collect::<Vec<_>>();
+        let expected = rows.into_iter().map(Some).chain([None]).collect::<Vec<_>>();
+        assert_eq!(query_decoded, expected);
+
+        let stored: String = db
+            .query_row("SELECT data FROM blobs WHERE id = 2", (), |row| row.get(0))
+            .unwrap();
+        assert_eq!(stored, encode(expected[1].as_deref().unwrap(), 94));
+    }
+
+    #[cfg(feature = "rusqlite")]
+    #[test]
+    fn test_rusqlite_rejects_invalid_text() {
+        use rusqlite::types::{FromSql, FromSqlError, ValueRef};
+        use rusqlite::{Connection, Error};
+
+        let db = Connection::open_in_memory().unwrap();
+        let error = db
+            .query_row("SELECT 'ab c'", (), |row| row.get::<_, Base94String>(0))
+            .unwrap_err();
+        let Error::FromSqlConversionFailure(0, _, source) = &error else {
+            panic!("unexpected error {:?}", error);
+        };
+        assert_eq!(
+            source.downcast_ref::<DecodeError>(),
+            Some(&DecodeError::InvalidCharacter {
+                c: ' ',
+                position: 2
+            })
+        );
+
+        let error = db
+            .query_row("SELECT 42", (), |row| row.get::<_, Base94String>(0))
+            .unwrap_err();
+        assert!(
+            matches!(error, Error::InvalidColumnType(0, _, _)),
+            "{:?}",
+            error
+        );
+        assert!(matches!(
+            Base94String::column_result(ValueRef::Null),
+            Err(FromSqlError::InvalidType)
+        ));
+    }
+}
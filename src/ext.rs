@@ -0,0 +1,118 @@
+//! Extension traits that add encoding and decoding methods to byte slices and strings.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{decode, encode, DecodeError};
+
+/// Encodes bytes with method syntax, as a thin wrapper over [`encode`].
+///
+/// # Examples
+///
+/// ```
+/// use base94::prelude::*;
+///
+/// let data = b"Hello, World!".to_vec();
+/// assert_eq!(data.to_base94(), base94::encode(&data, 94));
+/// assert_eq!(data[..5].to_base_n(50), base94::encode(b"Hello", 50));
+/// ```
+pub trait ToBaseEncoded {
+    /// Encodes `self` in base 94.
+    fn to_base94(&self) -> String {
+        self.to_base_n(94)
+    }
+
+    /// Encodes `self` in `base`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the specified base is outside the valid range (2 to 94).
+    fn to_base_n(&self, base: u8) -> String;
+}
+
+impl ToBaseEncoded for [u8] {
+    fn to_base_n(&self, base: u8) -> String {
+        encode(self, base)
+    }
+}
+
+impl ToBaseEncoded for Vec<u8> {
+    fn to_base_n(&self, base: u8) -> String {
+        encode(self, base)
+    }
+}
+
+impl<T: ToBaseEncoded + ?Sized> ToBaseEncoded for &T {
+    fn to_base_n(&self, base: u8) -> String {
+        (**self).to_base_n(base)
+    }
+}
+
+/// Decodes strings with method syntax, as a thin wrapper over [`decode`].
+///
+/// # Examples
+///
+/// ```
+/// use base94::prelude::*;
+///
+/// let encoded = b"Hello, World!".to_base94();
+/// assert_eq!(encoded.from_base94().unwrap(), b"Hello, World!");
+/// assert_eq!(
+///     "A@#D9".from_base_n(94).unwrap(),
+///     base94::decode("A@#D9", 94).unwrap()
+/// );
+/// ```
+#[allow(clippy::wrong_self_convention)]
+pub trait FromBaseEncoded {
+    /// Decodes `self` from base 94.
+    ///
+    /// # Returns
+    ///
+    /// The decoded bytes, or a `DecodeError` under the same conditions as [`decode`].
+    fn from_base94(&self) -> Result<Vec<u8>, DecodeError> {
+        self.from_base_n(94)
+    }
+
+    /// Decodes `self` from `base`.
+    ///
+    /// # Returns
+    ///
+    /// The decoded bytes, or a `DecodeError` under the same conditions as [`decode`].
+    fn from_base_n(&self, base: u8) -> Result<Vec<u8>, DecodeError>;
+}
+
+impl FromBaseEncoded for str {
+    fn from_base_n(&self, base: u8) -> Result<Vec<u8>, DecodeError> {
+        decode(self, base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_methods_match_free_functions() {
+        for base in 2..=MAX_BASE {
+            let data = (0..50).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let encoded = encode(&data, base);
+            assert_eq!(data.to_base_n(base), encoded);
+            assert_eq!(data.as_slice().to_base_n(base), encoded);
+            assert_eq!((&&data[..]).to_base_n(base), encoded);
+            assert_eq!(encoded.from_base_n(base).unwrap(), data);
+        }
+
+        let data = b"Hello, World!";
+        assert_eq!(data.to_base94(), encode(data, 94));
+        assert_eq!(data.to_base94().from_base94().unwrap(), data);
+        assert!(matches!(
+            "ab\u{7f}".from_base94(),
+            Err(DecodeError::InvalidCharacter {
+                c: '\u{7f}',
+                position: 2
+            })
+        ));
+    }
+}
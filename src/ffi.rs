@@ -0,0 +1,313 @@
+//! A C interface to [`encode`](crate::encode) and [`decode`](crate::decode).
+//!
+//! The functions are exported from the `cdylib` build with unmangled names, and
+//! `include/base94.h` declares them for C and C++. Every function returns a status code:
+//! `BASE94_OK` on success, the [`DecodeError::code`] of a decode error, or one of the
+//! `BASE94_ERROR_*` codes below. The code and the position of the last error on the calling
+//! thread are also available from [`base94_last_error_code`] and
+//! [`base94_last_error_position`].
+//!
+//! Output buffers are allocated by this library and must be released with [`base94_free`],
+//! passing the length that was returned with them. Each buffer has a NUL byte after its last
+//! byte, so encoded text can be used as a C string directly.
+//!
+//! No function unwinds into the caller: panics are caught and reported as
+//! `BASE94_ERROR_PANIC`.
+//!
+//! ```c
+//! char *encoded;
+//! size_t encoded_len;
+//! if (base94_encode((const uint8_t *)"Hello", 5, 94, &encoded, &encoded_len) == BASE94_OK) {
+//!     puts(encoded);
+//!     base94_free((uint8_t *)encoded, encoded_len);
+//! }
+//! ```
+
+use std::cell::Cell;
+use std::os::raw::c_char;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+
+use crate::{decode_bytes, try_encode, DecodeError};
+
+/// The call succeeded.
+pub const BASE94_OK: u32 = 0;
+/// `base` is outside the valid range (2 to 94). Equal to the code of
+/// `DecodeError::InvalidBase`, which decoding reports for the same condition.
+pub const BASE94_ERROR_INVALID_BASE: u32 = 3;
+/// A required pointer argument was null.
+pub const BASE94_ERROR_NULL_POINTER: u32 = 1000;
+/// The library panicked. This is a bug; the outputs are left empty.
+pub const BASE94_ERROR_PANIC: u32 = 1001;
+
+/// Returned by [`base94_last_error_position`] when the last error has no position.
+pub const BASE94_NO_POSITION: usize = usize::MAX;
+
+thread_local! {
+    static LAST_ERROR: Cell<(u32, usize)> = const { Cell::new((BASE94_OK, BASE94_NO_POSITION)) };
+}
+
+/// Records the outcome of a call for the `base94_last_error_*` functions and returns its code.
+fn status(code: u32, position: Option<usize>) -> u32 {
+    LAST_ERROR.with(|last| last.set((code, position.unwrap_or(BASE94_NO_POSITION))));
+    code
+}
+
+/// Runs `f`, storing its output in `out` and `out_len`, and turns errors and panics into
+/// status codes. On failure the outputs are set to null and 0.
+///
+/// # Safety
+///
+/// `out` and `out_len` must be null or valid for writes.
+unsafe fn run<T>(
+    out: *mut *mut T,
+    out_len: *mut usize,
+    f: impl FnOnce() -> Result<Vec<u8>, (u32, Option<usize>)>,
+) -> u32 {
+    if out.is_null() || out_len.is_null() {
+        return status(BASE94_ERROR_NULL_POINTER, None);
+    }
+    *out = ptr::null_mut();
+    *out_len = 0;
+
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(mut bytes)) => {
+            let len = bytes.len();
+            bytes.push(0);
+            *out = Box::into_raw(bytes.into_boxed_slice()).cast::<T>();
+            *out_len = len;
+            status(BASE94_OK, None)
+        }
+        Ok(Err((code, position))) => status(code, position),
+        Err(_) => status(BASE94_ERROR_PANIC, None),
+    }
+}
+
+/// Returns the `len` bytes at `data`, allowing a null `data` when `len` is 0.
+///
+/// # Safety
+///
+/// `data` must be null or valid for reads of `len` bytes.
+unsafe fn input<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    match (data.is_null(), len) {
+        (true, 0) => Some(&[]),
+        (true, _) => None,
+        (false, _) => Some(slice::from_raw_parts(data, len)),
+    }
+}
+
+/// Encodes the `len` bytes at `data` in `base`.
+///
+/// On success, `*out` points to the encoded text, `*out_len` is its length without the
+/// terminating NUL, and the result is `BASE94_OK`. The text must be released with
+/// [`base94_free`].
+///
+/// # Returns
+///
+/// `BASE94_OK`, `BASE94_ERROR_INVALID_BASE`, `BASE94_ERROR_NULL_POINTER` if `out`, `out_len`,
+/// or `data` with a nonzero `len` is null, or `BASE94_ERROR_PANIC`.
+///
+/// # Safety
+///
+/// `data` must be null or valid for reads of `len` bytes, and `out` and `out_len` must be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn base94_encode(
+    data: *const u8,
+    len: usize,
+    base: u8,
+    out: *mut *mut c_char,
+    out_len: *mut usize,
+) -> u32 {
+    let Some(data) = input(data, len) else {
+        return status(BASE94_ERROR_NULL_POINTER, None);
+    };
+    run(out, out_len, || {
+        try_encode(data, base)
+            .map(String::into_bytes)
+            .map_err(|_| (BASE94_ERROR_INVALID_BASE, None))
+    })
+}
+
+/// Decodes the `len` bytes of text at `encoded` in `base`. The text does not need to be
+/// NUL-terminated or valid UTF-8.
+///
+/// On success, `*out` points to the decoded bytes, `*out_len` is their number, and the
+/// result is `BASE94_OK`. The bytes must be released with [`base94_free`].
+///
+/// # Returns
+///
+/// `BASE94_OK`, the [`DecodeError::code`] of the decode error, whose byte offset is then
+/// available from [`base94_last_error_position`], `BASE94_ERROR_NULL_POINTER` if `out`,
+/// `out_len`, or `encoded` with a nonzero `len` is null, or `BASE94_ERROR_PANIC`.
+///
+/// # Safety
+///
+/// `encoded` must be null or valid for reads of `len` bytes, and `out` and `out_len` must be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn base94_decode(
+    encoded: *const c_char,
+    len: usize,
+    base: u8,
+    out: *mut *mut u8,
+    out_len: *mut usize,
+) -> u32 {
+    let Some(encoded) = input(encoded.cast(), len) else {
+        return status(BASE94_ERROR_NULL_POINTER, None);
+    };
+    run(out, out_len, || {
+        decode_bytes(encoded, base).map_err(|err: DecodeError| (err.code(), err.position()))
+    })
+}
+
+/// Releases a buffer returned by [`base94_encode`] or [`base94_decode`]. `len` is the length
+/// returned with it. A null `ptr` is ignored.
+///
+/// # Safety
+///
+/// `ptr` must be null or a buffer returned by this library that has not been released yet,
+/// and `len` must be the length returned with it.
+#[no_mangle]
+pub unsafe extern "C" fn base94_free(ptr: *mut u8, len: usize) {
+    if !ptr.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(ptr, len + 1)));
+    }
+}
+
+/// Returns the status code of the last call on this thread, `BASE94_OK` if it succeeded.
+#[no_mangle]
+pub extern "C" fn base94_last_error_code() -> u32 {
+    LAST_ERROR.with(|last| last.get().0)
+}
+
+/// Returns the byte offset of the last decode error on this thread, or
+/// `BASE94_NO_POSITION` if the last call succeeded or its error has no position.
+#[no_mangle]
+pub extern "C" fn base94_last_error_position() -> usize {
+    LAST_ERROR.with(|last| last.get().1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode;
+
+    /// Calls `base94_encode` on `data` and copies the result.
+    fn ffi_encode(data: &[u8], base: u8) -> Result<String, u32> {
+        let mut out = ptr::null_mut();
+        let mut out_len = 0;
+        let code =
+            unsafe { base94_encode(data.as_ptr(), data.len(), base, &mut out, &mut out_len) };
+        if code != BASE94_OK {
+            assert!(out.is_null());
+            return Err(code);
+        }
+        let encoded = unsafe { slice::from_raw_parts(out.cast::<u8>(), out_len + 1) }.to_vec();
+        unsafe { base94_free(out.cast(), out_len) };
+        assert_eq!(encoded.last(), Some(&0));
+        Ok(String::from_utf8(encoded[..out_len].to_vec()).unwrap())
+    }
+
+    /// Calls `base94_decode` on `encoded` and copies the result.
+    fn ffi_decode(encoded: &[u8], base: u8) -> Result<Vec<u8>, u32> {
+        let mut out = ptr::null_mut();
+        let mut out_len = 0;
+        let code = unsafe {
+            base94_decode(
+                encoded.as_ptr().cast(),
+                encoded.len(),
+                base,
+                &mut out,
+                &mut out_len,
+            )
+        };
+        if code != BASE94_OK {
+            assert!(out.is_null());
+            assert_eq!(out_len, 0);
+            return Err(code);
+        }
+        let decoded = unsafe { slice::from_raw_parts(out, out_len) }.to_vec();
+        unsafe { base94_free(out, out_len) };
+        Ok(decoded)
+    }
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_round_trip() {
+        for base in 2..=MAX_BASE {
+            let data = (0..100).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let encoded = ffi_encode(&data, base).unwrap();
+            assert_eq!(encoded, encode(&data, base));
+            assert_eq!(ffi_decode(encoded.as_bytes(), base).unwrap(), data);
+            assert_eq!(base94_last_error_code(), BASE94_OK);
+            assert_eq!(base94_last_error_position(), BASE94_NO_POSITION);
+        }
+        assert_eq!(ffi_encode(&[], 94).unwrap(), "");
+        assert_eq!(ffi_decode(b"", 94).unwrap(), b"");
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(ffi_encode(b"Hi", 95), Err(BASE94_ERROR_INVALID_BASE));
+        assert_eq!(base94_last_error_code(), BASE94_ERROR_INVALID_BASE);
+        assert_eq!(ffi_decode(b"!!", 1), Err(BASE94_ERROR_INVALID_BASE));
+
+        assert_eq!(ffi_decode(b"ab \xff", 94), Err(1));
+        assert_eq!(base94_last_error_code(), 1);
+        assert_eq!(base94_last_error_position(), 2);
+        assert_eq!(ffi_decode(b"!!~", 10), Err(2));
+        assert_eq!(base94_last_error_position(), 2);
+    }
+
+    #[test]
+    fn test_null_pointers() {
+        let mut out = ptr::null_mut();
+        let mut out_len = 0;
+        unsafe {
+            assert_eq!(
+                base94_encode(ptr::null(), 0, 94, &mut out, &mut out_len),
+                BASE94_OK
+            );
+            assert_eq!(out_len, 0);
+            assert_eq!(*out, 0);
+            base94_free(out.cast(), out_len);
+
+            assert_eq!(
+                base94_encode(ptr::null(), 1, 94, &mut out, &mut out_len),
+                BASE94_ERROR_NULL_POINTER
+            );
+            assert_eq!(
+                base94_decode(ptr::null(), 1, 94, &mut ptr::null_mut(), &mut out_len),
+                BASE94_ERROR_NULL_POINTER
+            );
+            assert_eq!(
+                base94_encode(b"Hi".as_ptr(), 2, 94, ptr::null_mut(), &mut out_len),
+                BASE94_ERROR_NULL_POINTER
+            );
+            assert_eq!(
+                base94_decode(
+                    b"!!".as_ptr().cast(),
+                    2,
+                    94,
+                    &mut ptr::null_mut(),
+                    ptr::null_mut()
+                ),
+                BASE94_ERROR_NULL_POINTER
+            );
+            assert_eq!(base94_last_error_code(), BASE94_ERROR_NULL_POINTER);
+            base94_free(ptr::null_mut(), 0);
+        }
+    }
+
+    #[test]
+    fn test_last_error_is_per_thread() {
+        assert_eq!(ffi_decode(b" ", 94), Err(1));
+        std::thread::spawn(|| assert_eq!(base94_last_error_code(), BASE94_OK))
+            .join()
+            .unwrap();
+        assert_eq!(base94_last_error_code(), 1);
+    }
+}
@@ -0,0 +1,273 @@
+//! A uuencode-style container that keeps a file's name and permission bits with its data.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::checked::crc32;
+use crate::{decode, encode_wrapped, ArmorError, DecodeError, LineEnding};
+
+/// The base of the payload.
+const BASE: u8 = 94;
+
+/// The number of characters per payload line.
+const LINE_WIDTH: usize = 64;
+
+/// The mode recorded when the platform has no Unix permission bits.
+#[cfg(all(feature = "std", not(unix)))]
+const DEFAULT_MODE: u32 = 0o644;
+
+/// A file's name, Unix permission bits, and contents, as stored by
+/// [`to_armored`](Self::to_armored) and read back by [`decode_file_armored`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArmoredFile {
+    /// The file name, without any directory.
+    pub name: String,
+    /// The permission bits, such as `0o644`.
+    pub mode: u32,
+    /// The contents of the file.
+    pub data: Vec<u8>,
+}
+
+impl ArmoredFile {
+    /// Writes the file as text:
+    ///
+    /// ```text
+    /// begin 644 notes.txt
+    /// <contents in base 94, wrapped at 64 characters>
+    /// end
+    /// crc32 <CRC-32 of the contents in hex>
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `name` contains a line break or `mode` has bits above
+    /// `0o7777`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base94::{decode_file_armored, ArmoredFile};
+    ///
+    /// let file = ArmoredFile {
+    ///     name: "my notes.txt".to_string(),
+    ///     mode: 0o600,
+    ///     data: b"Hello, World!".to_vec(),
+    /// };
+    /// let armored = file.to_armored();
+    /// assert!(armored.starts_with("begin 600 my notes.txt\n"));
+    /// assert_eq!(decode_file_armored(&armored).unwrap(), file);
+    /// ```
+    pub fn to_armored(&self) -> String {
+        if self.name.contains(['\n', '\r']) {
+            panic!("File name {:?} contains a line break", self.name);
+        }
+        if self.mode > 0o7777 {
+            panic!("Invalid file mode {:o}", self.mode);
+        }
+
+        let payload = encode_wrapped(&self.data, BASE, LINE_WIDTH, LineEnding::Lf);
+        let mut out = String::with_capacity(payload.len() + self.name.len() + 64);
+        out.push_str(&format!("begin {:03o} {}\n", self.mode, self.name));
+        if !payload.is_empty() {
+            out.push_str(&payload);
+            out.push('\n');
+        }
+        out.push_str(&format!("end\ncrc32 {:08x}\n", crc32(&self.data)));
+        out
+    }
+}
+
+/// Reads the file at `path` and writes it with its name and permission bits, as
+/// [`ArmoredFile::to_armored`] does.
+///
+/// On platforms without Unix permission bits the mode is recorded as `644`.
+///
+/// # Returns
+///
+/// The armored text, or an `io::Error` if the file cannot be read or its name is not valid
+/// UTF-8 or contains a line break.
+#[cfg(feature = "std")]
+pub fn encode_file_armored(path: impl AsRef<std::path::Path>) -> std::io::Result<String> {
+    use std::io::{Error, ErrorKind};
+
+    let path = path.as_ref();
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .filter(|name| !name.contains(['\n', '\r']))
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "unsupported file name"))?;
+    let data = std::fs::read(path)?;
+
+    #[cfg(unix)]
+    let mode = {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::metadata(path)?.permissions().mode() & 0o7777
+    };
+    #[cfg(not(unix))]
+    let mode = DEFAULT_MODE;
+
+    Ok(ArmoredFile {
+        name: name.to_string(),
+        mode,
+        data,
+    }
+    .to_armored())
+}
+
+/// Finds the first file written by [`ArmoredFile::to_armored`] in `text` and decodes it.
+///
+/// Text before the `begin` line and after the checksum line is ignored, as are blank lines
+/// and whitespace around every line, so a name that ends in whitespace loses it.
+///
+/// # Returns
+///
+/// The file, or an `ArmorError` if:
+/// - There is no `begin` line, its mode is not an octal number up to `7777`, or the
+///   checksum is not hex.
+/// - The text ends before the `end` and checksum lines, as when it was cut off.
+/// - The payload does not decode, or `DecodeError::ChecksumMismatch` if it decodes to
+///   different data than was written.
+pub fn decode_file_armored(text: &str) -> Result<ArmoredFile, ArmorError> {
+    let mut lines = text.lines();
+    let header = lines
+        .by_ref()
+        .find_map(|line| line.trim().strip_prefix("begin "))
+        .ok_or(ArmorError::MissingBegin)?;
+    let invalid_header = || ArmorError::InvalidHeader {
+        line: format!("begin {}", header),
+    };
+    let (mode, name) = header.split_once(' ').ok_or_else(invalid_header)?;
+    let mode = u32::from_str_radix(mode, 8)
+        .ok()
+        .filter(|&mode| mode <= 0o7777)
+        .ok_or_else(invalid_header)?;
+
+    // Payload lines never contain spaces, so the checksum line is the first line that does,
+    // and the `end` line is the last one before it. Looking for `end` directly could stop
+    // at a payload line that happens to read "end".
+    let mut payload = Vec::new();
+    let mut expected = None;
+    for line in lines.map(str::trim).filter(|line| !line.is_empty()) {
+        if let Some(checksum) = line.strip_prefix("crc32 ") {
+            expected = Some(checksum);
+            break;
+        }
+        payload.push(line);
+    }
+    let (Some(expected), Some(&"end")) = (expected, payload.last()) else {
+        return Err(ArmorError::Truncated);
+    };
+    payload.pop();
+
+    let expected = u32::from_str_radix(expected, 16).map_err(|_| ArmorError::InvalidHeader {
+        line: format!("crc32 {}", expected),
+    })?;
+    let data = decode(&payload.concat(), BASE)?;
+    let actual = crc32(&data);
+    if expected != actual {
+        return Err(DecodeError::ChecksumMismatch { expected, actual }.into());
+    }
+    Ok(ArmoredFile {
+        name: name.to_string(),
+        mode,
+        data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_file(len: usize) -> ArmoredFile {
+        ArmoredFile {
+            name: "report final (2).pdf".to_string(),
+            mode: 0o640,
+            data: (0..len).map(|_| rand::random::<u8>()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for len in [0, 1, 52, 53, 1000] {
+            let file = random_file(len);
+            let armored = file.to_armored();
+            assert!(armored.lines().all(|line| line.len() <= LINE_WIDTH));
+            assert_eq!(decode_file_armored(&armored).unwrap(), file);
+
+            let pasted = format!(
+                "Attached:\r\n\r\n{}\r\n-- \r\n",
+                armored.replace('\n', " \r\n")
+            );
+            assert_eq!(decode_file_armored(&pasted).unwrap(), file);
+        }
+    }
+
+    #[test]
+    fn test_payload_line_reading_end() {
+        let mut file = random_file(0);
+        file.data = crate::decode("end", BASE).unwrap();
+        let armored = file.to_armored();
+        assert!(armored.contains("\nend\nend\n"));
+        assert_eq!(decode_file_armored(&armored).unwrap(), file);
+    }
+
+    #[test]
+    fn test_truncated() {
+        let armored = random_file(500).to_armored();
+        let lines = armored.lines().collect::<Vec<&str>>();
+        for len in 1..lines.len() {
+            let truncated = lines[..len].join("\n");
+            assert!(matches!(
+                decode_file_armored(&truncated),
+                Err(ArmorError::Truncated)
+            ));
+        }
+
+        let mut lines = lines;
+        lines.remove(3);
+        assert!(matches!(
+            decode_file_armored(&lines.join("\n")),
+            Err(ArmorError::Decode(DecodeError::ChecksumMismatch { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        assert!(matches!(
+            decode_file_armored("no file here"),
+            Err(ArmorError::MissingBegin)
+        ));
+        for header in ["begin 999 a", "begin 17777 a", "begin 644"] {
+            let text = format!("{}\nend\ncrc32 00000000\n", header);
+            assert!(matches!(
+                decode_file_armored(&text),
+                Err(ArmorError::InvalidHeader { line }) if line == header
+            ));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_encode_file() {
+        let dir = std::env::temp_dir().join(format!("base94-armor-{}", rand::random::<u64>()));
+        std::fs::create_dir(&dir).unwrap();
+        let path = dir.join("file with spaces.bin");
+        let data = (0..300).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+        std::fs::write(&path, &data).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o751)).unwrap();
+        }
+
+        let file = decode_file_armored(&encode_file_armored(&path).unwrap()).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(file.name, "file with spaces.bin");
+        assert_eq!(file.data, data);
+        #[cfg(unix)]
+        assert_eq!(file.mode, 0o751);
+        #[cfg(not(unix))]
+        assert_eq!(file.mode, DEFAULT_MODE);
+    }
+}
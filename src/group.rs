@@ -0,0 +1,219 @@
+//! Grouped output like `XXXXX-XXXXX-XXXXX` for strings that people read out or type in.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{
+    decode_digits, digit_value, encode_digits, is_valid_base, wipe, DecodeError, EncodeError,
+    CHARACTERS,
+};
+
+/// The digit that marks the end of the encoding before the padding of the last group.
+const END_MARKER: u8 = 1;
+
+/// Checks that `separator` is not one of the characters of `base`.
+fn check_separator(separator: char, base: u8) -> Result<(), DecodeError> {
+    if separator.is_ascii() && CHARACTERS[..base as usize].contains(&(separator as u8)) {
+        return Err(DecodeError::SeparatorInAlphabet { separator, base });
+    }
+    Ok(())
+}
+
+/// Encodes a slice of bytes in groups of `group_size` characters joined by `separator`.
+///
+/// Every group is full: the encoding of [`encode`](crate::encode) is followed by one
+/// `"` (digit 1) and then `!` (digit 0) up to a multiple of `group_size`. The marker is
+/// always written, even if the encoding already fills its last group, so
+/// [`decode_grouped`] can strip the padding by removing the last non-zero digit and
+/// everything after it.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+/// * `group_size` - The number of characters per group. Must be at least 1.
+/// * `separator` - The character between groups. Must not be a character of `base`.
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94),
+/// `group_size` is 0, or `separator` is one of the characters of `base`.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_grouped, encode_grouped};
+///
+/// let key = encode_grouped(b"license", 94, 5, ' ');
+/// assert!(key.split(' ').all(|group| group.len() == 5));
+/// assert_eq!(decode_grouped(&key, 94, ' ').unwrap(), b"license");
+///
+/// // `-` is a digit from base 13 up, so it only separates groups in small bases.
+/// let key = encode_grouped(b"license", 10, 5, '-');
+/// assert_eq!(decode_grouped(&key, 10, '-').unwrap(), b"license");
+/// ```
+pub fn encode_grouped(data: &[u8], base: u8, group_size: usize, separator: char) -> String {
+    if !is_valid_base(base) {
+        panic!("{}", EncodeError::InvalidBase { base });
+    }
+    if group_size == 0 {
+        panic!("Group size must be at least 1");
+    }
+    if let Err(err) = check_separator(separator, base) {
+        panic!("{}", err);
+    }
+
+    let mut digits = encode_digits(data, base);
+    digits.push(END_MARKER);
+    digits.resize(digits.len().next_multiple_of(group_size), 0);
+
+    let groups = digits.len() / group_size;
+    let mut out = String::with_capacity(digits.len() + (groups - 1) * separator.len_utf8());
+    for (i, group) in digits.chunks(group_size).enumerate() {
+        if i > 0 {
+            out.push(separator);
+        }
+        out.extend(
+            group
+                .iter()
+                .map(|&digit| CHARACTERS[digit as usize] as char),
+        );
+    }
+    wipe(&mut digits);
+    out
+}
+
+/// Decodes a string produced by [`encode_grouped`] with the same base and separator.
+///
+/// The separators are skipped wherever they are, so a string that was regrouped or had
+/// separators dropped during transcription still decodes.
+///
+/// # Arguments
+///
+/// * `encoded` - The grouped string to decode.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+/// * `separator` - The character between groups.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `DecodeError` if:
+/// - The base is invalid, or `separator` is one of its characters.
+/// - `encoded` contains a character other than the digits of `base` and `separator`.
+/// - The last non-zero digit is not the end marker, with `DecodeError::InvalidPadding`.
+pub fn decode_grouped(encoded: &str, base: u8, separator: char) -> Result<Vec<u8>, DecodeError> {
+    if !is_valid_base(base) {
+        return Err(DecodeError::InvalidBase { base });
+    }
+    check_separator(separator, base)?;
+
+    let mut digits = Vec::with_capacity(encoded.len());
+    let mut last_nonzero = None;
+    for (position, c) in encoded.char_indices() {
+        if c == separator {
+            continue;
+        }
+        let digit = match digit_value(&encoded.as_bytes()[position..], position, base) {
+            Ok(digit) => digit,
+            Err(err) => {
+                wipe(&mut digits);
+                return Err(err);
+            }
+        };
+        if digit != 0 {
+            last_nonzero = Some((digits.len(), position));
+        }
+        digits.push(digit);
+    }
+
+    let end = match last_nonzero {
+        Some((index, _)) if digits[index] == END_MARKER => index,
+        found => {
+            wipe(&mut digits);
+            return Err(DecodeError::InvalidPadding {
+                position: found.map_or(encoded.len(), |(_, position)| position),
+            });
+        }
+    };
+    let decoded = decode_digits(&digits[..end], base);
+    wipe(&mut digits);
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_round_trip() {
+        for base in 2..=MAX_BASE {
+            for group_size in 1..=7 {
+                for data in [&b"Hello, World!"[..], &[], &[0], &[1, 0, 0]] {
+                    let mut separators = vec![' ', '\u{b7}'];
+                    if base <= 12 {
+                        separators.extend(['-', '.']);
+                    }
+                    for separator in separators {
+                        let grouped = encode_grouped(data, base, group_size, separator);
+                        assert!(grouped
+                            .split(separator)
+                            .all(|group| group.len() == group_size));
+                        assert_eq!(decode_grouped(&grouped, base, separator).unwrap(), data);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_padding() {
+        let encoded = crate::encode(b"Hello", 94);
+        assert_eq!(encoded.len(), 6);
+        let grouped = encode_grouped(b"Hello", 94, 3, ' ');
+        assert_eq!(grouped, format!("{} {} \"!!", &encoded[..3], &encoded[3..]));
+        let grouped = encode_grouped(b"Hello", 94, 4, ' ');
+        assert_eq!(grouped, format!("{} {}\"!", &encoded[..4], &encoded[4..]));
+        let grouped = encode_grouped(b"Hello", 94, 7, ' ');
+        assert_eq!(grouped, format!("{}\"", encoded));
+    }
+
+    #[test]
+    fn test_separators_are_skipped_anywhere() {
+        let grouped = encode_grouped(b"Hello, World!", 10, 4, '-');
+        let regrouped = grouped.replace('-', "").replace("", "-");
+        assert_eq!(
+            decode_grouped(&regrouped, 10, '-').unwrap(),
+            b"Hello, World!"
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            decode_grouped("abc-def", 94, '-'),
+            Err(DecodeError::SeparatorInAlphabet {
+                separator: '-',
+                base: 94
+            })
+        ));
+        assert!(matches!(
+            decode_grouped("AB!! !!!!", 94, ' '),
+            Err(DecodeError::InvalidPadding { position: 1 })
+        ));
+        assert!(matches!(
+            decode_grouped("!!!! !!!!", 94, ' '),
+            Err(DecodeError::InvalidPadding { position: 9 })
+        ));
+        assert!(matches!(
+            decode_grouped("\"!\"!-\u{b7}", 10, '-'),
+            Err(DecodeError::InvalidCharacter { position: 5, .. })
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_separator_in_alphabet_panics() {
+        encode_grouped(b"Hello", 94, 5, '-');
+    }
+}
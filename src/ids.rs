@@ -0,0 +1,261 @@
+//! Short, non-sequential strings for integer IDs, in the spirit of the hashids libraries.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Alphabet, DecodeError};
+
+/// Encodes `u64` IDs as strings that hide their order, for database IDs in URLs.
+///
+/// The salt shuffles the alphabet, and a leading character derived from the ID selects a
+/// different offset for every following digit, so consecutive IDs look unrelated and the
+/// same ID looks different under another salt. Each ID has exactly one encoding for a given
+/// codec, and the output is the same in every version of this crate.
+///
+/// This hides order from casual observers but is not encryption: anyone with enough
+/// encoded IDs, or the salt, can work out the mapping. Do not rely on it for access control.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{presets, IdCodec};
+///
+/// let codec = IdCodec::new("my salt", 8, presets::BASE62);
+/// let id = codec.encode_id(12345);
+/// assert_eq!(id.len(), 8);
+/// assert_eq!(codec.decode_id(&id).unwrap(), 12345);
+/// assert_ne!(codec.encode_id(12346)[1..], id[1..]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IdCodec {
+    /// The alphabet in the order chosen by the salt.
+    alphabet: Alphabet,
+    /// The hash of the salt that seeds every other choice.
+    seed: u64,
+    min_length: usize,
+}
+
+impl IdCodec {
+    /// Creates a codec whose output uses the characters of `alphabet` in an order chosen by
+    /// `salt`, and is at least `min_length` characters long.
+    ///
+    /// # Arguments
+    ///
+    /// * `salt` - Any string. Codecs with different salts produce different strings.
+    /// * `min_length` - The length that shorter output is padded to. Output is always at
+    ///   least 2 characters long.
+    /// * `alphabet` - The characters of the output. Its length is the base of the digits.
+    pub fn new(salt: &str, min_length: usize, alphabet: Alphabet) -> Self {
+        let seed = fnv1a(salt.as_bytes());
+        let mut symbols = alphabet.chars().to_vec();
+        let mut state = seed;
+        for i in (1..symbols.len()).rev() {
+            state = mix(state);
+            symbols.swap(i, (state % (i as u64 + 1)) as usize);
+        }
+        let alphabet = Alphabet::new(&symbols).expect("a shuffled alphabet is still valid");
+        Self {
+            alphabet,
+            seed,
+            min_length,
+        }
+    }
+
+    /// Encodes `id`.
+    ///
+    /// The output is one character that varies with the ID, followed by the digits of the
+    /// ID, most significant first, padded with leading zero digits to `min_length`.
+    pub fn encode_id(&self, id: u64) -> String {
+        let base = self.alphabet.base() as u64;
+        let mut digits = Vec::new();
+        let mut value = id;
+        loop {
+            digits.push((value % base) as u8);
+            value /= base;
+            if value == 0 {
+                break;
+            }
+        }
+        digits.resize(digits.len().max(self.min_length.saturating_sub(1)), 0);
+        digits.reverse();
+
+        let lottery = (mix(self.seed ^ id) % base) as u8;
+        let mut encoded = String::with_capacity(digits.len() + 1);
+        encoded.push(self.char_at(lottery));
+        for (position, digit) in digits.into_iter().enumerate() {
+            let offset = self.offset(lottery, position);
+            encoded.push(self.char_at(((digit as u64 + offset) % base) as u8));
+        }
+        encoded
+    }
+
+    /// Decodes a string produced by [`encode_id`](Self::encode_id) with the same salt,
+    /// minimum length, and alphabet.
+    ///
+    /// # Returns
+    ///
+    /// The ID, or a `DecodeError` if:
+    /// - `encoded` contains a character outside the alphabet, with
+    ///   `DecodeError::InvalidCharacter`.
+    /// - The value does not fit in 64 bits, with `DecodeError::Overflow`.
+    /// - `encoded` is not the string this codec produces for any ID, for example because it
+    ///   was made with another salt or has too much padding, with `DecodeError::InvalidInput`.
+    pub fn decode_id(&self, encoded: &str) -> Result<u64, DecodeError> {
+        let base = self.alphabet.base() as u64;
+        let mut digits = (0..encoded.len()).map(|position| {
+            self.alphabet
+                .digit_value(&encoded.as_bytes()[position..], position)
+        });
+        let lottery = match digits.next() {
+            Some(lottery) => lottery?,
+            None => return Err(DecodeError::InvalidInput),
+        };
+
+        let mut id = 0u64;
+        for (position, digit) in digits.enumerate() {
+            let offset = self.offset(lottery, position);
+            let digit = (digit? as u64 + base - offset % base) % base;
+            id = id
+                .checked_mul(base)
+                .and_then(|id| id.checked_add(digit))
+                .ok_or(DecodeError::Overflow { bits: 64 })?;
+        }
+
+        if self.encode_id(id) != encoded {
+            return Err(DecodeError::InvalidInput);
+        }
+        Ok(id)
+    }
+
+    /// Returns the character of the shuffled alphabet for `digit`.
+    fn char_at(&self, digit: u8) -> char {
+        self.alphabet.chars()[digit as usize] as char
+    }
+
+    /// Returns the amount that the digit at `position` after the lottery character is
+    /// shifted by.
+    fn offset(&self, lottery: u8, position: usize) -> u64 {
+        mix(self.seed ^ mix((lottery as u64) << 32 | position as u64))
+    }
+}
+
+/// The 64-bit FNV-1a hash, which turns the salt into a seed.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// The SplitMix64 output function, which scrambles every bit of `x` into every bit of the
+/// result.
+fn mix(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{presets, CHARACTERS};
+
+    const MAX_BASE: u8 = 94;
+
+    const BOUNDARIES: [u64; 7] = [0, 1, 2, 93, 94, u64::MAX - 1, u64::MAX];
+
+    #[test]
+    fn test_round_trip() {
+        for base in 2..=MAX_BASE {
+            let alphabet = Alphabet::new(&CHARACTERS[..base as usize]).unwrap();
+            for min_length in [0, 1, 2, 10, 70] {
+                let codec = IdCodec::new("salt", min_length, alphabet);
+                for id in BOUNDARIES
+                    .into_iter()
+                    .chain((0..20).map(|_| rand::random()))
+                {
+                    let encoded = codec.encode_id(id);
+                    assert!(encoded.len() >= min_length.max(2));
+                    assert!(encoded.bytes().all(|c| alphabet.digit_of(c).is_some()));
+                    assert_eq!(codec.decode_id(&encoded).unwrap(), id);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_collisions() {
+        let codec = IdCodec::new("salt", 4, presets::BASE62);
+        let mut seen = (0..10_000)
+            .map(|id| codec.encode_id(id))
+            .collect::<Vec<String>>();
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen.len(), 10_000);
+    }
+
+    #[test]
+    fn test_salts_differ() {
+        let first = IdCodec::new("first", 6, presets::BASE62);
+        let second = IdCodec::new("second", 6, presets::BASE62);
+        for id in BOUNDARIES {
+            let encoded = first.encode_id(id);
+            assert_ne!(encoded, second.encode_id(id));
+            assert!(!matches!(second.decode_id(&encoded), Ok(decoded) if decoded == id));
+        }
+    }
+
+    /// The output must never change between versions, since IDs end up in stored URLs.
+    #[test]
+    fn test_frozen_vectors() {
+        let codec = IdCodec::new("this is my salt", 8, presets::BASE62);
+        let vectors = [
+            (0, "XYsqsFKz"),
+            (1, "VsFwjbAw"),
+            (12345, "S5H7Drig"),
+            (u64::MAX, "yA5UqkylV7aA"),
+        ];
+        for (id, encoded) in vectors {
+            assert_eq!(codec.encode_id(id), encoded);
+            assert_eq!(codec.decode_id(encoded).unwrap(), id);
+        }
+        assert_eq!(IdCodec::new("", 0, crate::STANDARD).encode_id(42), ";u");
+    }
+
+    #[test]
+    fn test_errors() {
+        let codec = IdCodec::new("salt", 4, presets::BASE62);
+        let encoded = codec.encode_id(1000);
+        assert!(matches!(
+            codec.decode_id(""),
+            Err(DecodeError::InvalidInput)
+        ));
+        assert!(matches!(
+            codec.decode_id(&encoded[..1]),
+            Err(DecodeError::InvalidInput)
+        ));
+        assert!(matches!(
+            codec.decode_id(&format!("{}-", encoded)),
+            Err(DecodeError::InvalidCharacter {
+                c: '-',
+                position: 4
+            })
+        ));
+        assert!(matches!(
+            codec.decode_id(&format!("{}{}", encoded, "0".repeat(20))),
+            Err(DecodeError::Overflow { bits: 64 })
+        ));
+
+        let padded = IdCodec::new("salt", 10, presets::BASE62).encode_id(1000);
+        assert!(matches!(
+            codec.decode_id(&padded),
+            Err(DecodeError::InvalidInput)
+        ));
+        let mut tampered = encoded.into_bytes();
+        tampered[0] = if tampered[0] == b'a' { b'b' } else { b'a' };
+        assert!(!matches!(
+            codec.decode_id(core::str::from_utf8(&tampered).unwrap()),
+            Ok(1000)
+        ));
+    }
+}
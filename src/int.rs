@@ -0,0 +1,246 @@
+//! Fast encoding of primitive integers without big-number arithmetic.
+
+use alloc::string::String;
+
+use crate::{is_valid_base, parse_digits, DecodeError, EncodeError, CHARACTERS};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// An unsigned primitive integer that can be used with [`encode_int`] and [`decode_int`].
+///
+/// This trait is sealed and implemented for `u8`, `u16`, `u32`, `u64`, `u128`, and `usize`.
+/// All arithmetic uses the integer type itself, so no value ever goes through a big number.
+pub trait EncodeInteger: Copy + PartialEq + private::Sealed {
+    #[doc(hidden)]
+    const ZERO: Self;
+    #[doc(hidden)]
+    const BITS: u32;
+    #[doc(hidden)]
+    fn div_rem_base(self, base: u8) -> (Self, u8);
+    #[doc(hidden)]
+    fn checked_mul_add(self, base: u8, digit: u8) -> Option<Self>;
+}
+
+macro_rules! impl_encode_integer {
+    ($($t:ty),*) => {
+        $(
+            impl private::Sealed for $t {}
+
+            impl EncodeInteger for $t {
+                const ZERO: Self = 0;
+                const BITS: u32 = <$t>::BITS;
+
+                fn div_rem_base(self, base: u8) -> (Self, u8) {
+                    (self / base as $t, (self % base as $t) as u8)
+                }
+
+                fn checked_mul_add(self, base: u8, digit: u8) -> Option<Self> {
+                    self.checked_mul(base as $t)?.checked_add(digit as $t)
+                }
+            }
+        )*
+    };
+}
+
+impl_encode_integer!(u8, u16, u32, u64, u128, usize);
+
+/// Encodes an unsigned integer using the same digits and order as [`encode`](crate::encode).
+///
+/// The result equals `encode` of the minimal little-endian bytes of `n`, computed with plain
+/// integer arithmetic instead of a heap-allocated big number. Zero encodes to a single zero
+/// digit.
+///
+/// # Arguments
+///
+/// * `n` - The value to encode.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode, encode_int};
+///
+/// assert_eq!(encode_int(0x0201u16, 94), encode(&[0x01, 0x02], 94));
+/// assert_eq!(encode_int(u128::MAX, 94), encode(&[0xFF; 16], 94));
+/// ```
+pub fn encode_int<T: EncodeInteger>(n: T, base: u8) -> String {
+    if !is_valid_base(base) {
+        panic!("{}", EncodeError::InvalidBase { base });
+    }
+
+    let mut n = n;
+    let mut out = String::new();
+    loop {
+        let (div, rem) = n.div_rem_base(base);
+        out.push(CHARACTERS[rem as usize] as char);
+        n = div;
+        if n == T::ZERO {
+            break out;
+        }
+    }
+}
+
+/// Decodes a string into an unsigned integer, the inverse of [`encode_int`].
+///
+/// Trailing zero digits only add leading zeros to the number, so this also accepts the
+/// output of [`encode`](crate::encode) for any input that fits in `T`.
+///
+/// # Arguments
+///
+/// * `s` - The string to decode.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+///
+/// # Returns
+///
+/// The decoded value, `DecodeError::Overflow` if it exceeds `T::MAX`, or a `DecodeError`
+/// under the same conditions as [`decode`](crate::decode).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_int, encode_int, DecodeError};
+///
+/// assert_eq!(decode_int::<u32>(&encode_int(4_000_000_000u32, 94), 94).unwrap(), 4_000_000_000);
+/// assert!(matches!(decode_int::<u8>("~~", 94), Err(DecodeError::Overflow { bits: 8 })));
+/// ```
+pub fn decode_int<T: EncodeInteger>(s: &str, base: u8) -> Result<T, DecodeError> {
+    parse_digits(s.as_bytes(), base)?
+        .iter()
+        .rev()
+        .try_fold(T::ZERO, |n, &digit| n.checked_mul_add(base, digit))
+        .ok_or(DecodeError::Overflow { bits: T::BITS })
+}
+
+/// Encodes a `u64`; shorthand for [`encode_int`].
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode, encode_u64};
+///
+/// assert_eq!(encode_u64(0x0201, 94), encode(&[0x01, 0x02], 94));
+/// assert_eq!(encode_u64(0, 94), "!");
+/// ```
+pub fn encode_u64(n: u64, base: u8) -> String {
+    encode_int(n, base)
+}
+
+/// Decodes a `u64`; shorthand for [`decode_int`].
+///
+/// `decode_u64(&encode(&n.to_le_bytes(), base), base) == Ok(n)` holds for every `n`.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_u64, encode_u64, DecodeError};
+///
+/// assert_eq!(decode_u64(&encode_u64(1_690_000_000, 94), 94).unwrap(), 1_690_000_000);
+/// assert!(matches!(decode_u64("~~~~~~~~~~~", 94), Err(DecodeError::Overflow { bits: 64 })));
+/// ```
+pub fn decode_u64(s: &str, base: u8) -> Result<u64, DecodeError> {
+    decode_int(s, base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode;
+
+    const MAX_BASE: u8 = 94;
+
+    fn samples() -> Vec<u64> {
+        let mut samples = vec![0, 1, 255, 256, u32::MAX as u64, u64::MAX - 1, u64::MAX];
+        samples.extend((0..32).map(|_| rand::random::<u64>()));
+        samples
+    }
+
+    #[test]
+    fn test_encode_decode_u64() {
+        for base in 2..=MAX_BASE {
+            for n in samples() {
+                assert_eq!(decode_u64(&encode_u64(n, base), base).unwrap(), n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_u64_matches_encode() {
+        for base in 2..=MAX_BASE {
+            for n in samples().into_iter().filter(|&n| n != 0) {
+                let bytes = n.to_le_bytes();
+                let len = 8 - n.leading_zeros() as usize / 8;
+                assert_eq!(encode_u64(n, base), encode(&bytes[..len], base));
+                assert_eq!(decode_u64(&encode(&bytes, base), base).unwrap(), n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_u64_overflow() {
+        for base in 2..=MAX_BASE {
+            let mut encoded = encode(&u64::MAX.to_le_bytes(), base);
+            assert_eq!(decode_u64(&encoded, base).unwrap(), u64::MAX);
+            encoded.push(CHARACTERS[1] as char);
+            assert!(matches!(
+                decode_u64(&encoded, base),
+                Err(DecodeError::Overflow { bits: 64 })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_encode_decode_int_all_widths() {
+        for base in 2..=MAX_BASE {
+            for n in [0u128, 1, u8::MAX as u128, u64::MAX as u128 + 1, u128::MAX] {
+                let bytes = n.to_le_bytes();
+                assert_eq!(decode_int::<u128>(&encode_int(n, base), base).unwrap(), n);
+                assert_eq!(decode_int::<u128>(&encode(&bytes, base), base).unwrap(), n);
+            }
+            for n in [0u8, 1, u8::MAX] {
+                assert_eq!(decode_int::<u8>(&encode_int(n, base), base).unwrap(), n);
+            }
+            for n in [0u32, u32::MAX] {
+                assert_eq!(decode_int::<u32>(&encode_int(n, base), base).unwrap(), n);
+            }
+            for n in [0usize, usize::MAX] {
+                assert_eq!(decode_int::<usize>(&encode_int(n, base), base).unwrap(), n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_int_overflow() {
+        for base in 2..=MAX_BASE {
+            let encoded = encode_int(u8::MAX as u16 + 1, base);
+            assert!(matches!(
+                decode_int::<u8>(&encoded, base),
+                Err(DecodeError::Overflow { bits: 8 })
+            ));
+            assert_eq!(decode_int::<u16>(&encoded, base).unwrap(), 256);
+
+            let encoded = encode_int(u32::MAX, base);
+            assert!(matches!(
+                decode_int::<u16>(&encoded, base),
+                Err(DecodeError::Overflow { bits: 16 })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_decode_u64_invalid_character() {
+        assert!(matches!(
+            decode_u64("ab\u{7f}", 94),
+            Err(DecodeError::InvalidCharacter { position: 2, .. })
+        ));
+    }
+}
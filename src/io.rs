@@ -0,0 +1,480 @@
+//! `std::io` adapters built on the streaming [`Encoder`] and [`Decoder`].
+//!
+//! Like the streaming types, the adapters produce and consume the chunked block format with
+//! [`STREAM_CHUNK_SIZE`](crate::STREAM_CHUNK_SIZE) byte blocks, not the whole-number format of
+//! [`encode`](crate::encode).
+
+use std::io::{self, Read, Write};
+
+use crate::{DecodeError, Decoder, Encoder};
+
+/// The most input bytes a single call to [`EncodeWriter::write`] accepts, which bounds the
+/// encoded text buffered between calls.
+const MAX_WRITE: usize = 8192;
+
+/// The number of encoded bytes [`DecodeReader`] requests from its inner reader at a time.
+const READ_SIZE: usize = 8192;
+
+/// A writer that encodes everything written to it and writes the text to an inner writer.
+///
+/// Call [`finish`](Self::finish) after the last write to emit the final partial block.
+/// Dropping the writer without finishing loses that block.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use base94::{decode_chunked, EncodeWriter, STREAM_CHUNK_SIZE};
+///
+/// let mut writer = EncodeWriter::new(Vec::new(), 94);
+/// writer.write_all(b"Hello, World!").unwrap();
+/// let encoded = String::from_utf8(writer.finish().unwrap()).unwrap();
+///
+/// let decoded = decode_chunked(&encoded, 94, STREAM_CHUNK_SIZE).unwrap();
+/// assert_eq!(decoded, b"Hello, World!");
+/// ```
+#[derive(Debug)]
+pub struct EncodeWriter<W: Write> {
+    inner: W,
+    encoder: Encoder,
+    /// Encoded text that has not been written to `inner` yet.
+    buffer: String,
+    /// How much of `buffer` has already been written.
+    written: usize,
+}
+
+impl<W: Write> EncodeWriter<W> {
+    /// Creates a writer that encodes in `base` and writes the text to `inner`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the specified base is outside the valid range (2 to 94).
+    pub fn new(inner: W, base: u8) -> Self {
+        Self {
+            inner,
+            encoder: Encoder::new(base),
+            buffer: String::new(),
+            written: 0,
+        }
+    }
+
+    /// Returns a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer. Writing to it directly corrupts the
+    /// encoded output.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Encodes the final partial block, writes all remaining text, flushes the inner
+    /// writer, and returns it.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_buffer()?;
+        self.buffer = self.encoder.finalize_in_place();
+        self.written = 0;
+        self.write_buffer()?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    /// Writes the buffered text to the inner writer, keeping track of partial writes so
+    /// that a failed call can be retried.
+    fn write_buffer(&mut self) -> io::Result<()> {
+        while self.written < self.buffer.len() {
+            match self.inner.write(&self.buffer.as_bytes()[self.written..]) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write the encoded text",
+                    ))
+                }
+                Ok(n) => self.written += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        self.buffer.clear();
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for EncodeWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Errors from earlier text surface here, before any new input is accepted.
+        self.write_buffer()?;
+        let buf = &buf[..buf.len().min(MAX_WRITE)];
+        self.buffer.push_str(self.encoder.push(buf));
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_buffer()?;
+        self.inner.flush()
+    }
+}
+
+/// A reader that decodes the encoded text produced by an inner reader.
+///
+/// Decoding errors are returned as [`io::Error`]s of kind [`io::ErrorKind::InvalidData`]
+/// that wrap the [`DecodeError`](crate::DecodeError), whose position counts from the start of
+/// the stream. After a decoding error, every later read returns the same error again.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Read;
+/// use base94::{encode_chunked, DecodeError, DecodeReader, STREAM_CHUNK_SIZE};
+///
+/// let encoded = encode_chunked(b"Hello, World!", 94, STREAM_CHUNK_SIZE);
+/// let mut decoded = Vec::new();
+/// DecodeReader::new(encoded.as_bytes(), 94)
+///     .read_to_end(&mut decoded)
+///     .unwrap();
+/// assert_eq!(decoded, b"Hello, World!");
+///
+/// let error = DecodeReader::new(&b"!! !"[..], 94)
+///     .read_to_end(&mut Vec::new())
+///     .unwrap_err();
+/// let error = error.into_inner().unwrap().downcast::<DecodeError>().unwrap();
+/// assert!(matches!(*error, DecodeError::InvalidCharacter { position: 2, .. }));
+/// ```
+#[derive(Debug)]
+pub struct DecodeReader<R: Read> {
+    inner: R,
+    decoder: Decoder,
+    /// Encoded text read from `inner`.
+    input: Vec<u8>,
+    /// Decoded bytes that have not been returned yet, starting at `consumed`.
+    output: Vec<u8>,
+    consumed: usize,
+    /// Whether the inner reader has reached the end and the final block was decoded.
+    finished: bool,
+    /// The first decoding error, after which the decoder's state is not usable.
+    error: Option<DecodeError>,
+}
+
+impl<R: Read> DecodeReader<R> {
+    /// Creates a reader that decodes the text from `inner` in `base`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the specified base is outside the valid range (2 to 94).
+    pub fn new(inner: R, base: u8) -> Self {
+        Self {
+            inner,
+            decoder: Decoder::new(base),
+            input: vec![0; READ_SIZE],
+            output: Vec::new(),
+            consumed: 0,
+            finished: false,
+            error: None,
+        }
+    }
+
+    /// Returns a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader. Reading from it directly corrupts
+    /// the decoded output.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader, discarding any buffered data.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for DecodeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if let Some(err) = &self.error {
+            return Err(err.clone().into());
+        }
+        while self.consumed == self.output.len() && !self.finished && !buf.is_empty() {
+            let n = match self.inner.read(&mut self.input) {
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            let decoded = if n == 0 {
+                self.finished = true;
+                self.decoder.finalize_in_place()
+            } else {
+                self.decoder.push_bytes(&self.input[..n])
+            };
+            self.output = decoded.inspect_err(|err| self.error = Some(err.clone()))?;
+            self.consumed = 0;
+        }
+
+        let n = buf.len().min(self.output.len() - self.consumed);
+        buf[..n].copy_from_slice(&self.output[self.consumed..self.consumed + n]);
+        self.consumed += n;
+        Ok(n)
+    }
+}
+
+/// Converts a decode error into an `io::Error` of kind `InvalidData`, so decoding can use `?`
+/// in functions that return `io::Result`.
+///
+/// The `DecodeError` is kept as the inner error and can be recovered with
+/// [`io::Error::into_inner`] and `downcast`.
+///
+/// # Examples
+///
+/// ```
+/// use base94::DecodeError;
+/// use std::io;
+///
+/// fn parse(text: &str) -> io::Result<Vec<u8>> {
+///     Ok(base94::decode(text, 94)?)
+/// }
+///
+/// let error = parse("ab\u{7f}").unwrap_err();
+/// assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+/// let error = error.into_inner().unwrap().downcast::<DecodeError>().unwrap();
+/// assert_eq!(*error, DecodeError::InvalidCharacter { c: '\u{7f}', position: 2 });
+/// ```
+impl From<DecodeError> for io::Error {
+    fn from(err: DecodeError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, decode_chunked, encode_chunked, STREAM_CHUNK_SIZE};
+
+    const MAX_BASE: u8 = 94;
+
+    /// Accepts at most three bytes per call and, if `fail_every` is nonzero, fails every
+    /// `fail_every`-th call.
+    struct Trickle {
+        data: Vec<u8>,
+        calls: usize,
+        fail_every: usize,
+    }
+
+    impl Trickle {
+        fn new(fail_every: usize) -> Self {
+            Self {
+                data: Vec::new(),
+                calls: 0,
+                fail_every,
+            }
+        }
+    }
+
+    impl Write for Trickle {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.calls += 1;
+            if self.fail_every != 0 && self.calls.is_multiple_of(self.fail_every) {
+                return Err(io::Error::other("trickle failed"));
+            }
+            let n = buf.len().min(3);
+            self.data.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_encode_writer_round_trip() {
+        let data = (0..2_000_000)
+            .map(|_| rand::random::<u8>())
+            .collect::<Vec<u8>>();
+        for base in [10, MAX_BASE] {
+            let mut writer = EncodeWriter::new(Vec::new(), base);
+            io::copy(&mut &data[..], &mut writer).unwrap();
+            let encoded = String::from_utf8(writer.finish().unwrap()).unwrap();
+            assert_eq!(
+                decode_chunked(&encoded, base, STREAM_CHUNK_SIZE).unwrap(),
+                data
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_writer_partial_writes() {
+        for base in 2..=MAX_BASE {
+            let data = (0..1000).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let mut writer = EncodeWriter::new(Trickle::new(0), base);
+            writer.write_all(&data).unwrap();
+            let encoded = writer.finish().unwrap().data;
+            assert_eq!(
+                encoded,
+                encode_chunked(&data, base, STREAM_CHUNK_SIZE).into_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_writer_errors_propagate_and_can_be_retried() {
+        for base in 2..=MAX_BASE {
+            let data = (0..1000).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let mut writer = EncodeWriter::new(Trickle::new(5), base);
+
+            let mut rest = &data[..];
+            let mut errors = 0;
+            while !rest.is_empty() {
+                match writer.write(&rest[..rest.len().min(100)]) {
+                    Ok(n) => rest = &rest[n..],
+                    Err(e) => {
+                        assert_eq!(e.to_string(), "trickle failed");
+                        errors += 1;
+                    }
+                }
+            }
+            assert!(errors > 0);
+
+            writer.get_mut().fail_every = 0;
+            let encoded = writer.finish().unwrap().data;
+            assert_eq!(
+                encoded,
+                encode_chunked(&data, base, STREAM_CHUNK_SIZE).into_bytes()
+            );
+        }
+    }
+
+    /// Returns between one and seven bytes of `data` per call.
+    struct Jittery<'a> {
+        data: &'a [u8],
+    }
+
+    impl Read for Jittery<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf
+                .len()
+                .min(self.data.len())
+                .min(1 + rand::random::<usize>() % 7);
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_decode_reader_round_trip() {
+        let data = (0..2_000_000)
+            .map(|_| rand::random::<u8>())
+            .collect::<Vec<u8>>();
+        for base in [10, MAX_BASE] {
+            let mut writer = EncodeWriter::new(Vec::new(), base);
+            writer.write_all(&data).unwrap();
+            let encoded = writer.finish().unwrap();
+
+            let mut decoded = Vec::new();
+            io::copy(&mut DecodeReader::new(&encoded[..], base), &mut decoded).unwrap();
+            assert_eq!(decoded, data);
+        }
+    }
+
+    #[test]
+    fn test_decode_reader_split_input_and_small_reads() {
+        for base in 2..=MAX_BASE {
+            let data = (0..500).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let encoded = encode_chunked(&data, base, STREAM_CHUNK_SIZE);
+            let mut reader = DecodeReader::new(
+                Jittery {
+                    data: encoded.as_bytes(),
+                },
+                base,
+            );
+
+            let mut decoded = Vec::new();
+            let mut byte = [0];
+            while reader.read(&mut byte).unwrap() == 1 {
+                decoded.push(byte[0]);
+            }
+            assert_eq!(decoded, data);
+        }
+    }
+
+    #[test]
+    fn test_decode_reader_invalid_data() {
+        let mut encoded = encode_chunked(&[7; 1000], 94, STREAM_CHUNK_SIZE).into_bytes();
+        encoded[900] = b' ';
+        let error = DecodeReader::new(Jittery { data: &encoded }, 94)
+            .read_to_end(&mut Vec::new())
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let error = error
+            .into_inner()
+            .unwrap()
+            .downcast::<DecodeError>()
+            .unwrap();
+        assert!(matches!(
+            *error,
+            DecodeError::InvalidCharacter {
+                c: ' ',
+                position: 900
+            }
+        ));
+    }
+
+    #[test]
+    fn test_decode_reader_errors_are_sticky() {
+        let invalid = DecodeError::InvalidCharacter {
+            c: ' ',
+            position: 45,
+        };
+        let mut encoded = encode_chunked(&[7; 100], 94, STREAM_CHUNK_SIZE).into_bytes();
+        encoded[45] = b' ';
+        let truncated = encode_chunked(&[7; 100], 94, STREAM_CHUNK_SIZE);
+        let truncated = &truncated.as_bytes()[..50];
+        for (input, expected) in [(&encoded[..], Some(invalid)), (truncated, None)] {
+            let mut reader = DecodeReader::new(input, 94);
+            let first = reader.read_to_end(&mut Vec::new()).unwrap_err();
+            let first = first
+                .into_inner()
+                .unwrap()
+                .downcast::<DecodeError>()
+                .unwrap();
+            if let Some(expected) = &expected {
+                assert_eq!(*first, *expected);
+            }
+            // Neither a clean end nor decoding from a broken state.
+            for _ in 0..3 {
+                let again = reader.read(&mut [0; 64]).unwrap_err();
+                assert_eq!(again.kind(), io::ErrorKind::InvalidData);
+                let again = again
+                    .into_inner()
+                    .unwrap()
+                    .downcast::<DecodeError>()
+                    .unwrap();
+                assert_eq!(again, first);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_error_into_io_error() {
+        let text = "!!~\u{e9}!!";
+        let error = io::Error::from(decode(text, 94).unwrap_err());
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            error.to_string(),
+            "Invalid character '\u{e9}' at position 3"
+        );
+        let error = error
+            .get_ref()
+            .unwrap()
+            .downcast_ref::<DecodeError>()
+            .unwrap();
+        assert!(matches!(
+            error,
+            DecodeError::InvalidCharacter { position: 3, .. }
+        ));
+        assert_eq!(&text[3..], "\u{e9}!!");
+    }
+}
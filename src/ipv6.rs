@@ -0,0 +1,121 @@
+//! RFC 1924 encoding of IPv6 addresses.
+//!
+//! RFC 1924 writes the 128-bit address as one number in base 85, most significant digit
+//! first, always using 20 characters of the [`RFC1924`] alphabet.
+
+use alloc::string::String;
+use core::net::Ipv6Addr;
+
+use crate::presets::RFC1924;
+use crate::DecodeError;
+
+/// The number of characters in an encoded address.
+const ENCODED_LEN: usize = 20;
+
+/// Encodes `addr` as the 20 characters defined by RFC 1924.
+///
+/// # Examples
+///
+/// ```
+/// use base94::encode_ipv6;
+///
+/// let addr = "1080::8:800:200C:417A".parse().unwrap();
+/// assert_eq!(encode_ipv6(addr), "4)+k&C#VzJ4br>0wv%Yp");
+/// ```
+pub fn encode_ipv6(addr: Ipv6Addr) -> String {
+    let mut n = u128::from(addr);
+    let mut digits = [0; ENCODED_LEN];
+    for digit in digits.iter_mut().rev() {
+        *digit = RFC1924.chars()[(n % 85) as usize];
+        n /= 85;
+    }
+    digits.iter().map(|&c| c as char).collect()
+}
+
+/// Decodes an address produced by [`encode_ipv6`].
+///
+/// # Returns
+///
+/// The address, or a `DecodeError` if:
+/// - `encoded` is not exactly 20 characters long.
+/// - `encoded` contains a character outside [`RFC1924`].
+/// - The encoded value does not fit in 128 bits.
+///
+/// # Examples
+///
+/// ```
+/// use base94::decode_ipv6;
+///
+/// let addr = decode_ipv6("4)+k&C#VzJ4br>0wv%Yp").unwrap();
+/// assert_eq!(addr, "1080::8:800:200C:417A".parse::<std::net::Ipv6Addr>().unwrap());
+/// ```
+pub fn decode_ipv6(encoded: &str) -> Result<Ipv6Addr, DecodeError> {
+    if encoded.len() != ENCODED_LEN {
+        return Err(DecodeError::InvalidLength {
+            length: encoded.len(),
+        });
+    }
+
+    let mut n = 0u128;
+    for position in 0..encoded.len() {
+        let digit = RFC1924.digit_value(&encoded.as_bytes()[position..], position)?;
+        n = n
+            .checked_mul(85)
+            .and_then(|n| n.checked_add(digit as u128))
+            .ok_or(DecodeError::Overflow { bits: 128 })?;
+    }
+    Ok(Ipv6Addr::from(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc_example() {
+        let addr = "1080::8:800:200C:417A".parse::<Ipv6Addr>().unwrap();
+        assert_eq!(encode_ipv6(addr), "4)+k&C#VzJ4br>0wv%Yp");
+        assert_eq!(decode_ipv6("4)+k&C#VzJ4br>0wv%Yp").unwrap(), addr);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for addr in [Ipv6Addr::UNSPECIFIED, Ipv6Addr::LOCALHOST, u128::MAX.into()] {
+            let encoded = encode_ipv6(addr);
+            assert_eq!(encoded.len(), ENCODED_LEN);
+            assert_eq!(decode_ipv6(&encoded).unwrap(), addr);
+        }
+        assert_eq!(encode_ipv6(Ipv6Addr::UNSPECIFIED), "00000000000000000000");
+        assert_eq!(encode_ipv6(u128::MAX.into()), "=r54lj&NUUO~Hi%c2ym0");
+
+        for _ in 0..1000 {
+            let addr = Ipv6Addr::from(rand::random::<u128>());
+            assert_eq!(decode_ipv6(&encode_ipv6(addr)).unwrap(), addr);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        for encoded in ["", "4)+k&C#VzJ4br>0wv%Y", "4)+k&C#VzJ4br>0wv%Yp0"] {
+            assert!(matches!(
+                decode_ipv6(encoded),
+                Err(DecodeError::InvalidLength { length }) if length == encoded.len()
+            ));
+        }
+        assert!(matches!(
+            decode_ipv6("4)+k&C#VzJ4br>0wv\"Yp"),
+            Err(DecodeError::InvalidCharacter {
+                c: '"',
+                position: 17
+            })
+        ));
+        assert!(matches!(
+            decode_ipv6("=r54lj&NUUO~Hi%c2ym1"),
+            Err(DecodeError::Overflow { bits: 128 })
+        ));
+        assert!(matches!(
+            decode_ipv6("~~~~~~~~~~~~~~~~~~~~"),
+            Err(DecodeError::Overflow { bits: 128 })
+        ));
+    }
+}
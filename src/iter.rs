@@ -0,0 +1,300 @@
+//! Encoding to and decoding from character iterators.
+
+use alloc::vec::Vec;
+
+use core::iter::FusedIterator;
+
+use crate::len::{max_digits, min_digits};
+use crate::native::word_power;
+use crate::{
+    collect_digits, decode_digits, digit_value, is_valid_base, trailing_zeros, DecodeError,
+    EncodeError, CHARACTERS,
+};
+
+/// Returns an iterator over the characters of the encoding of `data`, without building the
+/// whole string.
+///
+/// The iterator keeps a copy of the number and divides it as characters are requested, so
+/// taking only a prefix, e.g. with [`take`](Iterator::take), only pays for the digits that
+/// are produced. Collecting all of it yields exactly [`encode`](crate::encode)'s output.
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode, encode_iter};
+///
+/// let data = b"Hello, World!";
+/// assert_eq!(encode_iter(data, 94).collect::<String>(), encode(data, 94));
+///
+/// let preview = encode_iter(&[0xAB; 1000], 94).take(8).collect::<String>();
+/// assert_eq!(preview.len(), 8);
+/// ```
+pub fn encode_iter(data: &[u8], base: u8) -> EncodeIter {
+    if !is_valid_base(base) {
+        panic!("{}", EncodeError::InvalidBase { base });
+    }
+
+    let zeros = trailing_zeros(data, 0);
+    let mut limbs = data[..data.len() - zeros]
+        .chunks(4)
+        .map(|chunk| {
+            let mut word = [0; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            u32::from_le_bytes(word)
+        })
+        .collect::<Vec<u32>>();
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+
+    let (power, exponent) = word_power(base as u32);
+    EncodeIter {
+        limbs,
+        base,
+        power,
+        exponent,
+        batch: [0; 32],
+        next: 0,
+        end: 0,
+        zeros,
+    }
+}
+
+/// The iterator returned by [`encode_iter`].
+#[derive(Clone, Debug)]
+pub struct EncodeIter {
+    /// The part of the number that has not been converted yet, little-endian and trimmed.
+    limbs: Vec<u32>,
+    base: u8,
+    power: u32,
+    exponent: usize,
+    /// Digits split off the last remainder; `batch[next..end]` are still to be yielded.
+    batch: [u8; 32],
+    next: usize,
+    end: usize,
+    /// Zero digits still to be yielded for the trailing zero bytes.
+    zeros: usize,
+}
+
+impl EncodeIter {
+    /// Divides the remaining number by `power` and splits the remainder into digits.
+    fn refill(&mut self) {
+        let mut rem = 0u64;
+        for limb in self.limbs.iter_mut().rev() {
+            let cur = (rem << 32) | *limb as u64;
+            *limb = (cur / self.power as u64) as u32;
+            rem = cur % self.power as u64;
+        }
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+
+        let base = self.base as u64;
+        self.next = 0;
+        self.end = 0;
+        // Once the number is used up, the top digits of the last remainder are leading zeros.
+        while self.end < self.exponent && (rem > 0 || !self.limbs.is_empty()) {
+            self.batch[self.end] = (rem % base) as u8;
+            rem /= base;
+            self.end += 1;
+        }
+    }
+
+    fn remaining_bits(&self) -> u128 {
+        match self.limbs.last() {
+            Some(&top) => self.limbs.len() as u128 * 32 - top.leading_zeros() as u128,
+            None => 0,
+        }
+    }
+}
+
+impl Iterator for EncodeIter {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.next == self.end && !self.limbs.is_empty() {
+            self.refill();
+        }
+        if self.next < self.end {
+            self.next += 1;
+            return Some(CHARACTERS[self.batch[self.next - 1] as usize] as char);
+        }
+        if self.zeros > 0 {
+            self.zeros -= 1;
+            return Some(CHARACTERS[0] as char);
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let known = self.end - self.next + self.zeros;
+        let bits = self.remaining_bits();
+        (
+            known + min_digits(bits, self.base),
+            Some(known + max_digits(bits, self.base)),
+        )
+    }
+}
+
+impl FusedIterator for EncodeIter {}
+
+/// Decodes the characters yielded by `chars` like [`decode`](crate::decode), without
+/// collecting them into a string first.
+///
+/// Error positions are byte offsets, counted as if the characters were in a `str`, so they
+/// match what [`decode`](crate::decode) reports for the collected string.
+///
+/// # Arguments
+///
+/// * `chars` - The characters of a Base94-encoded string.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `DecodeError` under the same conditions as `decode`. The
+/// iterator is not consumed past the first invalid character.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_iter, encode};
+///
+/// let encoded = encode(b"Hello, World!", 94);
+/// assert_eq!(decode_iter(encoded.chars(), 94).unwrap(), b"Hello, World!");
+/// ```
+pub fn decode_iter(
+    chars: impl IntoIterator<Item = char>,
+    base: u8,
+) -> Result<Vec<u8>, DecodeError> {
+    if !is_valid_base(base) {
+        return Err(DecodeError::InvalidBase { base });
+    }
+
+    // Positions count UTF-8 bytes, so errors point at the same offsets as `decode`.
+    let mut position = 0;
+    let digits = collect_digits(chars.into_iter().map(|c| {
+        let mut buf = [0; 4];
+        let digit = digit_value(c.encode_utf8(&mut buf).as_bytes(), position, base);
+        position += c.len_utf8();
+        digit
+    }))?;
+    Ok(decode_digits(&digits, base))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_encode_iter_matches_encode() {
+        for base in 2..=MAX_BASE {
+            for len in [0, 1, 3, 4, 5, 16, 17, 100] {
+                let mut data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+                data.resize(len + rand::random::<usize>() % 10, 0);
+                assert_eq!(
+                    encode_iter(&data, base).collect::<String>(),
+                    encode(&data, base)
+                );
+            }
+            for data in [vec![255; 40], vec![0; 40], vec![1, 0, 0, 0, 0, 1]] {
+                assert_eq!(
+                    encode_iter(&data, base).collect::<String>(),
+                    encode(&data, base)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_iter_size_hint() {
+        for base in 2..=MAX_BASE {
+            let mut data = (0..50).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            data.resize(55, 0);
+            let mut iter = encode_iter(&data, base);
+            let mut remaining = encode(&data, base).len();
+            loop {
+                let (lower, upper) = iter.size_hint();
+                assert!(lower <= remaining, "{lower} > {remaining}");
+                assert!(upper.unwrap() >= remaining);
+                if iter.next().is_none() {
+                    break;
+                }
+                remaining -= 1;
+            }
+            assert_eq!(iter.size_hint(), (0, Some(0)));
+        }
+    }
+
+    #[test]
+    fn test_encode_iter_take() {
+        let data = (0..1000).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+        let preview = encode_iter(&data, 94).take(10).collect::<String>();
+        assert_eq!(preview, encode(&data, 94)[..10]);
+    }
+
+    #[test]
+    fn test_decode_iter_from_chars() {
+        for base in 2..=MAX_BASE {
+            let mut data = (0..100).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            data.resize(105, 0);
+            let encoded = encode(&data, base);
+            assert_eq!(decode_iter(encoded.chars(), base).unwrap(), data);
+        }
+    }
+
+    #[test]
+    // `Repeat` wrapped in `Take` is the adapter chain being tested, not an oversight.
+    #[allow(clippy::manual_repeat_n)]
+    fn test_decode_iter_from_repeat() {
+        for base in 2..=MAX_BASE {
+            let c = CHARACTERS[base as usize - 1] as char;
+            let expected = decode(&c.to_string().repeat(50), base).unwrap();
+            assert_eq!(
+                decode_iter(std::iter::repeat(c).take(50), base).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_iter_invalid_character() {
+        let text = "!#~é !#";
+        // The error matches `decode`: the whole of 'é' at its byte offset.
+        assert!(matches!(
+            decode(text, 94),
+            Err(DecodeError::InvalidCharacter {
+                c: 'é',
+                position: 3
+            })
+        ));
+        assert!(matches!(
+            decode_iter(text.chars(), 94),
+            Err(DecodeError::InvalidCharacter {
+                c: 'é',
+                position: 3
+            })
+        ));
+
+        let mut chars = "!!~ !!".chars();
+        assert!(matches!(
+            decode_iter(&mut chars, 94),
+            Err(DecodeError::InvalidCharacter {
+                c: ' ',
+                position: 3
+            })
+        ));
+        assert_eq!(chars.as_str(), "!!");
+
+        assert!(matches!(
+            decode_iter("!!".chars(), 95),
+            Err(DecodeError::InvalidBase { base: 95 })
+        ));
+    }
+}
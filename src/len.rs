@@ -0,0 +1,173 @@
+//! Output size estimation.
+//!
+//! All functions here are `const fn` and use integer arithmetic only, so they can size
+//! arrays and protocol fields at compile time.
+
+/// Number of fractional bits in the fixed-point logarithms below.
+const FRACTION_BITS: u32 = 32;
+
+/// Returns `log2(base)` in fixed point with [`FRACTION_BITS`] fractional bits, rounded down.
+///
+/// Each fractional bit is found by squaring the mantissa; truncation only ever makes the
+/// mantissa smaller, so the result never exceeds the true logarithm. For powers of two the
+/// result is exact.
+const fn log2_fixed(base: u8) -> u64 {
+    let int_part = 31 - (base as u32).leading_zeros();
+    let one = 1u128 << FRACTION_BITS;
+    let mut mantissa = ((base as u128) << FRACTION_BITS) >> int_part;
+    let mut result = (int_part as u64) << FRACTION_BITS;
+
+    let mut bit = FRACTION_BITS;
+    while bit > 0 {
+        bit -= 1;
+        mantissa = (mantissa * mantissa) >> FRACTION_BITS;
+        if mantissa >= 2 * one {
+            mantissa >>= 1;
+            result |= 1 << bit;
+        }
+    }
+    result
+}
+
+/// Returns the maximum length of the encoding of `input_len` bytes in `base`.
+///
+/// The result is the number of digits needed for the largest `input_len`-byte value, so
+/// [`encode`](crate::encode) output never exceeds it. It is exact for power-of-two bases and
+/// may exceed the true maximum by one character for other bases and very large inputs.
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode, encoded_len};
+///
+/// const KEY_FIELD_LEN: usize = encoded_len(16, 94);
+///
+/// assert_eq!(KEY_FIELD_LEN, 20);
+/// assert!(encode(&[0xAB; 16], 94).len() <= KEY_FIELD_LEN);
+/// ```
+pub const fn encoded_len(input_len: usize, base: u8) -> usize {
+    assert!(base >= 2 && base <= 94, "Base must be in the range 2..=94");
+
+    max_digits(input_len as u128 * 8, base)
+}
+
+/// Returns the most digits in `base` that a value of `bits` bits can need.
+pub(crate) const fn max_digits(bits: u128, base: u8) -> usize {
+    let log2 = log2_fixed(base) as u128;
+    (bits << FRACTION_BITS).div_ceil(log2) as usize
+}
+
+/// Returns `digits * log2(base)` rounded down, roughly the number of bits that `digits`
+/// digits in `base` can hold.
+#[cfg(feature = "alloc")]
+pub(crate) const fn digit_bits(digits: usize, base: u8) -> u128 {
+    (digits as u128 * log2_fixed(base) as u128) >> FRACTION_BITS
+}
+
+/// Returns the fewest digits in `base` that a value of exactly `bits` bits can need, i.e. a
+/// lower bound on the digit count of any value in `2^(bits - 1)..2^bits`.
+#[cfg(feature = "alloc")]
+pub(crate) const fn min_digits(bits: u128, base: u8) -> usize {
+    if bits == 0 {
+        return 0;
+    }
+    // One more than the rounded-down logarithm is never below the true one.
+    let log2 = log2_fixed(base) as u128 + 1;
+    (((bits - 1) << FRACTION_BITS) / log2) as usize + 1
+}
+
+/// Returns an upper bound on the length of the encoding of `input_len` bytes in `base`.
+///
+/// This is meant for sizing buffers, e.g. for [`encode_to_slice`](crate::encode_to_slice),
+/// and returns the same bound as [`encoded_len`].
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode, encoded_len_upper_bound};
+///
+/// let data = [255; 16];
+/// assert!(encode(&data, 94).len() <= encoded_len_upper_bound(data.len(), 94));
+/// ```
+pub const fn encoded_len_upper_bound(input_len: usize, base: u8) -> usize {
+    encoded_len(input_len, base)
+}
+
+/// Returns the maximum number of bytes that an encoded string of `encoded_len` characters in
+/// `base` can decode to.
+///
+/// Every trailing zero digit stands for a whole zero byte, so a string made only of zero
+/// digits decodes to one byte per character. Any other string decodes to fewer bytes than it
+/// has characters, which makes `encoded_len` itself the tight bound for every valid base.
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode, decoded_len};
+///
+/// assert_eq!(decode("!!!!", 94).unwrap().len(), decoded_len(4, 94));
+/// ```
+pub const fn decoded_len(encoded_len: usize, base: u8) -> usize {
+    assert!(base >= 2 && base <= 94, "Base must be in the range 2..=94");
+
+    encoded_len
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_encoded_len_bounds_random_inputs() {
+        for base in 2..=MAX_BASE {
+            for len in [0, 1, 2, 15, 16, 100] {
+                let data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+                assert!(encode(&data, base).len() <= encoded_len(len, base));
+            }
+        }
+    }
+
+    #[test]
+    fn test_encoded_len_is_tight() {
+        for base in 2..=MAX_BASE {
+            for len in [1, 16, 100] {
+                let max = encode(&vec![255; len], base).len();
+                assert!(max <= encoded_len(len, base));
+                assert!(encoded_len(len, base) <= max + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_encoded_len_power_of_two_bases() {
+        assert_eq!(encoded_len(3, 2), 24);
+        assert_eq!(encoded_len(3, 16), 6);
+        assert_eq!(encoded_len(3, 64), 4);
+    }
+
+    #[test]
+    fn test_decoded_len_bounds_random_inputs() {
+        for base in 2..=MAX_BASE {
+            for len in [0, 1, 2, 15, 16, 100] {
+                let data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+                let encoded = encode(&data, base);
+                assert!(decode(&encoded, base).unwrap().len() <= decoded_len(encoded.len(), base));
+            }
+        }
+    }
+}
@@ -27,11 +27,57 @@
 //! assert_eq!(decoded, data);
 //! ```
 //!
+//! `encode` panics on an out-of-range base; use `try_encode` to get an `EncodeError`
+//! instead when the base comes from user input or configuration.
+//!
 //! ## Supported Bases
 //!
 //! The encoding and decoding functions support various bases within the range of 2 to 94.
 //! The specified base must be consistent between encoding and decoding operations.
 //!
+//! ## Length Preservation
+//!
+//! The input is treated as a little-endian number, so trailing zero bytes carry no numeric
+//! value. To keep the round trip length-preserving, every trailing zero byte is emitted as
+//! one extra zero digit (`CHARACTERS[0]`) at the end of the encoded string, in the same way
+//! Base58 maps leading zero bytes to leading `1`s. Data without trailing zero bytes encodes
+//! exactly as it did before this rule existed.
+//!
+//! In particular, the empty slice encodes to the empty string and the empty string decodes
+//! to an empty vector, while `[0]` encodes to a single zero digit and decodes back to `[0]`.
+//!
+//! ## Canonical Form
+//!
+//! Because trailing zero digits stand for trailing zero bytes rather than being ignored,
+//! every string that `decode` accepts is the one and only encoding of the bytes it decodes
+//! to: `encode(&decode(s, base)?, base) == s` always holds. Appending a zero digit to a
+//! valid string yields the encoding of a different value (one more zero byte), so no
+//! separate strict decoding mode is needed for content-addressed uses.
+//!
+//! ## Stability
+//!
+//! The output of `encode` is covered by semver: an input and base encode to the same string
+//! in every release until the next major version. The known-answer vectors in
+//! [`vectors`] pin this down and are exported for checking other implementations.
+//!
+//! ## `no_std`
+//!
+//! The crate is `#![no_std]` when the default `std` feature is disabled. Building with
+//! `default-features = false, features = ["alloc"]` keeps every function except the
+//! `std::io` adapters, and the error types implement `core::error::Error`. Without `alloc`,
+//! the `heapless` feature still provides fixed-capacity encoding and decoding.
+//!
+//! ## Zeroize
+//!
+//! With the `zeroize` feature, `encode` and `decode` overwrite their intermediate digit,
+//! limb, and scratch byte buffers before freeing them, and `Base94String` and the streaming
+//! `Encoder` and `Decoder` implement `Zeroize` and `ZeroizeOnDrop`. The feature selects the
+//! native backend even when `backend-num` is enabled: `BigUint` reallocates its limbs during
+//! arithmetic and gives no access to the freed copies, so nothing the `num` backend touches
+//! could be wiped. Copies the compiler keeps in registers or on the stack, the
+//! returned output, and the buffers of the chunked, streaming I/O, and other helper
+//! functions are not wiped.
+//!
 //! ## Examples
 //!
 //! ```
@@ -47,12 +93,246 @@
 //! ```
 //!
 
-use num::BigUint;
-use num::Integer;
-use num::ToPrimitive;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+// The `cdylib` output needs a panic handler and a global allocator. Hosted targets take them
+// from `std` even when the `std` API surface is disabled.
+#[cfg(all(not(feature = "std"), not(target_os = "none")))]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 use thiserror::Error;
 
-pub static CHARACTERS: &[u8; 94] = include_bytes!("characters.txt");
+// The `uniffi` module's interface is registered against the tag this defines.
+#[cfg(feature = "uniffi")]
+::uniffi::setup_scaffolding!();
+
+mod alphabet;
+#[cfg(feature = "alloc")]
+mod any;
+#[cfg(feature = "alloc")]
+mod armor;
+#[cfg(feature = "heapless")]
+mod array;
+#[cfg(feature = "async-tokio")]
+mod async_io;
+#[cfg(feature = "futures")]
+mod async_stream;
+#[cfg(feature = "alloc")]
+mod base58;
+#[cfg(feature = "bytes")]
+mod buf;
+#[cfg(feature = "alloc")]
+mod checked;
+#[cfg(feature = "alloc")]
+mod chunked;
+#[cfg(feature = "alloc")]
+mod codec;
+mod compare;
+#[cfg(feature = "alloc")]
+pub mod compat;
+#[cfg(feature = "compression")]
+mod compress;
+#[cfg(feature = "alloc")]
+mod config;
+#[cfg(feature = "alloc")]
+mod ct;
+mod detect;
+#[cfg(feature = "alloc")]
+mod display;
+#[cfg(feature = "alloc")]
+mod encoded;
+#[cfg(feature = "alloc")]
+mod ext;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "alloc")]
+mod file_armor;
+#[cfg(feature = "alloc")]
+mod group;
+#[cfg(feature = "alloc")]
+mod ids;
+#[cfg(feature = "alloc")]
+mod int;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "alloc")]
+mod ipv6;
+#[cfg(feature = "alloc")]
+mod iter;
+mod len;
+#[cfg(feature = "alloc")]
+mod lossy;
+#[cfg(feature = "alloc")]
+mod native;
+#[cfg(feature = "backend-num")]
+#[cfg_attr(any(feature = "backend-native", feature = "zeroize"), allow(dead_code))]
+mod num_backend;
+#[cfg(feature = "alloc")]
+mod order;
+#[cfg(feature = "alloc")]
+mod padded;
+#[cfg(feature = "alloc")]
+pub mod prelude;
+pub mod presets;
+#[cfg(all(kani, feature = "alloc"))]
+mod proofs;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "alloc")]
+mod slice;
+mod snippet;
+#[cfg(feature = "alloc")]
+mod sortable;
+#[cfg(feature = "alloc")]
+mod stream;
+#[cfg(feature = "alloc")]
+mod tagged;
+#[cfg(feature = "rand")]
+mod token;
+#[cfg(feature = "async-tokio")]
+mod tokio_codec;
+#[cfg(feature = "alloc")]
+mod transcode;
+#[cfg(feature = "uniffi")]
+pub mod uniffi;
+mod validate;
+pub mod vectors;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "alloc")]
+mod wrap;
+
+#[cfg(feature = "alloc")]
+pub use alphabet::{
+    decode_big_endian_with_alphabet, decode_with_alphabet, decode_with_unicode_alphabet,
+    encode_big_endian_with_alphabet, encode_with_alphabet, encode_with_unicode_alphabet,
+    UnicodeAlphabet,
+};
+pub use alphabet::{Alphabet, AlphabetError, STANDARD};
+#[cfg(feature = "alloc")]
+pub use any::{decode_any, Container, Decoded};
+#[cfg(feature = "alloc")]
+pub use armor::{decode_armored, encode_armored, ArmorError};
+#[cfg(feature = "heapless")]
+pub use array::{decode_to_arrayvec, encode_to_arraystring};
+#[cfg(feature = "async-tokio")]
+pub use async_io::{AsyncDecodeReader, AsyncEncodeWriter};
+#[cfg(feature = "futures")]
+pub use async_stream::{decode_stream, encode_stream};
+#[cfg(feature = "alloc")]
+pub use base58::{decode_base58, encode_base58};
+#[cfg(feature = "base58check")]
+pub use base58::{decode_base58check, encode_base58check};
+#[cfg(feature = "bytes")]
+pub use buf::{decode_buf, encode_buf};
+#[cfg(feature = "alloc")]
+pub use checked::{decode_checked, encode_checked};
+#[cfg(feature = "alloc")]
+pub use chunked::{decode_chunked, encode_chunked};
+#[cfg(feature = "parallel")]
+pub use chunked::{decode_chunked_parallel, encode_chunked_parallel};
+#[cfg(feature = "alloc")]
+pub use codec::Codec;
+pub use compare::cmp_encoded;
+#[cfg(feature = "compression")]
+pub use compress::{decode_compressed, encode_compressed};
+#[cfg(feature = "alloc")]
+pub use config::{decode_ignoring_whitespace, decode_with_config, DecodeConfig};
+#[cfg(feature = "alloc")]
+pub use ct::decode_ct;
+pub use detect::{analyze, detect_base, Analysis};
+#[cfg(feature = "alloc")]
+pub use display::{display, encode_to_fmt, Display};
+#[cfg(feature = "alloc")]
+pub use encoded::{Base94String, EncodedStr};
+#[cfg(feature = "alloc")]
+pub use ext::{FromBaseEncoded, ToBaseEncoded};
+#[cfg(feature = "std")]
+pub use file_armor::encode_file_armored;
+#[cfg(feature = "alloc")]
+pub use file_armor::{decode_file_armored, ArmoredFile};
+#[cfg(feature = "alloc")]
+pub use group::{decode_grouped, encode_grouped};
+#[cfg(feature = "alloc")]
+pub use ids::IdCodec;
+#[cfg(feature = "alloc")]
+pub use int::{decode_int, decode_u64, encode_int, encode_u64, EncodeInteger};
+#[cfg(feature = "std")]
+pub use io::{DecodeReader, EncodeWriter};
+#[cfg(feature = "alloc")]
+pub use ipv6::{decode_ipv6, encode_ipv6};
+#[cfg(feature = "alloc")]
+pub use iter::{decode_iter, encode_iter, EncodeIter};
+pub use len::{decoded_len, encoded_len, encoded_len_upper_bound};
+#[cfg(feature = "alloc")]
+pub use lossy::{decode_lossy, decode_partial};
+#[cfg(feature = "alloc")]
+pub use order::{decode_with_order, encode_with_order, DigitOrder};
+#[cfg(feature = "alloc")]
+pub use padded::{decode_padded, encode_padded};
+#[cfg(feature = "serde_with")]
+pub use serde::Base94;
+#[cfg(feature = "alloc")]
+pub use slice::{decode_exact, decode_to_slice, encode_to_slice, EncodeSliceError};
+pub use snippet::WithSource;
+#[cfg(feature = "alloc")]
+pub use sortable::{decode_sortable, encode_sortable};
+#[cfg(feature = "alloc")]
+pub use stream::{Decoder, Encoder, STREAM_CHUNK_SIZE};
+#[cfg(feature = "alloc")]
+pub use tagged::{decode_tagged, encode_tagged};
+#[cfg(feature = "rand")]
+pub use token::{
+    generate_token, generate_token_chars, generate_token_chars_with_rng, generate_token_with_rng,
+};
+#[cfg(feature = "async-tokio")]
+pub use tokio_codec::{Base94Codec, Base94CodecError, DEFAULT_MAX_FRAME_LENGTH};
+#[cfg(feature = "alloc")]
+pub use transcode::{transcode, Encoding, TranscodeError};
+#[cfg(feature = "alloc")]
+pub use validate::{decode_collect_errors, MAX_COLLECTED_ERRORS};
+pub use validate::{is_valid, validate};
+#[cfg(feature = "alloc")]
+pub use wrap::{encode_wrapped, LineEnding};
+
+pub static CHARACTERS: &[u8; 94] = ALPHABET;
+
+const ALPHABET: &[u8; 94] = include_bytes!("characters.txt");
+
+/// Returns the index of the character `c` in `CHARACTERS`, or `None` if it is not part of the
+/// alphabet.
+///
+/// This is a single table lookup, so it is cheap enough to call for every byte of the input.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{digit_index, CHARACTERS};
+///
+/// assert_eq!(digit_index(CHARACTERS[42]), Some(42));
+/// assert_eq!(digit_index(b' '), None);
+/// ```
+pub fn digit_index(c: u8) -> Option<u8> {
+    STANDARD.digit_of(c)
+}
+
+/// Errors that can occur while encoding data.
+#[derive(Error, Debug)]
+pub enum EncodeError {
+    #[error("Invalid base {base}, must be in the range 2..=94")]
+    InvalidBase { base: u8 },
+    #[error("Encoding needs {needed} characters but the width is {width}")]
+    WidthTooSmall { width: usize, needed: usize },
+}
 
 /// Encodes a slice of bytes into a Base94-encoded string using the specified base.
 ///
@@ -69,10 +349,13 @@ pub static CHARACTERS: &[u8; 94] = include_bytes!("characters.txt");
 /// # Panics
 ///
 /// This function panics if the specified base is outside the valid range (2 to 94).
+/// Use [`try_encode`] when the base comes from untrusted input.
 ///
 /// # Returns
 ///
-/// A Base94-encoded string representation of the input data.
+/// A Base94-encoded string representation of the input data. Each trailing zero byte of
+/// the input is represented by one trailing zero digit, so the output decodes back to
+/// exactly `data.len()` bytes. An empty slice encodes to an empty string.
 ///
 /// # Examples
 ///
@@ -84,26 +367,480 @@ pub static CHARACTERS: &[u8; 94] = include_bytes!("characters.txt");
 /// let encoded = encode(data, base);
 /// println!("Encoded: {}", encoded);
 /// ```
+#[cfg(feature = "alloc")]
 pub fn encode(data: &[u8], base: u8) -> String {
-    assert!(base <= 94, "Base must be less than or equal to 94");
-    assert!(base >= 2, "Base must be greater than or equal to 2");
+    try_encode(data, base).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Encodes a slice of bytes like [`encode`], but returns the ASCII characters as bytes.
+///
+/// This is convenient when the output goes straight to a socket or file, as it skips the
+/// conversion to `String`.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode, encode_to_bytes};
+///
+/// let data = b"Hello, World!";
+/// assert_eq!(encode_to_bytes(data, 94), encode(data, 94).into_bytes());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode_to_bytes(data: &[u8], base: u8) -> Vec<u8> {
+    if !is_valid_base(base) {
+        panic!("{}", EncodeError::InvalidBase { base });
+    }
 
-    let mut num = BigUint::from_bytes_le(data);
-    let mut out = String::new();
+    encode_symbols(data, base, STANDARD.chars())
+}
 
-    while num > BigUint::from(0u8) {
-        let (div, rem) = num.div_rem(&BigUint::from(base));
-        num = div;
-        out.push(CHARACTERS[rem.to_usize().unwrap()] as char);
+/// Encodes `data` in `base`, writing digit `d` as `symbols[d]`.
+#[cfg(feature = "alloc")]
+pub(crate) fn encode_symbols(data: &[u8], base: u8, symbols: &[u8]) -> Vec<u8> {
+    let mut digits = encode_digits(data, base);
+    let encoded = digits
+        .iter()
+        .map(|&digit| symbols[digit as usize])
+        .collect();
+    wipe(&mut digits);
+    encoded
+}
+
+/// Encodes a slice of bytes like [`encode`], but returns an error instead of panicking
+/// when the base is invalid.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+///
+/// # Returns
+///
+/// The encoded string, or `EncodeError::InvalidBase` if the base is outside the valid
+/// range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{try_encode, EncodeError};
+///
+/// assert!(try_encode(b"Hello, World!", 94).is_ok());
+/// assert!(matches!(try_encode(b"Hello, World!", 95), Err(EncodeError::InvalidBase { base: 95 })));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn try_encode(data: &[u8], base: u8) -> Result<String, EncodeError> {
+    if !is_valid_base(base) {
+        return Err(EncodeError::InvalidBase { base });
     }
 
-    out
+    let encoded = encode_symbols(data, base, STANDARD.chars());
+    Ok(String::from_utf8(encoded).expect("CHARACTERS is ASCII"))
 }
 
-#[derive(Error, Debug)]
+/// Encodes a slice of bytes like [`encode`], but writes the result into a caller-provided
+/// buffer so it can be reused across calls.
+///
+/// The buffer is cleared first and then reserved up front for the expected output length,
+/// so calling this in a loop with the same `String` avoids a fresh allocation per call.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+/// * `out` - The buffer that receives the encoded string.
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode, encode_into};
+///
+/// let mut buffer = String::new();
+/// for record in [&b"first"[..], b"second"] {
+///     encode_into(record, 94, &mut buffer);
+///     assert_eq!(buffer, encode(record, 94));
+/// }
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode_into(data: &[u8], base: u8, out: &mut String) {
+    if !is_valid_base(base) {
+        panic!("{}", EncodeError::InvalidBase { base });
+    }
+
+    out.clear();
+    out.reserve(encoded_len(data.len(), base));
+    let mut digits = encode_digits(data, base);
+    out.extend(
+        digits
+            .iter()
+            .map(|&digit| CHARACTERS[digit as usize] as char),
+    );
+    wipe(&mut digits);
+}
+
+/// Converts `data` into the digits of its encoding, least significant first, including one
+/// zero digit per trailing zero byte.
+#[cfg(feature = "alloc")]
+pub(crate) fn encode_digits(data: &[u8], base: u8) -> Vec<u8> {
+    encode_digits_with(data, |data| to_digits(data, base))
+}
+
+/// Like [`encode_digits`], with `to_digits` converting the bytes before the trailing zeros.
+#[cfg(feature = "alloc")]
+#[inline(always)]
+pub(crate) fn encode_digits_with(data: &[u8], to_digits: impl FnOnce(&[u8]) -> Vec<u8>) -> Vec<u8> {
+    let zeros = trailing_zeros(data, 0);
+    append_zeros(to_digits(&data[..data.len() - zeros]), zeros)
+}
+
+/// Converts the digits of an encoding, least significant first, back into the original
+/// bytes. This is the inverse of [`encode_digits`].
+#[cfg(feature = "alloc")]
+pub(crate) fn decode_digits(digits: &[u8], base: u8) -> Vec<u8> {
+    decode_digits_with(digits, |digits| from_digits(digits, base))
+}
+
+/// Like [`decode_digits`], with `from_digits` converting the digits before the trailing
+/// zeros.
+#[cfg(feature = "alloc")]
+#[inline(always)]
+pub(crate) fn decode_digits_with(
+    digits: &[u8],
+    from_digits: impl FnOnce(&[u8]) -> Vec<u8>,
+) -> Vec<u8> {
+    let zeros = trailing_zeros(digits, 0);
+    append_zeros(from_digits(&digits[..digits.len() - zeros]), zeros)
+}
+
+/// Appends `zeros` zero elements to `items`. If the buffer has to grow, the old allocation
+/// is wiped rather than left behind by the reallocation.
+#[cfg(feature = "alloc")]
+fn append_zeros(mut items: Vec<u8>, zeros: usize) -> Vec<u8> {
+    if items.capacity() - items.len() < zeros {
+        let mut grown = Vec::with_capacity(items.len() + zeros);
+        grown.extend_from_slice(&items);
+        wipe(&mut items);
+        items = grown;
+    }
+    items.resize(items.len() + zeros, 0);
+    items
+}
+
+/// Overwrites an intermediate value derived from the caller's data with zeros when the
+/// `zeroize` feature is enabled, and does nothing otherwise.
+#[cfg(feature = "zeroize")]
+#[inline]
+pub(crate) fn wipe<T: zeroize::Zeroize + ?Sized>(value: &mut T) {
+    value.zeroize();
+}
+
+#[cfg(all(feature = "alloc", not(feature = "zeroize")))]
+#[inline(always)]
+pub(crate) fn wipe<T: ?Sized>(_value: &mut T) {}
+
+/// Converts the little-endian number in `data` into its digits in `base`, least significant
+/// first. Zero produces no digits.
+///
+/// Inputs of up to 16 bytes are converted with `u128` arithmetic; larger ones go to the
+/// backend selected at compile time. Both paths produce identical digits.
+#[cfg(feature = "alloc")]
+pub(crate) fn to_digits(data: &[u8], base: u8) -> Vec<u8> {
+    if data.len() <= 16 {
+        let mut bytes = [0; 16];
+        bytes[..data.len()].copy_from_slice(data);
+        let n = u128::from_le_bytes(bytes);
+        wipe(&mut bytes);
+        return u128_to_digits(n, base);
+    }
+    backend_to_digits(data, base)
+}
+
+/// Converts digits in `base`, least significant first, back into the minimal little-endian
+/// bytes of the number they represent. Zero produces no bytes.
+///
+/// Values that fit in a `u128` are converted with primitive arithmetic; larger ones go to the
+/// backend selected at compile time. Both paths produce identical bytes.
+#[cfg(feature = "alloc")]
+pub(crate) fn from_digits(digits: &[u8], base: u8) -> Vec<u8> {
+    if digits.len() <= encoded_len(16, base) {
+        if let Some(n) = u128_from_digits(digits, base) {
+            let mut bytes = n.to_le_bytes();
+            let minimal = bytes[..16 - n.leading_zeros() as usize / 8].to_vec();
+            wipe(&mut bytes);
+            return minimal;
+        }
+    }
+    backend_from_digits(digits, base)
+}
+
+/// Converts `n` into its digits in `base`, least significant first.
+#[cfg(feature = "alloc")]
+fn u128_to_digits(mut n: u128, base: u8) -> Vec<u8> {
+    let mut digits = Vec::with_capacity(encoded_len(16, base));
+    while n > 0 {
+        digits.push((n % base as u128) as u8);
+        n /= base as u128;
+    }
+    digits
+}
+
+/// Converts digits in `base`, least significant first, into a `u128` if the value fits.
+#[cfg(feature = "alloc")]
+fn u128_from_digits(digits: &[u8], base: u8) -> Option<u128> {
+    digits.iter().rev().try_fold(0u128, |n, &digit| {
+        n.checked_mul(base as u128)?.checked_add(digit as u128)
+    })
+}
+
+#[cfg(feature = "alloc")]
+fn backend_to_digits(data: &[u8], base: u8) -> Vec<u8> {
+    #[cfg(any(
+        feature = "backend-native",
+        feature = "zeroize",
+        not(feature = "backend-num")
+    ))]
+    return native::to_digits(data, base as u32);
+    #[cfg(all(
+        feature = "backend-num",
+        not(feature = "backend-native"),
+        not(feature = "zeroize")
+    ))]
+    return num_backend::to_digits(data, base);
+}
+
+#[cfg(feature = "alloc")]
+fn backend_from_digits(digits: &[u8], base: u8) -> Vec<u8> {
+    #[cfg(any(
+        feature = "backend-native",
+        feature = "zeroize",
+        not(feature = "backend-num")
+    ))]
+    return native::from_digits(digits, base as u32);
+    #[cfg(all(
+        feature = "backend-num",
+        not(feature = "backend-native"),
+        not(feature = "zeroize")
+    ))]
+    return num_backend::from_digits(digits, base);
+}
+
+/// Looks up the digit value of the first byte of `rest`, found at byte offset `position`,
+/// checking that it is valid in `base`.
+pub(crate) fn digit_value(rest: &[u8], position: usize, base: u8) -> Result<u8, DecodeError> {
+    let c = rest[0];
+    let index = digit_index(c).ok_or_else(|| invalid_character(rest, position))?;
+    if index >= base {
+        return Err(DecodeError::DigitOutOfRange {
+            c: char::from(c),
+            position,
+            base,
+        });
+    }
+    Ok(index)
+}
+
+/// Builds `DecodeError::InvalidCharacter` for the character at the start of `rest`, found at
+/// byte offset `position`. A byte that does not start a valid UTF-8 sequence, including a
+/// sequence cut off by the end of `rest`, is reported as U+FFFD.
+pub(crate) fn invalid_character(rest: &[u8], position: usize) -> DecodeError {
+    let head = &rest[..rest.len().min(4)];
+    let valid = match core::str::from_utf8(head) {
+        Ok(valid) => valid,
+        Err(err) => core::str::from_utf8(&head[..err.valid_up_to()]).unwrap_or_default(),
+    };
+    DecodeError::InvalidCharacter {
+        c: valid.chars().next().unwrap_or(char::REPLACEMENT_CHARACTER),
+        position,
+    }
+}
+
+/// Returns whether `base` is in the supported range (2 to 94).
+fn is_valid_base(base: u8) -> bool {
+    (2..=94).contains(&base)
+}
+
+/// Counts how many elements at the end of `items` are equal to `zero`.
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+pub(crate) fn trailing_zeros(items: &[u8], zero: u8) -> usize {
+    items.iter().rev().take_while(|&&x| x == zero).count()
+}
+
+/// Errors that can occur while decoding data.
+///
+/// New variants may be added in minor releases, so matches on this enum need a wildcard arm.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum DecodeError {
-    #[error("Invalid character '{c}' at position {position}")]
-    InvalidCharacter { c: u8, position: usize },
+    /// `c` is not a digit of the alphabet. It holds the whole character, even when that takes
+    /// several bytes in UTF-8, or U+FFFD for bytes that are not valid UTF-8.
+    ///
+    /// `position` is the byte offset of the character's first byte in the input, so
+    /// `&input[position..]` starts with `c`. Decoders that skip separators or whitespace still
+    /// count them. For input in which every character before `c` is ASCII, which holds
+    /// whenever `c` is the first character that is not a digit, the byte offset is also the
+    /// character index.
+    #[error("Invalid character {c:?} at position {position}")]
+    InvalidCharacter { c: char, position: usize },
+    #[error("Character {c:?} at position {position} is out of range for base {base}")]
+    DigitOutOfRange { c: char, position: usize, base: u8 },
+    #[error("Invalid base {base}, must be in the range 2..=94")]
+    InvalidBase { base: u8 },
+    #[error("Invalid encoded length {length}")]
+    InvalidLength { length: usize },
+    #[error("Input ends before its header or checksum is complete")]
+    TruncatedInput,
+    #[error("Block starting at position {position} holds a value too large for its length")]
+    BlockOverflow { position: usize },
+    #[error("Output buffer too small: {needed} bytes needed, {available} available")]
+    OutputTooSmall { needed: usize, available: usize },
+    #[error("Encoded value does not fit in {bits} bits")]
+    Overflow { bits: u32 },
+    #[error("Decoded data exceeds the capacity of {capacity} bytes")]
+    CapacityExceeded { capacity: usize },
+    #[error("Expected {expected} decoded bytes, got {actual}")]
+    LengthMismatch { expected: usize, actual: usize },
+    #[error("Invalid encoded data")]
+    InvalidInput,
+    #[error("Checksum mismatch: expected {expected:08x}, computed {actual:08x}")]
+    ChecksumMismatch { expected: u32, actual: u32 },
+    #[error("Separator '{separator}' is a digit in base {base}")]
+    SeparatorInAlphabet { separator: char, base: u8 },
+    #[error("Invalid padding at position {position}")]
+    InvalidPadding { position: usize },
+    #[error("Missing '~' tag at the start of the string")]
+    MissingTag,
+    #[error("Unsupported tagged format version '{version}'")]
+    UnsupportedVersion { version: u8 },
+    #[error("Unknown compression method {method}")]
+    UnknownCompression { method: u8 },
+    #[error("Corrupt compressed data")]
+    CorruptCompressedData,
+    #[error("Input is not armored or tagged, and no base was given for plain input")]
+    UnknownFormat,
+    #[cfg(feature = "alloc")]
+    #[error(transparent)]
+    Armor(alloc::boxed::Box<ArmorError>),
+}
+
+impl DecodeError {
+    /// Returns a numeric code for the kind of error, for bindings and structured logs that
+    /// should not depend on the `Display` text.
+    ///
+    /// Codes are stable: a variant keeps its code in every release, and new variants get new
+    /// codes. `0` is never used, so bindings can return it for success.
+    ///
+    /// | Code | Variant |
+    /// |-----:|---------|
+    /// | 1 | `InvalidCharacter` |
+    /// | 2 | `DigitOutOfRange` |
+    /// | 3 | `InvalidBase` |
+    /// | 4 | `InvalidLength` |
+    /// | 5 | `TruncatedInput` |
+    /// | 6 | `BlockOverflow` |
+    /// | 7 | `OutputTooSmall` |
+    /// | 8 | `Overflow` |
+    /// | 9 | `CapacityExceeded` |
+    /// | 10 | `LengthMismatch` |
+    /// | 11 | `InvalidInput` |
+    /// | 12 | `ChecksumMismatch` |
+    /// | 13 | `SeparatorInAlphabet` |
+    /// | 14 | `InvalidPadding` |
+    /// | 15 | `MissingTag` |
+    /// | 16 | `UnsupportedVersion` |
+    /// | 17 | `UnknownCompression` |
+    /// | 18 | `CorruptCompressedData` |
+    /// | 19 | `UnknownFormat` |
+    /// | 20 | `Armor` |
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let error = base94::decode("ab\u{7f}", 94).unwrap_err();
+    /// assert_eq!(error.code(), 1);
+    /// assert_eq!(error.name(), "invalid_character");
+    /// assert_eq!(error.position(), Some(2));
+    /// ```
+    pub fn code(&self) -> u32 {
+        // No wildcard arm, so a new variant does not compile until it has a code.
+        match self {
+            DecodeError::InvalidCharacter { .. } => 1,
+            DecodeError::DigitOutOfRange { .. } => 2,
+            DecodeError::InvalidBase { .. } => 3,
+            DecodeError::InvalidLength { .. } => 4,
+            DecodeError::TruncatedInput => 5,
+            DecodeError::BlockOverflow { .. } => 6,
+            DecodeError::OutputTooSmall { .. } => 7,
+            DecodeError::Overflow { .. } => 8,
+            DecodeError::CapacityExceeded { .. } => 9,
+            DecodeError::LengthMismatch { .. } => 10,
+            DecodeError::InvalidInput => 11,
+            DecodeError::ChecksumMismatch { .. } => 12,
+            DecodeError::SeparatorInAlphabet { .. } => 13,
+            DecodeError::InvalidPadding { .. } => 14,
+            DecodeError::MissingTag => 15,
+            DecodeError::UnsupportedVersion { .. } => 16,
+            DecodeError::UnknownCompression { .. } => 17,
+            DecodeError::CorruptCompressedData => 18,
+            DecodeError::UnknownFormat => 19,
+            #[cfg(feature = "alloc")]
+            DecodeError::Armor(_) => 20,
+        }
+    }
+
+    /// Returns a stable name for the kind of error: the variant name in snake case, such as
+    /// `"invalid_character"` for `InvalidCharacter`.
+    ///
+    /// Like [`code`](Self::code), names never change once released.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DecodeError::InvalidCharacter { .. } => "invalid_character",
+            DecodeError::DigitOutOfRange { .. } => "digit_out_of_range",
+            DecodeError::InvalidBase { .. } => "invalid_base",
+            DecodeError::InvalidLength { .. } => "invalid_length",
+            DecodeError::TruncatedInput => "truncated_input",
+            DecodeError::BlockOverflow { .. } => "block_overflow",
+            DecodeError::OutputTooSmall { .. } => "output_too_small",
+            DecodeError::Overflow { .. } => "overflow",
+            DecodeError::CapacityExceeded { .. } => "capacity_exceeded",
+            DecodeError::LengthMismatch { .. } => "length_mismatch",
+            DecodeError::InvalidInput => "invalid_input",
+            DecodeError::ChecksumMismatch { .. } => "checksum_mismatch",
+            DecodeError::SeparatorInAlphabet { .. } => "separator_in_alphabet",
+            DecodeError::InvalidPadding { .. } => "invalid_padding",
+            DecodeError::MissingTag => "missing_tag",
+            DecodeError::UnsupportedVersion { .. } => "unsupported_version",
+            DecodeError::UnknownCompression { .. } => "unknown_compression",
+            DecodeError::CorruptCompressedData => "corrupt_compressed_data",
+            DecodeError::UnknownFormat => "unknown_format",
+            #[cfg(feature = "alloc")]
+            DecodeError::Armor(_) => "armor",
+        }
+    }
+
+    /// Returns the byte offset into the input that the error points at, for errors that
+    /// have one: `InvalidCharacter`, `DigitOutOfRange`, `BlockOverflow`, and
+    /// `InvalidPadding`.
+    pub fn position(&self) -> Option<usize> {
+        match *self {
+            DecodeError::InvalidCharacter { position, .. }
+            | DecodeError::DigitOutOfRange { position, .. }
+            | DecodeError::BlockOverflow { position }
+            | DecodeError::InvalidPadding { position } => Some(position),
+            _ => None,
+        }
+    }
 }
 
 /// Decodes a Base94-encoded string back to its original byte representation using the specified base.
@@ -120,8 +857,11 @@ pub enum DecodeError {
 ///
 /// # Returns
 ///
-/// An optional vector of bytes representing the decoded original data. Returns `None`
-/// if the decoding process encounters invalid characters.
+/// A vector of bytes representing the decoded original data, or a `DecodeError` if the
+/// decoding process encounters invalid characters or digits that are not valid in `base`,
+/// or if `base` itself is outside the valid range (2 to 94). Every trailing zero digit
+/// decodes to one trailing zero byte, mirroring `encode`. An empty string decodes to an
+/// empty vector.
 ///
 /// # Examples
 ///
@@ -133,28 +873,111 @@ pub enum DecodeError {
 /// let decoded = decode(encoded, base).unwrap();
 /// println!("Decoded: {:?}", decoded);
 /// ```
+#[cfg(feature = "alloc")]
 pub fn decode(encoded: &str, base: u8) -> Result<Vec<u8>, DecodeError> {
-    let mut num = BigUint::from(0u8);
-    let mut power = BigUint::from(1u8);
+    decode_bytes(encoded.as_bytes(), base)
+}
 
-    for (i, c) in encoded.chars().enumerate() {
-        let index =
-            CHARACTERS
-                .iter()
-                .position(|&x| x == c as u8)
-                .ok_or(DecodeError::InvalidCharacter {
-                    c: c as u8,
-                    position: i,
-                })?;
-        num += BigUint::from(index) * &power;
-        power *= BigUint::from(base);
+/// Decodes Base94-encoded text given as raw bytes, like [`decode`] but without requiring
+/// valid UTF-8.
+///
+/// The alphabet is pure ASCII, so any byte outside it, including bytes that are not valid
+/// UTF-8, is reported as `DecodeError::InvalidCharacter` with its byte offset as the
+/// position. Bytes that are not valid UTF-8 are reported as U+FFFD.
+///
+/// # Arguments
+///
+/// * `encoded` - The encoded characters as bytes.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `DecodeError` under the same conditions as `decode`.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_bytes, encode_to_bytes, DecodeError};
+///
+/// let encoded = encode_to_bytes(b"Hello, World!", 94);
+/// assert_eq!(decode_bytes(&encoded, 94).unwrap(), b"Hello, World!");
+/// assert!(matches!(
+///     decode_bytes(b"ab\xff", 94),
+///     Err(DecodeError::InvalidCharacter { c: '\u{fffd}', position: 2 })
+/// ));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_bytes(encoded: &[u8], base: u8) -> Result<Vec<u8>, DecodeError> {
+    let mut digits = parse_digits(encoded, base)?;
+    let bytes = decode_digits(&digits, base);
+    wipe(&mut digits);
+    Ok(bytes)
+}
+
+/// Decodes a Base94-encoded string like [`decode`], but appends the result to a
+/// caller-provided vector.
+///
+/// Existing contents of `out` are kept, which allows decoding many records into one
+/// arena-style buffer. If decoding fails, `out` is left unchanged.
+///
+/// # Arguments
+///
+/// * `encoded` - A Base94-encoded string to be decoded.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+/// * `out` - The vector that the decoded bytes are appended to.
+///
+/// # Returns
+///
+/// The number of bytes appended to `out`, or a `DecodeError` under the same conditions as
+/// `decode`.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_into, encode};
+///
+/// let mut arena = Vec::new();
+/// let first = decode_into(&encode(b"first", 94), 94, &mut arena).unwrap();
+/// let second = decode_into(&encode(b"second", 94), 94, &mut arena).unwrap();
+/// assert_eq!((first, second), (5, 6));
+/// assert_eq!(arena, b"firstsecond");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_into(encoded: &str, base: u8, out: &mut Vec<u8>) -> Result<usize, DecodeError> {
+    let bytes = decode_bytes(encoded.as_bytes(), base)?;
+    out.extend_from_slice(&bytes);
+    Ok(bytes.len())
+}
+
+/// Validates `base` and converts every character of `encoded` into its digit value.
+#[cfg(feature = "alloc")]
+pub(crate) fn parse_digits(encoded: &[u8], base: u8) -> Result<Vec<u8>, DecodeError> {
+    if !is_valid_base(base) {
+        return Err(DecodeError::InvalidBase { base });
     }
 
-    let out = num.to_bytes_le();
-    Ok(out)
+    collect_digits((0..encoded.len()).map(|i| digit_value(&encoded[i..], i, base)))
+}
+
+/// Collects digit lookups, stopping at the first error.
+#[cfg(feature = "alloc")]
+pub(crate) fn collect_digits(
+    lookups: impl Iterator<Item = Result<u8, DecodeError>>,
+) -> Result<Vec<u8>, DecodeError> {
+    let mut digits = Vec::with_capacity(lookups.size_hint().0);
+    for lookup in lookups {
+        match lookup {
+            Ok(digit) => digits.push(digit),
+            Err(e) => {
+                wipe(&mut digits);
+                return Err(e);
+            }
+        }
+    }
+    Ok(digits)
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
 
@@ -163,13 +986,23 @@ mod tests {
     #[test]
     fn test_encode_decode_empty() {
         for base in 2..=MAX_BASE {
-            let data = [0];
+            let data: [u8; 0] = [];
             let encoded = encode(&data, base);
+            assert_eq!(encoded, "");
             let decoded = decode(&encoded, base).unwrap();
             assert_eq!(decoded, data);
         }
     }
 
+    #[test]
+    fn test_encode_decode_single_zero() {
+        for base in 2..=MAX_BASE {
+            let encoded = encode(&[0], base);
+            assert_eq!(encoded, (CHARACTERS[0] as char).to_string());
+            assert_eq!(decode(&encoded, base).unwrap(), [0]);
+        }
+    }
+
     #[test]
     fn test_encode_decode_single_byte() {
         for base in 2..=MAX_BASE {
@@ -209,4 +1042,414 @@ mod tests {
             assert_eq!(decoded, data);
         }
     }
+
+    #[test]
+    fn test_encode_decode_zero_bytes() {
+        for base in 2..=MAX_BASE {
+            for data in [&[0][..], &[0, 0, 0], &[1, 0]] {
+                let encoded = encode(data, base);
+                let decoded = decode(&encoded, base).unwrap();
+                assert_eq!(decoded, data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_trailing_zeros_distinct() {
+        for base in 2..=MAX_BASE {
+            assert_ne!(encode(&[0x41, 0x00, 0x00], base), encode(&[0x41], base));
+        }
+    }
+
+    #[test]
+    fn test_encode_decode_random_zero_suffix() {
+        for base in 2..=MAX_BASE {
+            let mut data = (0..32).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            data.resize(data.len() + rand::random::<usize>() % 8 + 1, 0);
+            let encoded = encode(&data, base);
+            let decoded = decode(&encoded, base).unwrap();
+            assert_eq!(decoded, data);
+        }
+    }
+
+    #[test]
+    fn test_decode_is_canonical() {
+        for base in 2..=MAX_BASE {
+            let encoded = (0..rand::random::<usize>() % 40)
+                .map(|_| CHARACTERS[rand::random::<usize>() % base as usize] as char)
+                .collect::<String>();
+            let decoded = decode(&encoded, base).unwrap();
+            assert_eq!(encode(&decoded, base), encoded);
+        }
+    }
+
+    #[test]
+    fn test_appended_zero_digit_changes_value() {
+        for base in 2..=MAX_BASE {
+            let data = (0..16).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let mut encoded = encode(&data, base);
+            encoded.push(CHARACTERS[0] as char);
+            let mut expected = data.clone();
+            expected.push(0);
+            assert_eq!(decode(&encoded, base).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_u128_fast_path_matches_backend() {
+        for base in 2..=MAX_BASE {
+            for len in 0..=16 {
+                for _ in 0..8 {
+                    let data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+                    let digits = to_digits(&data, base);
+                    assert_eq!(digits, backend_to_digits(&data, base));
+                    assert_eq!(
+                        from_digits(&digits, base),
+                        backend_from_digits(&digits, base)
+                    );
+                }
+            }
+            let max = vec![CHARACTERS[base as usize - 1]; encoded_len(16, base)];
+            let digits = parse_digits(&max, base).unwrap();
+            assert_eq!(
+                from_digits(&digits, base),
+                backend_from_digits(&digits, base)
+            );
+        }
+    }
+
+    #[test]
+    fn test_digit_index_matches_characters() {
+        for c in 0..=u8::MAX {
+            let expected = CHARACTERS.iter().position(|&x| x == c).map(|i| i as u8);
+            assert_eq!(digit_index(c), expected);
+        }
+    }
+
+    #[test]
+    fn test_decode_digit_out_of_range() {
+        let encoded = encode(b"Hello, World!", 94);
+        let expected = encoded
+            .bytes()
+            .position(|c| CHARACTERS.iter().position(|&x| x == c).unwrap() >= 10)
+            .unwrap();
+        match decode(&encoded, 10) {
+            Err(DecodeError::DigitOutOfRange { position, base, .. }) => {
+                assert_eq!(position, expected);
+                assert_eq!(base, 10);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(matches!(
+            decode("~~", 10),
+            Err(DecodeError::DigitOutOfRange {
+                c: '~',
+                position: 0,
+                base: 10
+            })
+        ));
+    }
+
+    #[test]
+    fn test_encode_to_bytes_matches_encode() {
+        for base in 2..=MAX_BASE {
+            for data in [&b"Hello, World!"[..], &[], &[1, 0, 0]] {
+                assert_eq!(encode_to_bytes(data, base), encode(data, base).as_bytes());
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_into_reuses_buffer() {
+        let mut buffer = String::from("stale contents");
+        for base in 2..=MAX_BASE {
+            for data in [&b"Hello, World!"[..], &[], &[1, 0, 0]] {
+                encode_into(data, base, &mut buffer);
+                assert_eq!(buffer, encode(data, base));
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_into_appends() {
+        for base in 2..=MAX_BASE {
+            let mut arena = vec![7];
+            for data in [&b"Hello, World!"[..], &[], &[1, 0, 0]] {
+                let start = arena.len();
+                let written = decode_into(&encode(data, base), base, &mut arena).unwrap();
+                assert_eq!(written, data.len());
+                assert_eq!(&arena[start..], data);
+            }
+            assert_eq!(arena[0], 7);
+        }
+    }
+
+    #[test]
+    fn test_decode_into_leaves_buffer_on_error() {
+        let mut out = vec![1, 2, 3];
+        assert!(decode_into("ab\u{7f}", 94, &mut out).is_err());
+        assert_eq!(out, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_decode_bytes_matches_decode() {
+        for base in 2..=MAX_BASE {
+            let encoded = encode(b"Hello, World!", base);
+            assert_eq!(
+                decode_bytes(encoded.as_bytes(), base).unwrap(),
+                decode(&encoded, base).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_bytes_invalid_utf8() {
+        let mut encoded = encode_to_bytes(b"Hello, World!", 94);
+        encoded.insert(3, 0xC3);
+        assert!(matches!(
+            decode_bytes(&encoded, 94),
+            Err(DecodeError::InvalidCharacter {
+                c: '\u{fffd}',
+                position: 3
+            })
+        ));
+
+        // A sequence cut off by the end of the input.
+        assert!(matches!(
+            decode_bytes(&"ab\u{2192}".as_bytes()[..4], 94),
+            Err(DecodeError::InvalidCharacter {
+                c: '\u{fffd}',
+                position: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_decode_error_compares_and_clones() {
+        let error = decode("ab\u{7f}", 94).unwrap_err();
+        assert_eq!(
+            error,
+            DecodeError::InvalidCharacter {
+                c: '\u{7f}',
+                position: 2
+            }
+        );
+        assert_eq!(error.clone(), error);
+        assert_ne!(error, decode("ab~\u{7f}", 94).unwrap_err());
+        assert_eq!(
+            decode("A", 10).unwrap_err(),
+            DecodeError::DigitOutOfRange {
+                c: 'A',
+                position: 0,
+                base: 10
+            }
+        );
+
+        for (error, message) in [
+            (
+                DecodeError::InvalidBase { base: 95 },
+                "Invalid base 95, must be in the range 2..=94",
+            ),
+            (
+                DecodeError::DigitOutOfRange {
+                    c: 'A',
+                    position: 3,
+                    base: 10,
+                },
+                "Character 'A' at position 3 is out of range for base 10",
+            ),
+            (
+                DecodeError::LengthMismatch {
+                    expected: 32,
+                    actual: 31,
+                },
+                "Expected 32 decoded bytes, got 31",
+            ),
+            (
+                DecodeError::TruncatedInput,
+                "Input ends before its header or checksum is complete",
+            ),
+        ] {
+            assert_eq!(error.to_string(), message);
+        }
+    }
+
+    #[test]
+    fn test_error_codes_and_names() {
+        // One error of every kind. `code` and `name` have no wildcard arm, so a variant
+        // missing from them does not compile; this checks that the table has no gaps or
+        // duplicates.
+        let errors = [
+            DecodeError::InvalidCharacter {
+                c: ' ',
+                position: 1,
+            },
+            DecodeError::DigitOutOfRange {
+                c: '~',
+                position: 2,
+                base: 10,
+            },
+            DecodeError::InvalidBase { base: 1 },
+            DecodeError::InvalidLength { length: 3 },
+            DecodeError::TruncatedInput,
+            DecodeError::BlockOverflow { position: 4 },
+            DecodeError::OutputTooSmall {
+                needed: 2,
+                available: 1,
+            },
+            DecodeError::Overflow { bits: 64 },
+            DecodeError::CapacityExceeded { capacity: 8 },
+            DecodeError::LengthMismatch {
+                expected: 2,
+                actual: 1,
+            },
+            DecodeError::InvalidInput,
+            DecodeError::ChecksumMismatch {
+                expected: 1,
+                actual: 2,
+            },
+            DecodeError::SeparatorInAlphabet {
+                separator: '-',
+                base: 94,
+            },
+            DecodeError::InvalidPadding { position: 5 },
+            DecodeError::MissingTag,
+            DecodeError::UnsupportedVersion { version: b'2' },
+            DecodeError::UnknownCompression { method: 7 },
+            DecodeError::CorruptCompressedData,
+            DecodeError::UnknownFormat,
+            DecodeError::Armor(Box::new(ArmorError::MissingBase)),
+        ];
+        for (i, error) in errors.iter().enumerate() {
+            assert_eq!(error.code(), i as u32 + 1, "{:?}", error);
+            let name = error.name();
+            assert!(name.bytes().all(|c| c.is_ascii_lowercase() || c == b'_'));
+            assert_eq!(
+                name.replace('_', ""),
+                format!("{:?}", error)
+                    .split([' ', '('])
+                    .next()
+                    .unwrap()
+                    .to_lowercase()
+            );
+        }
+
+        let positions = errors
+            .iter()
+            .filter_map(DecodeError::position)
+            .collect::<Vec<usize>>();
+        assert_eq!(positions, [1, 2, 4, 5]);
+        assert_eq!(decode("!!\u{e9}", 94).unwrap_err().position(), Some(2));
+    }
+
+    #[test]
+    fn test_decode_multibyte_invalid_character() {
+        for (text, c, position) in [
+            ("ab\u{e9}cd", '\u{e9}', 2),
+            ("!\u{2192}!", '\u{2192}', 1),
+            ("\u{1f600}", '\u{1f600}', 0),
+            ("~~~~\u{4e00}\u{e9}", '\u{4e00}', 4),
+        ] {
+            let error = decode(text, 94).unwrap_err();
+            assert!(
+                matches!(error, DecodeError::InvalidCharacter { c: found, position: at }
+                    if found == c && at == position),
+                "{:?}",
+                error
+            );
+            assert!(text[position..].starts_with(c));
+            assert_eq!(text[..position].chars().count(), position);
+            assert_eq!(
+                error.to_string(),
+                format!("Invalid character {:?} at position {}", c, position)
+            );
+            assert!(matches!(
+                decode_bytes(text.as_bytes(), 94),
+                Err(DecodeError::InvalidCharacter { c: found, position: at })
+                    if found == c && at == position
+            ));
+        }
+
+        // Bytes count, not characters, once a multibyte character precedes the error.
+        assert!(matches!(
+            decode_grouped("!!\u{b7}!\u{e9}", 94, '\u{b7}'),
+            Err(DecodeError::InvalidCharacter {
+                c: '\u{e9}',
+                position: 5
+            })
+        ));
+    }
+
+    #[test]
+    fn test_try_encode_invalid_base() {
+        for base in [0, 1, 95, 255] {
+            assert!(matches!(
+                try_encode(b"Hello, World!", base),
+                Err(EncodeError::InvalidBase { base: b }) if b == base
+            ));
+        }
+    }
+
+    #[test]
+    fn test_decode_invalid_base() {
+        for base in [0, 1, 95, 255] {
+            let err = decode("abc", base).unwrap_err();
+            assert!(matches!(err, DecodeError::InvalidBase { base: b } if b == base));
+            assert!(err.to_string().contains("2..=94"));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid base 95")]
+    fn test_encode_invalid_base_panics() {
+        encode(b"Hello, World!", 95);
+    }
+
+    /// Round-trips every 1- and 2-byte input in every base and checks that no two inputs of
+    /// the same length share an encoding. Run with `cargo test -- --ignored`.
+    #[test]
+    #[ignore = "exhaustive; run with --ignored"]
+    fn test_exhaustive_small_inputs() {
+        use std::collections::HashSet;
+
+        for base in 2..=MAX_BASE {
+            let mut seen = HashSet::new();
+            for byte in 0..=u8::MAX {
+                let encoded = encode(&[byte], base);
+                assert_eq!(decode(&encoded, base).unwrap(), [byte], "base {}", base);
+                assert!(
+                    seen.insert(encoded),
+                    "collision for {} in base {}",
+                    byte,
+                    base
+                );
+            }
+
+            let mut seen = HashSet::with_capacity(1 << 16);
+            for n in 0..=u16::MAX {
+                let data = n.to_le_bytes();
+                let encoded = encode(&data, base);
+                assert_eq!(decode(&encoded, base).unwrap(), data, "base {}", base);
+                assert!(seen.insert(encoded), "collision for {} in base {}", n, base);
+            }
+        }
+    }
+
+    /// Round-trips 3-byte inputs on a fixed stride, which covers every byte value in each
+    /// position without the 16 million inputs of a full sweep.
+    #[test]
+    #[ignore = "exhaustive; run with --ignored"]
+    fn test_stride_three_byte_inputs() {
+        use std::collections::HashSet;
+
+        for base in 2..=MAX_BASE {
+            let mut seen = HashSet::new();
+            for n in (0..1u32 << 24).step_by(251).chain([(1 << 24) - 1]) {
+                let data = &n.to_le_bytes()[..3];
+                let encoded = encode(data, base);
+                assert_eq!(decode(&encoded, base).unwrap(), data, "base {}", base);
+                assert!(seen.insert(encoded), "collision for {} in base {}", n, base);
+            }
+        }
+    }
 }
@@ -0,0 +1,197 @@
+//! Lenient decoding for text that has picked up stray characters.
+
+use alloc::vec::Vec;
+
+use crate::{decode_digits, digit_value, is_valid_base, wipe, DecodeError, EncodeError};
+
+/// Decodes `encoded` like [`decode`](crate::decode), but skips every character that is not a
+/// valid digit in `base` instead of failing.
+///
+/// Skipped characters are dropped, not replaced, so blobs scraped from logs or HTML with
+/// injected line numbers, quoting, or markup still decode as long as the remaining digits
+/// are intact. On clean input the result is identical to `decode`.
+///
+/// # Arguments
+///
+/// * `encoded` - The string to decode.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Returns
+///
+/// The decoded bytes and the number of characters that were skipped.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_lossy, encode};
+///
+/// let encoded = encode(b"Hello", 16);
+/// let noisy = format!("\u{e9}{}\u{e9}", encoded);
+/// assert_eq!(decode_lossy(&noisy, 16), (b"Hello".to_vec(), 2));
+/// ```
+pub fn decode_lossy(encoded: &str, base: u8) -> (Vec<u8>, usize) {
+    if !is_valid_base(base) {
+        panic!("{}", EncodeError::InvalidBase { base });
+    }
+
+    let mut digits = Vec::with_capacity(encoded.len());
+    let mut skipped = 0;
+    for (i, c) in encoded.chars().enumerate() {
+        match u8::try_from(c)
+            .ok()
+            .and_then(|c| digit_value(&[c], i, base).ok())
+        {
+            Some(digit) => digits.push(digit),
+            None => skipped += 1,
+        }
+    }
+
+    (decode_digits(&digits, base), skipped)
+}
+
+/// Decodes the longest valid prefix of `encoded`, for recovering data from truncated or
+/// corrupted blobs.
+///
+/// Unlike [`decode_lossy`], nothing after the first invalid character is used: the bytes are
+/// exactly what [`decode`](crate::decode) returns for the text before it.
+///
+/// # Arguments
+///
+/// * `encoded` - The string to decode.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+///
+/// # Returns
+///
+/// The decoded prefix, and the error that stopped decoding, or `None` if the whole input is
+/// valid. The error is the one `decode` reports for `encoded`, so its position is where the
+/// prefix ends. An invalid base gives no bytes and `DecodeError::InvalidBase`.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_partial, encode, DecodeError};
+///
+/// let encoded = encode(b"Hello", 94);
+/// let damaged = format!("{} \u{fffd}\u{fffd}", encoded);
+/// let (data, error) = decode_partial(&damaged, 94);
+/// assert_eq!(data, b"Hello");
+/// assert_eq!(
+///     error,
+///     Some(DecodeError::InvalidCharacter { c: ' ', position: encoded.len() })
+/// );
+/// ```
+pub fn decode_partial(encoded: &str, base: u8) -> (Vec<u8>, Option<DecodeError>) {
+    if !is_valid_base(base) {
+        return (Vec::new(), Some(DecodeError::InvalidBase { base }));
+    }
+
+    let mut digits = Vec::with_capacity(encoded.len());
+    let mut error = None;
+    for i in 0..encoded.len() {
+        match digit_value(&encoded.as_bytes()[i..], i, base) {
+            Ok(digit) => digits.push(digit),
+            Err(err) => {
+                error = Some(err);
+                break;
+            }
+        }
+    }
+
+    let decoded = decode_digits(&digits, base);
+    wipe(&mut digits);
+    (decoded, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_decode_lossy_clean_input() {
+        for base in 2..=MAX_BASE {
+            let encoded = encode(b"Hello, World!", base);
+            assert_eq!(
+                decode_lossy(&encoded, base),
+                (decode(&encoded, base).unwrap(), 0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_lossy_interleaved_garbage() {
+        // Base 10 only uses `!` through `*`, so digits and letters are garbage here.
+        let encoded = encode(b"Hello, World!", 10);
+        let (head, tail) = encoded.split_at(encoded.len() / 2);
+        let noisy = format!("12 \u{2192}{}line 7{}\u{1F600}\n", head, tail);
+        assert_eq!(decode_lossy(&noisy, 10), (b"Hello, World!".to_vec(), 12));
+    }
+
+    #[test]
+    fn test_decode_lossy_only_garbage() {
+        assert_eq!(decode_lossy("\u{e9}\u{e9}", 94), (Vec::new(), 2));
+    }
+
+    #[test]
+    fn test_decode_partial_clean_input() {
+        for base in 2..=MAX_BASE {
+            let data = (0..50).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            assert_eq!(decode_partial(&encode(&data, base), base), (data, None));
+        }
+        assert_eq!(decode_partial("", 94), (Vec::new(), None));
+    }
+
+    #[test]
+    fn test_decode_partial_trailing_garbage() {
+        for base in 2..=MAX_BASE {
+            let mut data = (0..50).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            data.extend([0, 0]);
+            let encoded = encode(&data, base);
+            for garbage in [" ", "\u{e9}!!", "\u{2192}~", "\n-----END-----"] {
+                let damaged = format!("{}{}", encoded, garbage);
+                let (decoded, error) = decode_partial(&damaged, base);
+                assert_eq!(decoded, data);
+                let error = error.unwrap();
+                assert_eq!(Err(error.clone()), decode(&damaged, base));
+                assert!(matches!(
+                    error,
+                    DecodeError::InvalidCharacter { position, .. }
+                        | DecodeError::DigitOutOfRange { position, .. }
+                        if position == encoded.len()
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_partial_matches_prefix() {
+        let encoded = encode(b"Hello, World!", 94);
+        for cut in 0..encoded.len() {
+            let damaged = format!("{}\u{e9}{}", &encoded[..cut], &encoded[cut..]);
+            assert_eq!(
+                decode_partial(&damaged, 94),
+                (
+                    decode(&encoded[..cut], 94).unwrap(),
+                    Some(DecodeError::InvalidCharacter {
+                        c: '\u{e9}',
+                        position: cut
+                    })
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_partial_invalid_base() {
+        assert_eq!(
+            decode_partial("!!", 95),
+            (Vec::new(), Some(DecodeError::InvalidBase { base: 95 }))
+        );
+    }
+}
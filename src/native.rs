@@ -0,0 +1,157 @@
+//! Radix conversion on plain `u32` limbs.
+//!
+//! Instead of dividing a big number by the base once per digit, the number is divided by
+//! the largest power of the base that fits in a `u32`, and the digits of each remainder are
+//! then split off with single-word arithmetic. The functions are `#[inline(always)]` so that
+//! callers with a constant base get division by a constant, which compiles to a
+//! multiplication.
+
+use alloc::vec::Vec;
+
+/// Returns the largest power of `base` that fits in a `u32`, and its exponent.
+#[inline(always)]
+pub(crate) fn word_power(base: u32) -> (u32, usize) {
+    let mut power = base;
+    let mut exponent = 1;
+    while let Some(next) = power.checked_mul(base) {
+        power = next;
+        exponent += 1;
+    }
+    (power, exponent)
+}
+
+/// Converts the little-endian number in `data` into its digits in `base`, least significant
+/// first. Zero produces no digits.
+#[inline(always)]
+pub(crate) fn to_digits(data: &[u8], base: u32) -> Vec<u8> {
+    let (power, exponent) = word_power(base);
+    let mut limbs = data
+        .chunks(4)
+        .map(|chunk| {
+            let mut word = [0; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            u32::from_le_bytes(word)
+        })
+        .collect::<Vec<u32>>();
+    // Every division emits `exponent` digits, so the count can overshoot by one word's worth
+    // before the leading zeros are trimmed. Reserving that up front avoids reallocating, which
+    // would leave an unwiped copy of the digits with the `zeroize` feature.
+    let mut digits =
+        Vec::with_capacity(crate::len::max_digits(data.len() as u128 * 8, base as u8) + exponent);
+
+    // The limbs are divided in place until they are all zero, so they need no wiping.
+    while let Some(&top) = limbs.last() {
+        if top == 0 {
+            limbs.pop();
+            continue;
+        }
+
+        let mut rem = 0u64;
+        for limb in limbs.iter_mut().rev() {
+            let cur = (rem << 32) | *limb as u64;
+            *limb = (cur / power as u64) as u32;
+            rem = cur % power as u64;
+        }
+
+        let mut rem = rem as u32;
+        for _ in 0..exponent {
+            digits.push((rem % base) as u8);
+            rem /= base;
+        }
+    }
+
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    digits
+}
+
+/// Converts digits in `base`, least significant first, back into the minimal little-endian
+/// bytes of the number they represent. Zero produces no bytes.
+#[inline(always)]
+pub(crate) fn from_digits(digits: &[u8], base: u32) -> Vec<u8> {
+    let (_, exponent) = word_power(base);
+    let mut limbs: Vec<u32> = Vec::with_capacity(digits.len() / 4 + 1);
+
+    // The most significant group may be shorter than the others.
+    let first = digits.len() % exponent;
+    let groups = core::iter::once(&digits[digits.len() - first..])
+        .filter(|group| !group.is_empty())
+        .chain(digits[..digits.len() - first].rchunks(exponent));
+
+    for group in groups {
+        let mut multiplier = 1u32;
+        let mut value = 0u32;
+        for &digit in group.iter().rev() {
+            value = value * base + digit as u32;
+            multiplier *= base;
+        }
+
+        let mut carry = value as u64;
+        for limb in limbs.iter_mut() {
+            let cur = *limb as u64 * multiplier as u64 + carry;
+            *limb = cur as u32;
+            carry = cur >> 32;
+        }
+        if carry != 0 {
+            limbs.push(carry as u32);
+        }
+    }
+
+    let mut bytes = Vec::with_capacity(limbs.len() * 4);
+    for limb in &limbs {
+        bytes.extend_from_slice(&limb.to_le_bytes());
+    }
+    crate::wipe(&mut limbs);
+    while bytes.last() == Some(&0) {
+        bytes.pop();
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_native_round_trip() {
+        for base in 2..=MAX_BASE {
+            for len in [0, 1, 3, 4, 5, 17, 100] {
+                let mut data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+                if let Some(last) = data.last_mut() {
+                    *last |= 1;
+                }
+                let digits = to_digits(&data, base as u32);
+                assert!(digits.iter().all(|&digit| digit < base));
+                assert_eq!(from_digits(&digits, base as u32), data);
+            }
+        }
+    }
+
+    #[cfg(feature = "backend-num")]
+    #[test]
+    fn test_native_matches_num_backend() {
+        use crate::num_backend;
+
+        for base in 2..=MAX_BASE {
+            for len in [0, 1, 3, 4, 5, 17, 100, 1000] {
+                let data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+                let digits = to_digits(&data, base as u32);
+                assert_eq!(digits, num_backend::to_digits(&data, base));
+                assert_eq!(
+                    from_digits(&digits, base as u32),
+                    num_backend::from_digits(&digits, base)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_native_zero() {
+        assert!(to_digits(&[0, 0, 0], 94).is_empty());
+        assert!(from_digits(&[0, 0], 94).is_empty());
+        assert!(from_digits(&[], 94).is_empty());
+    }
+}
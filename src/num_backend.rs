@@ -0,0 +1,292 @@
+//! Radix conversion using `num`'s `BigUint`.
+//!
+//! Small numbers are converted one digit at a time, which is quadratic in their length. Above
+//! [`SPLIT_BITS`] the conversion switches to divide and conquer: the number is split by a
+//! precomputed power `base^k` into a high and a low half, and both halves are converted
+//! recursively. The powers `base^k0`, `base^2k0`, `base^4k0`, ... are computed once per call.
+//! Decoding combines two halves with a single multiplication, and encoding splits with a
+//! division that is carried out as a multiplication by a precomputed reciprocal, so both
+//! directions inherit the Karatsuba/Toom-3 multiplication of `BigUint`.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num::BigInt;
+use num::BigUint;
+use num::Integer;
+use num::Signed;
+use num::Zero;
+
+use crate::len::max_digits;
+
+/// Numbers of at most this many bits are converted with the simple algorithm.
+pub(crate) const SPLIT_BITS: u64 = 4096;
+
+/// Reciprocals of divisors of at most this many bits are computed with a plain division.
+const RECIPROCAL_BITS: u64 = 2048;
+
+/// Converts the little-endian number in `data` into its digits in `base`, least significant
+/// first. Zero produces no digits.
+pub(crate) fn to_digits(data: &[u8], base: u8) -> Vec<u8> {
+    to_digits_with_threshold(data, base, SPLIT_BITS)
+}
+
+/// Converts digits in `base`, least significant first, back into the minimal little-endian
+/// bytes of the number they represent. Zero produces no bytes.
+pub(crate) fn from_digits(digits: &[u8], base: u8) -> Vec<u8> {
+    from_digits_with_threshold(digits, base, SPLIT_BITS)
+}
+
+fn to_digits_with_threshold(data: &[u8], base: u8, split_bits: u64) -> Vec<u8> {
+    let num = BigUint::from_bytes_le(data);
+    if num.bits() <= split_bits {
+        return simple_to_digits(num, base);
+    }
+
+    // `powers[i]` is `base^(width << i)`; stop at the first power larger than the number, so
+    // that the top level splits a number smaller than the square of its divisor.
+    let width = leaf_width(base, split_bits);
+    let mut powers = vec![BigUint::from(base).pow(width as u32)];
+    while powers[powers.len() - 1] <= num {
+        let last = &powers[powers.len() - 1];
+        powers.push(last * last);
+    }
+    powers.pop();
+    let powers = powers.into_iter().map(Power::new).collect::<Vec<_>>();
+
+    let mut digits = Vec::with_capacity(width << powers.len());
+    split_to_digits(num, base, &powers, width, false, &mut digits);
+    digits
+}
+
+/// Appends the digits of `num < powers[powers.len()]` to `out`. If `pad` is set the digits are
+/// padded with zeros to exactly `width << powers.len()` digits, since a low half must keep
+/// its full width even when its top digits are zero.
+fn split_to_digits(
+    num: BigUint,
+    base: u8,
+    powers: &[Power],
+    width: usize,
+    pad: bool,
+    out: &mut Vec<u8>,
+) {
+    let Some((power, lower)) = powers.split_last() else {
+        let start = out.len();
+        out.extend(simple_to_digits(num, base));
+        if pad {
+            out.resize(start + width, 0);
+        }
+        return;
+    };
+
+    let (high, low) = power.div_rem(num);
+    if !pad && high.is_zero() {
+        return split_to_digits(low, base, lower, width, false, out);
+    }
+    split_to_digits(low, base, lower, width, true, out);
+    split_to_digits(high, base, lower, width, pad, out);
+}
+
+fn from_digits_with_threshold(digits: &[u8], base: u8, split_bits: u64) -> Vec<u8> {
+    let width = leaf_width(base, split_bits);
+    let num = if digits.len() <= width {
+        simple_from_digits(digits, base)
+    } else {
+        let mut powers = vec![BigUint::from(base).pow(width as u32)];
+        while width << powers.len() < digits.len() {
+            let last = &powers[powers.len() - 1];
+            powers.push(last * last);
+        }
+        split_from_digits(digits, base, &powers, width)
+    };
+
+    if num.is_zero() {
+        Vec::new()
+    } else {
+        num.to_bytes_le()
+    }
+}
+
+/// Combines `digits`, which hold at most `width << powers.len()` digits, into a number.
+fn split_from_digits(digits: &[u8], base: u8, powers: &[BigUint], width: usize) -> BigUint {
+    let Some((power, lower)) = powers.split_last() else {
+        return simple_from_digits(digits, base);
+    };
+
+    let half = width << lower.len();
+    if digits.len() <= half {
+        return split_from_digits(digits, base, lower, width);
+    }
+    let (low, high) = digits.split_at(half);
+    split_from_digits(high, base, lower, width) * power + split_from_digits(low, base, lower, width)
+}
+
+/// A divisor together with its reciprocal, so that numbers below its square can be divided by
+/// it with two multiplications.
+struct Power {
+    value: BigUint,
+    /// `2^shift / value`, rounded down.
+    reciprocal: BigUint,
+    shift: u64,
+}
+
+impl Power {
+    fn new(value: BigUint) -> Self {
+        let shift = 2 * value.bits();
+        let reciprocal = reciprocal(&value);
+        Power {
+            value,
+            reciprocal,
+            shift,
+        }
+    }
+
+    /// Divides `num < value^2` by `value`, returning the quotient and the remainder.
+    fn div_rem(&self, num: BigUint) -> (BigUint, BigUint) {
+        // The estimate is never too large and, since `num < 2^shift`, at most two too small.
+        let mut quotient = (&num * &self.reciprocal) >> self.shift;
+        let mut remainder = num - &quotient * &self.value;
+        while remainder >= self.value {
+            remainder -= &self.value;
+            quotient += 1u8;
+        }
+        (quotient, remainder)
+    }
+}
+
+/// Computes `2^(2 * bits) / value` rounded down, where `bits` is the bit length of `value`.
+///
+/// The reciprocal of the top half of `value` gives an estimate accurate to about half the
+/// bits, and one Newton step `x + x * (2^shift - value * x) / 2^shift` doubles that.
+fn reciprocal(value: &BigUint) -> BigUint {
+    let bits = value.bits();
+    let shift = 2 * bits;
+    if bits <= RECIPROCAL_BITS {
+        return (BigUint::from(1u8) << shift) / value;
+    }
+
+    let half = bits / 2 + 1;
+    let estimate = BigInt::from(reciprocal(&(value >> (bits - half))) << (bits - half));
+    let value = BigInt::from(value.clone());
+    let one = BigInt::from(1u8) << shift;
+
+    let error = &one - &value * &estimate;
+    let mut x = &estimate + ((&estimate * error) >> shift);
+    let mut remainder = &one - &value * &x;
+    while remainder.is_negative() {
+        x -= 1u8;
+        remainder += &value;
+    }
+    while remainder >= value {
+        x += 1u8;
+        remainder -= &value;
+    }
+    x.to_biguint().expect("reciprocal is positive")
+}
+
+/// Returns the number of digits handled by a leaf of the recursion, about the largest width
+/// whose values fit in `split_bits` bits.
+fn leaf_width(base: u8, split_bits: u64) -> usize {
+    (max_digits(split_bits as u128, base) - 1).max(1)
+}
+
+fn simple_to_digits(mut num: BigUint, base: u8) -> Vec<u8> {
+    let mut digits = Vec::new();
+
+    while num > BigUint::from(0u8) {
+        let (div, rem) = num.div_rem(&BigUint::from(base));
+        num = div;
+        // The remainder is below the base, so it is its own lowest digit.
+        digits.push(rem.iter_u32_digits().next().unwrap_or(0) as u8);
+    }
+
+    digits
+}
+
+fn simple_from_digits(digits: &[u8], base: u8) -> BigUint {
+    let mut num = BigUint::from(0u8);
+
+    for &digit in digits.iter().rev() {
+        num = num * base + digit;
+    }
+
+    num
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_BASE: u8 = 94;
+
+    fn random_bytes(len: usize) -> Vec<u8> {
+        (0..len).map(|_| rand::random::<u8>()).collect()
+    }
+
+    #[test]
+    fn test_split_matches_simple() {
+        // A tiny threshold makes even short inputs recurse through several levels.
+        for base in 2..=MAX_BASE {
+            for _ in 0..40 {
+                let len = rand::random::<usize>() % 200;
+                let mut data = random_bytes(len);
+                if rand::random::<bool>() {
+                    data.truncate(len / 2);
+                }
+                let num = BigUint::from_bytes_le(&data);
+                let expected = simple_to_digits(num.clone(), base);
+
+                let digits = to_digits_with_threshold(&data, base, 16);
+                assert_eq!(digits, expected);
+
+                let bytes = from_digits_with_threshold(&digits, base, 16);
+                let expected_bytes = if num.is_zero() {
+                    Vec::new()
+                } else {
+                    num.to_bytes_le()
+                };
+                assert_eq!(bytes, expected_bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_preserves_inner_zero_digits() {
+        for base in 2..=MAX_BASE {
+            // base^n has a long run of zero digits that every low half must keep.
+            let num = BigUint::from(base).pow(300) + 1u8;
+            let digits = to_digits_with_threshold(&num.to_bytes_le(), base, 16);
+            assert_eq!(digits, simple_to_digits(num.clone(), base));
+            assert_eq!(
+                from_digits_with_threshold(&digits, base, 16),
+                num.to_bytes_le()
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_large_input() {
+        let data = random_bytes(20_000);
+        for base in [2, 10, 94] {
+            let digits = to_digits(&data, base);
+            assert_eq!(
+                digits,
+                simple_to_digits(BigUint::from_bytes_le(&data), base)
+            );
+            let mut bytes = from_digits(&digits, base);
+            bytes.resize(data.len(), 0);
+            assert_eq!(bytes, data);
+        }
+    }
+
+    #[test]
+    fn test_reciprocal() {
+        for len in [1, 100, 256, 257, 1000, 5000] {
+            let mut data = random_bytes(len);
+            data[len - 1] |= 1;
+            let value = BigUint::from_bytes_le(&data);
+            let expected = (BigUint::from(1u8) << (2 * value.bits())) / &value;
+            assert_eq!(reciprocal(&value), expected);
+        }
+    }
+}
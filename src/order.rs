@@ -0,0 +1,132 @@
+//! Choice of digit order for the encoded string.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{decode_digits, encode, parse_digits, DecodeError};
+
+/// The order in which digits appear in an encoded string.
+///
+/// Strings produced with one order cannot be decoded with the other: the same characters
+/// in reverse order represent a different number.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DigitOrder {
+    /// Least significant digit first. This is what [`encode`] and [`decode`](crate::decode)
+    /// use.
+    #[default]
+    LsbFirst,
+    /// Most significant digit first, the way hex, Base58, and most positional-notation tools
+    /// print numbers. Trailing zero bytes then show up as leading zero digits.
+    MsbFirst,
+}
+
+/// Encodes a slice of bytes like [`encode`], emitting digits in the given order.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+/// * `order` - The order of the digits in the output.
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode, encode_with_order, DigitOrder};
+///
+/// let lsb = encode_with_order(b"Hello", 94, DigitOrder::LsbFirst);
+/// let msb = encode_with_order(b"Hello", 94, DigitOrder::MsbFirst);
+/// assert_eq!(lsb, encode(b"Hello", 94));
+/// assert_eq!(msb, lsb.chars().rev().collect::<String>());
+/// ```
+pub fn encode_with_order(data: &[u8], base: u8, order: DigitOrder) -> String {
+    let encoded = encode(data, base);
+    match order {
+        DigitOrder::LsbFirst => encoded,
+        DigitOrder::MsbFirst => encoded.chars().rev().collect(),
+    }
+}
+
+/// Decodes a string produced by [`encode_with_order`] with the same order.
+///
+/// # Arguments
+///
+/// * `encoded` - An encoded string to be decoded.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+/// * `order` - The digit order used for encoding.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `DecodeError` under the same conditions as
+/// [`decode`](crate::decode). Error positions refer to `encoded` as given.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_with_order, encode_with_order, DigitOrder};
+///
+/// let encoded = encode_with_order(b"Hello", 94, DigitOrder::MsbFirst);
+/// let decoded = decode_with_order(&encoded, 94, DigitOrder::MsbFirst).unwrap();
+/// assert_eq!(decoded, b"Hello");
+/// ```
+pub fn decode_with_order(
+    encoded: &str,
+    base: u8,
+    order: DigitOrder,
+) -> Result<Vec<u8>, DecodeError> {
+    let mut digits = parse_digits(encoded.as_bytes(), base)?;
+    if order == DigitOrder::MsbFirst {
+        digits.reverse();
+    }
+    Ok(decode_digits(&digits, base))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode;
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_encode_decode_with_order() {
+        for base in 2..=MAX_BASE {
+            for order in [DigitOrder::LsbFirst, DigitOrder::MsbFirst] {
+                for data in [&b"Hello, World!"[..], &[], &[0, 0], &[1, 0, 0], &[0, 0, 1]] {
+                    let encoded = encode_with_order(data, base, order);
+                    assert_eq!(decode_with_order(&encoded, base, order).unwrap(), data);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cross_order_mismatch() {
+        for base in 2..=MAX_BASE {
+            let data = b"Hello, World!";
+            let msb = encode_with_order(data, base, DigitOrder::MsbFirst);
+            let lsb = encode_with_order(data, base, DigitOrder::LsbFirst);
+            assert_ne!(decode(&msb, base).unwrap(), data);
+            assert_ne!(
+                decode_with_order(&lsb, base, DigitOrder::MsbFirst).unwrap(),
+                data
+            );
+        }
+    }
+
+    #[test]
+    fn test_msb_first_error_position() {
+        assert!(matches!(
+            decode_with_order("ab\u{7f}", 94, DigitOrder::MsbFirst),
+            Err(DecodeError::InvalidCharacter { position: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_msb_first_trailing_zeros_lead() {
+        assert!(encode_with_order(&[1, 0, 0], 94, DigitOrder::MsbFirst).starts_with("!!"));
+    }
+}
@@ -0,0 +1,137 @@
+//! Fixed-width encoding for constant-length output.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{decode, encode_digits, is_valid_base, DecodeError, EncodeError, CHARACTERS};
+
+/// Encodes a slice of bytes like [`encode`](crate::encode), then pads the result with zero
+/// digits up to exactly `width` characters.
+///
+/// Padding goes after the last digit, i.e. on the most significant side, so it does not
+/// change the encoded number. Use [`encoded_len`](crate::encoded_len) for a width that fits
+/// every value of a given byte length.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+/// * `width` - The exact length of the output.
+///
+/// # Returns
+///
+/// The padded string, `EncodeError::InvalidBase` if the base is invalid, or
+/// `EncodeError::WidthTooSmall` if the natural encoding is longer than `width`.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_padded, encode_padded, encoded_len};
+///
+/// let width = encoded_len(16, 94);
+/// let encoded = encode_padded(&[0; 16], 94, width).unwrap();
+/// assert_eq!(encoded.len(), width);
+/// assert_eq!(decode_padded(&encoded, 94, 16).unwrap(), [0; 16]);
+/// ```
+pub fn encode_padded(data: &[u8], base: u8, width: usize) -> Result<String, EncodeError> {
+    if !is_valid_base(base) {
+        return Err(EncodeError::InvalidBase { base });
+    }
+
+    let mut digits = encode_digits(data, base);
+    if digits.len() > width {
+        return Err(EncodeError::WidthTooSmall {
+            width,
+            needed: digits.len(),
+        });
+    }
+    digits.resize(width, 0);
+
+    Ok(digits
+        .into_iter()
+        .map(|digit| CHARACTERS[digit as usize] as char)
+        .collect())
+}
+
+/// Decodes a string produced by [`encode_padded`] back to the original `len` bytes.
+///
+/// # Arguments
+///
+/// * `encoded` - A padded string to be decoded.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+/// * `len` - The length of the original data.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `DecodeError` under the same conditions as
+/// [`decode`](crate::decode). Strings that are too short to be padded `len`-byte values
+/// produce `DecodeError::InvalidLength`, and values that do not fit in `len` bytes produce
+/// `DecodeError::Overflow`.
+pub fn decode_padded(encoded: &str, base: u8, len: usize) -> Result<Vec<u8>, DecodeError> {
+    let mut bytes = decode(encoded, base)?;
+    if bytes.len() < len {
+        return Err(DecodeError::InvalidLength {
+            length: encoded.len(),
+        });
+    }
+    if bytes[len..].iter().any(|&b| b != 0) {
+        return Err(DecodeError::Overflow {
+            bits: len as u32 * 8,
+        });
+    }
+    bytes.truncate(len);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{encode, encoded_len};
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_padded_16_byte_values_have_constant_width() {
+        for base in 2..=MAX_BASE {
+            let width = encoded_len(16, base);
+            let mut values = vec![[0; 16], [255; 16], [1; 16]];
+            values.extend((0..8).map(|_| rand::random::<[u8; 16]>()));
+            let mut leading = [0; 16];
+            leading[15] = 1;
+            let mut trailing = [0; 16];
+            trailing[0] = 1;
+            values.extend([leading, trailing]);
+
+            for data in values {
+                let encoded = encode_padded(&data, base, width).unwrap();
+                assert_eq!(encoded.len(), width);
+                assert_eq!(decode_padded(&encoded, base, 16).unwrap(), data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_padded_width_too_small() {
+        let needed = encode(b"Hello, World!", 94).len();
+        assert!(matches!(
+            encode_padded(b"Hello, World!", 94, needed - 1),
+            Err(EncodeError::WidthTooSmall { width, needed: n }) if width == needed - 1 && n == needed
+        ));
+        assert_eq!(
+            encode_padded(b"Hello, World!", 94, needed).unwrap(),
+            encode(b"Hello, World!", 94)
+        );
+    }
+
+    #[test]
+    fn test_decode_padded_rejects_wrong_lengths() {
+        assert!(matches!(
+            decode_padded("A", 94, 2),
+            Err(DecodeError::InvalidLength { length: 1 })
+        ));
+        assert!(matches!(
+            decode_padded("~~~", 94, 1),
+            Err(DecodeError::Overflow { bits: 8 })
+        ));
+    }
+}
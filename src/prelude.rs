@@ -0,0 +1,10 @@
+//! Glob-importable extension traits.
+//!
+//! ```
+//! use base94::prelude::*;
+//!
+//! let encoded = b"Hello, World!".to_base94();
+//! assert_eq!(encoded.from_base94().unwrap(), b"Hello, World!");
+//! ```
+
+pub use crate::ext::{FromBaseEncoded, ToBaseEncoded};
@@ -0,0 +1,216 @@
+//! Ready-made alphabets for text that is embedded in other formats.
+//!
+//! The standard alphabet uses every printable ASCII character except space, which includes
+//! characters that must be escaped in JSON strings, shell words, URLs, or XML. Each preset
+//! leaves those out, trading a slightly larger output for text that can be pasted without
+//! escaping. Expansion ratios are encoded characters per input byte for large inputs; the
+//! standard alphabet's is 1.22.
+//!
+//! ```
+//! use base94::{decode_with_alphabet, encode_with_alphabet, presets};
+//!
+//! let encoded = encode_with_alphabet(b"Hello, World!", &presets::JSON_SAFE);
+//! assert!(!encoded.contains(['"', '\\']));
+//! assert_eq!(decode_with_alphabet(&encoded, &presets::JSON_SAFE).unwrap(), b"Hello, World!");
+//! ```
+
+use crate::{Alphabet, ALPHABET};
+
+/// Builds an alphabet at compile time, failing compilation if `symbols` is invalid.
+const fn preset(symbols: &[u8]) -> Alphabet {
+    match Alphabet::new(symbols) {
+        Ok(alphabet) => alphabet,
+        Err(_) => panic!("invalid preset alphabet"),
+    }
+}
+
+/// Builds the standard alphabet without the characters in `excluded`.
+const fn standard_without(excluded: &[u8]) -> Alphabet {
+    let mut symbols = [0; 94];
+    let mut len = 0;
+    let mut i = 0;
+    while i < ALPHABET.len() {
+        let mut keep = true;
+        let mut j = 0;
+        while j < excluded.len() {
+            keep &= ALPHABET[i] != excluded[j];
+            j += 1;
+        }
+        if keep {
+            symbols[len] = ALPHABET[i];
+            len += 1;
+        }
+        i += 1;
+    }
+    preset(symbols.split_at(len).0)
+}
+
+/// The standard alphabet without `"` and `\`, so the output can be placed in a JSON string
+/// as is.
+///
+/// Base 92, expanding data by 1.23 characters per byte.
+pub const JSON_SAFE: Alphabet = standard_without(b"\"\\");
+
+/// The standard alphabet without `'`, `"`, `` ` ``, `$`, `\`, and `!`, so the output can be
+/// quoted in POSIX shells and is not subject to expansion inside double quotes or history
+/// expansion in interactive shells.
+///
+/// Base 88, expanding data by 1.24 characters per byte.
+pub const SHELL_SAFE: Alphabet = standard_without(b"'\"`$\\!");
+
+/// The RFC 3986 unreserved characters (`A-Z`, `a-z`, `0-9`, `-`, `.`, `_`, `~`) plus the
+/// sub-delimiters `!`, `'`, `(`, `)`, and `*`, so the output can be used in URL paths and
+/// query values without percent-encoding. These are the characters that JavaScript's
+/// `encodeURIComponent` leaves alone.
+///
+/// Base 71, expanding data by 1.30 characters per byte.
+pub const URL_SAFE: Alphabet =
+    preset(b"!'()*-.0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ_abcdefghijklmnopqrstuvwxyz~");
+
+/// The digits, upper-case letters, and lower-case letters, in ASCII order, as used by URL
+/// shorteners and most Base62 libraries.
+///
+/// Base 62, expanding data by 1.34 characters per byte. Those libraries print numbers most
+/// significant digit first and read bytes as a big-endian number, so use
+/// [`encode_big_endian_with_alphabet`] for compatible output; [`encode_with_alphabet`]
+/// uses this crate's least-significant-first order.
+///
+/// [`encode_with_alphabet`]: crate::encode_with_alphabet
+/// [`encode_big_endian_with_alphabet`]: crate::encode_big_endian_with_alphabet
+pub const BASE62: Alphabet =
+    preset(b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz");
+
+/// Bitcoin's Base58 alphabet: the digits and letters without `0`, `O`, `I`, and `l`, which
+/// are easy to confuse.
+///
+/// Base 58, expanding data by 1.37 characters per byte. [`encode_with_alphabet`] puts the
+/// least significant digit first like the rest of the crate, so use
+/// [`encode_base58`](crate::encode_base58) for output that Bitcoin tools accept.
+///
+/// [`encode_with_alphabet`]: crate::encode_with_alphabet
+pub const BASE58_BITCOIN: Alphabet =
+    preset(b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+
+/// The alphabet of RFC 1924, which encodes IPv6 addresses in 20 characters: the digits,
+/// letters, `!#$%&()*+-;<=>?@^_`, `` ` ``, and `{|}~`.
+///
+/// Base 85, expanding data by 1.25 characters per byte. [`encode_ipv6`] and [`decode_ipv6`]
+/// use it in the fixed-width, most-significant-first form the RFC specifies.
+///
+/// [`encode_ipv6`]: crate::encode_ipv6
+/// [`decode_ipv6`]: crate::decode_ipv6
+pub const RFC1924: Alphabet = preset(
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~",
+);
+
+/// The standard alphabet without `<`, `>`, `&`, and `"`, so the output can be placed in XML
+/// and HTML text or double-quoted attributes without entity escaping.
+///
+/// Base 90, expanding data by 1.23 characters per byte.
+pub const XML_SAFE: Alphabet = standard_without(b"<>&\"");
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{decode_with_alphabet, encode_with_alphabet};
+
+    /// Checks that `alphabet` has `base` characters, none of which is in `forbidden`, and
+    /// that random data round-trips through it.
+    fn check(alphabet: &Alphabet, base: u8, forbidden: &[u8]) {
+        assert_eq!(alphabet.base(), base);
+        assert!(alphabet.chars().iter().all(|c| !forbidden.contains(c)));
+        for len in [0, 1, 16, 17, 100] {
+            let mut data = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            data.push(0);
+            let encoded = encode_with_alphabet(&data, alphabet);
+            assert!(encoded.bytes().all(|c| !forbidden.contains(&c)));
+            assert_eq!(decode_with_alphabet(&encoded, alphabet).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_json_safe() {
+        check(&JSON_SAFE, 92, b"\"\\");
+        let encoded = encode_with_alphabet(&[255; 64], &JSON_SAFE);
+        assert_eq!(
+            serde_json::to_string(&encoded).unwrap(),
+            format!("\"{}\"", encoded)
+        );
+    }
+
+    #[test]
+    fn test_shell_safe() {
+        check(&SHELL_SAFE, 88, b"'\"`$\\!");
+    }
+
+    #[test]
+    fn test_url_safe() {
+        check(&URL_SAFE, 71, b"\"#$%&+,/:;<=>?@[\\]^`{|} ");
+        assert!(URL_SAFE
+            .chars()
+            .iter()
+            .all(|&c| c.is_ascii_alphanumeric() || b"-._~!'()*".contains(&c)));
+    }
+
+    #[test]
+    fn test_base62() {
+        check(&BASE62, 62, b"-_+/=");
+        assert!(BASE62.chars().iter().all(u8::is_ascii_alphanumeric));
+    }
+
+    /// Integer IDs and byte strings in the layout of the `base-x` libraries and Base62 URL
+    /// shorteners: big-endian, most significant digit first, leading zero bytes as `0`.
+    #[test]
+    fn test_base62_vectors() {
+        use crate::{decode_big_endian_with_alphabet, encode_big_endian_with_alphabet};
+
+        for (id, encoded) in [
+            (1u64, "1"),
+            (61, "z"),
+            (62, "10"),
+            (3844, "100"),
+            (1234567890, "1LY7VK"),
+            (i64::MAX as u64, "AzL8n0Y58m7"),
+            (u64::MAX, "LygHa16AHYF"),
+        ] {
+            let bytes = id.to_be_bytes();
+            let minimal = &bytes[id.leading_zeros() as usize / 8..];
+            assert_eq!(encode_big_endian_with_alphabet(minimal, &BASE62), encoded);
+            assert_eq!(
+                decode_big_endian_with_alphabet(encoded, &BASE62).unwrap(),
+                minimal
+            );
+        }
+
+        for (data, encoded) in [
+            (&b""[..], ""),
+            (b"\0\0\x01", "001"),
+            (b"Hello World!", "T8dgcjRGkZ3aysdN"),
+            (
+                b"The quick brown fox jumps over the lazy dog",
+                "83UM8dOjD4xrzASgmqLOXTgTagvV1jPegUJ39mcYnwHwTlzpdfKXvpp4RL",
+            ),
+        ] {
+            assert_eq!(encode_big_endian_with_alphabet(data, &BASE62), encoded);
+            assert_eq!(
+                decode_big_endian_with_alphabet(encoded, &BASE62).unwrap(),
+                data
+            );
+        }
+    }
+
+    #[test]
+    fn test_base58_bitcoin() {
+        check(&BASE58_BITCOIN, 58, b"0OIl+/");
+    }
+
+    #[test]
+    fn test_rfc1924() {
+        check(&RFC1924, 85, b"\"',./:[\\]");
+    }
+
+    #[test]
+    fn test_xml_safe() {
+        check(&XML_SAFE, 90, b"<>&\"");
+    }
+}
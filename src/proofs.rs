@@ -0,0 +1,35 @@
+//! Bounded proofs of the round trip and of panic freedom, checked by Kani.
+//!
+//! These harnesses are only compiled by `cargo kani` and do not affect normal builds. Run
+//! them with:
+//!
+//! ```sh
+//! cargo install --locked kani-verifier
+//! cargo kani setup
+//! cargo kani --harness round_trip_up_to_3_bytes
+//! cargo kani --harness decode_4_chars_never_panics
+//! ```
+
+use crate::{decode_bytes, encode_to_bytes};
+
+/// Every input of up to 3 bytes round-trips in every valid base.
+#[kani::proof]
+#[kani::unwind(26)]
+fn round_trip_up_to_3_bytes() {
+    let bytes: [u8; 3] = kani::any();
+    let len: usize = kani::any_where(|&len| len <= bytes.len());
+    let base: u8 = kani::any_where(|&base| (2..=94).contains(&base));
+    let data = &bytes[..len];
+
+    let encoded = encode_to_bytes(data, base);
+    assert!(decode_bytes(&encoded, base).is_ok_and(|decoded| decoded == data));
+}
+
+/// Decoding any 4 bytes in any base, valid or not, returns instead of panicking.
+#[kani::proof]
+#[kani::unwind(6)]
+fn decode_4_chars_never_panics() {
+    let encoded: [u8; 4] = kani::any();
+    let base: u8 = kani::any();
+    let _ = decode_bytes(&encoded, base);
+}
@@ -0,0 +1,97 @@
+//! A Python extension module exposing [`encode`](crate::encode) and
+//! [`decode`](crate::decode), built with pyo3.
+//!
+//! `maturin build --release` builds it as the `base94` module using the settings in
+//! `pyproject.toml`:
+//!
+//! ```python
+//! import base94
+//!
+//! text = base94.encode(b"Hello")
+//! assert base94.decode(text) == b"Hello"
+//! assert base94.decode(base94.encode(b"Hello", base=16), base=16) == b"Hello"
+//! ```
+//!
+//! Invalid input raises `ValueError` with the Rust error message, which includes the
+//! position of the offending character. The exception also has `code` and `position`
+//! attributes holding the [`DecodeError::code`] and the [`DecodeError::position`], or
+//! `None`. Positions are byte offsets into the UTF-8 form of the string.
+//!
+//! Conversions of more than [`DETACH_LEN`] bytes or characters release the GIL, so other
+//! Python threads keep running while large payloads are converted.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::{decode_bytes, try_encode, DecodeError};
+
+/// Inputs longer than this are converted with the GIL released. Below it, releasing and
+/// reacquiring the GIL costs more than the conversion.
+pub const DETACH_LEN: usize = 64 * 1024;
+
+/// Runs `f`, releasing the GIL if the input is longer than [`DETACH_LEN`].
+fn convert<T: Send>(py: Python<'_>, len: usize, f: impl FnOnce() -> T + Send) -> T {
+    if len > DETACH_LEN {
+        py.detach(f)
+    } else {
+        f()
+    }
+}
+
+/// Converts `err` into a `ValueError` with `code` and `position` attributes.
+fn value_error(py: Python<'_>, err: &DecodeError) -> PyErr {
+    let error = PyValueError::new_err(err.to_string());
+    let value = error.value(py);
+    // Setting an attribute on a fresh exception instance cannot fail.
+    let _ = value.setattr("code", err.code());
+    let _ = value.setattr("position", err.position());
+    error
+}
+
+/// Encodes `data` in `base`.
+///
+/// # Arguments
+///
+/// * `data` - The bytes to encode.
+/// * `base` - The base used for encoding, 94 if omitted. Must be between 2 and 94
+///   (inclusive).
+///
+/// # Returns
+///
+/// The encoded string. Raises `ValueError` if the base is outside the valid range.
+#[pyfunction]
+#[pyo3(signature = (data, base = 94))]
+fn encode(py: Python<'_>, data: &[u8], base: u8) -> PyResult<String> {
+    convert(py, data.len(), || try_encode(data, base))
+        .map_err(|_| value_error(py, &DecodeError::InvalidBase { base }))
+}
+
+/// Decodes `encoded` in `base`.
+///
+/// # Arguments
+///
+/// * `encoded` - The encoded string.
+/// * `base` - The base used for encoding, 94 if omitted. Must be between 2 and 94
+///   (inclusive).
+///
+/// # Returns
+///
+/// The decoded bytes. Raises `ValueError` if the string is not valid in the base or the
+/// base is outside the valid range.
+#[pyfunction]
+#[pyo3(signature = (encoded, base = 94))]
+fn decode<'py>(py: Python<'py>, encoded: &str, base: u8) -> PyResult<Bound<'py, PyBytes>> {
+    let decoded = convert(py, encoded.len(), || decode_bytes(encoded.as_bytes(), base))
+        .map_err(|err| value_error(py, &err))?;
+    Ok(PyBytes::new(py, &decoded))
+}
+
+/// The `base94` Python module.
+#[pymodule(name = "base94")]
+fn base94_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(encode, m)?)?;
+    m.add_function(wrap_pyfunction!(decode, m)?)?;
+    m.add("DETACH_LEN", DETACH_LEN)?;
+    Ok(())
+}
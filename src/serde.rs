@@ -0,0 +1,287 @@
+//! Serde helpers that store byte fields as encoded strings.
+//!
+//! Use this module with `#[serde(with = "base94::serde")]` on a `Vec<u8>` field to store it
+//! in base 94, or [`Base`] to pick another base:
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Blob {
+//!     #[serde(with = "base94::serde")]
+//!     data: Vec<u8>,
+//!     #[serde(with = "base94::serde::Base::<16>")]
+//!     checksum: Vec<u8>,
+//! }
+//!
+//! let blob = Blob { data: b"Hello, World!".to_vec(), checksum: vec![0xab, 0xcd] };
+//! let json = serde_json::to_string(&blob).unwrap();
+//! assert_eq!(serde_json::from_str::<Blob>(&json).unwrap(), blob);
+//! ```
+//!
+//! The bytes are always serialized as a string, including in formats that are not human
+//! readable such as bincode, so a field reads the same way in every format. Formats that
+//! store raw bytes more compactly than text are better served by `serde_bytes`.
+//!
+//! With the `serde_with` feature, [`Base94`](crate::Base94) provides the same conversion as
+//! a `serde_with` adapter.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use ::serde::de::{self, Visitor};
+use ::serde::{Deserializer, Serializer};
+
+use crate::{decode, encode};
+
+/// Serializes `bytes` as a base 94 string.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: AsRef<[u8]> + ?Sized,
+    S: Serializer,
+{
+    Base::<94>::serialize(bytes, serializer)
+}
+
+/// Deserializes bytes from a base 94 string.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Base::<94>::deserialize(deserializer)
+}
+
+/// The base 94 helpers, for `#[serde(with = "base94::serde::standard")]`.
+pub mod standard {
+    pub use super::{deserialize, serialize};
+}
+
+/// Serde helpers for the base `BASE`, for `#[serde(with = "base94::serde::Base::<BASE>")]`.
+///
+/// An invalid `BASE` (outside 2 to 94) is a compile error.
+pub struct Base<const BASE: u8>;
+
+impl<const BASE: u8> Base<BASE> {
+    /// Evaluated for every `BASE` that is instantiated, failing compilation if it is invalid.
+    const VALID_BASE: () = assert!(BASE >= 2 && BASE <= 94, "Base must be in the range 2..=94");
+
+    /// Serializes `bytes` as a string in `BASE`.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]> + ?Sized,
+        S: Serializer,
+    {
+        let () = Self::VALID_BASE;
+        serializer.serialize_str(&encode(bytes.as_ref(), BASE))
+    }
+
+    /// Deserializes bytes from a string in `BASE`. Decode errors, including the position of
+    /// an invalid character, are reported through `serde::de::Error::custom`.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let () = Self::VALID_BASE;
+        deserializer.deserialize_str(EncodedVisitor::<BASE>)
+    }
+}
+
+/// Decodes borrowed and owned strings without copying them first.
+struct EncodedVisitor<const BASE: u8>;
+
+impl<const BASE: u8> Visitor<'_> for EncodedVisitor<BASE> {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "a base {} encoded string", BASE)
+    }
+
+    fn visit_str<E: de::Error>(self, encoded: &str) -> Result<Vec<u8>, E> {
+        decode(encoded, BASE).map_err(E::custom)
+    }
+
+    fn visit_string<E: de::Error>(self, encoded: String) -> Result<Vec<u8>, E> {
+        self.visit_str(&encoded)
+    }
+}
+
+/// A `serde_with` adapter that stores bytes as a string in `BASE`, for
+/// `#[serde_as(as = "Base94")]` or `#[serde_as(as = "Base94<BASE>")]`.
+///
+/// It converts `Vec<u8>` and `[u8; N]`, and nests inside other adapters such as
+/// `Option<Base94>` and `Vec<Base94>`. Like [`Base`], it always writes a string. An invalid
+/// `BASE` (outside 2 to 94) is a compile error.
+///
+/// # Examples
+///
+/// ```
+/// use base94::Base94;
+/// use serde::{Deserialize, Serialize};
+/// use serde_with::serde_as;
+///
+/// #[serde_as]
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct Keys {
+///     #[serde_as(as = "Base94")]
+///     public: [u8; 4],
+///     #[serde_as(as = "Option<Base94<16>>")]
+///     backup: Option<Vec<u8>>,
+/// }
+///
+/// let keys = Keys { public: [1, 2, 3, 4], backup: None };
+/// let json = serde_json::to_string(&keys).unwrap();
+/// assert_eq!(serde_json::from_str::<Keys>(&json).unwrap(), keys);
+/// ```
+#[cfg(feature = "serde_with")]
+pub struct Base94<const BASE: u8 = 94>;
+
+#[cfg(feature = "serde_with")]
+impl<const BASE: u8> serde_with::SerializeAs<Vec<u8>> for Base94<BASE> {
+    fn serialize_as<S: Serializer>(source: &Vec<u8>, serializer: S) -> Result<S::Ok, S::Error> {
+        Base::<BASE>::serialize(source, serializer)
+    }
+}
+
+#[cfg(feature = "serde_with")]
+impl<const BASE: u8, const N: usize> serde_with::SerializeAs<[u8; N]> for Base94<BASE> {
+    fn serialize_as<S: Serializer>(source: &[u8; N], serializer: S) -> Result<S::Ok, S::Error> {
+        Base::<BASE>::serialize(source, serializer)
+    }
+}
+
+#[cfg(feature = "serde_with")]
+impl<'de, const BASE: u8> serde_with::DeserializeAs<'de, Vec<u8>> for Base94<BASE> {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        Base::<BASE>::deserialize(deserializer)
+    }
+}
+
+/// Fails with `DecodeError::LengthMismatch` if the string does not decode to exactly `N`
+/// bytes.
+#[cfg(feature = "serde_with")]
+impl<'de, const BASE: u8, const N: usize> serde_with::DeserializeAs<'de, [u8; N]> for Base94<BASE> {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<[u8; N], D::Error> {
+        let () = Base::<BASE>::VALID_BASE;
+        deserializer.deserialize_str(ArrayVisitor::<BASE, N>)
+    }
+}
+
+/// Decodes strings into arrays of exactly `N` bytes.
+#[cfg(feature = "serde_with")]
+struct ArrayVisitor<const BASE: u8, const N: usize>;
+
+#[cfg(feature = "serde_with")]
+impl<const BASE: u8, const N: usize> Visitor<'_> for ArrayVisitor<BASE, N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "a base {} encoded string of {} bytes", BASE, N)
+    }
+
+    fn visit_str<E: de::Error>(self, encoded: &str) -> Result<[u8; N], E> {
+        crate::decode_exact(encoded, BASE).map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Record {
+        #[serde(with = "crate::serde")]
+        data: Vec<u8>,
+        #[serde(with = "crate::serde::standard")]
+        key: Vec<u8>,
+        #[serde(with = "crate::serde::Base::<10>")]
+        digits: Vec<u8>,
+    }
+
+    fn random_record() -> Record {
+        let random = |len| (0..len).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+        Record {
+            data: random(100),
+            key: [random(16), vec![0; 3]].concat(),
+            digits: random(20),
+        }
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let record = random_record();
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["data"], crate::encode(&record.data, 94));
+        assert_eq!(json["digits"], crate::encode(&record.digits, 10));
+        assert_eq!(serde_json::from_value::<Record>(json).unwrap(), record);
+
+        let text = serde_json::to_string(&record).unwrap();
+        assert_eq!(serde_json::from_str::<Record>(&text).unwrap(), record);
+    }
+
+    #[test]
+    fn test_bincode_round_trip() {
+        let record = random_record();
+        let bytes = bincode::serialize(&record).unwrap();
+        assert_eq!(bincode::deserialize::<Record>(&bytes).unwrap(), record);
+    }
+
+    #[test]
+    fn test_error_includes_position() {
+        let error = serde_json::from_str::<Record>(r#"{"data":"ab\u007f","key":"","digits":""}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("position 2"), "{}", error);
+
+        let error = serde_json::from_str::<Record>(r#"{"data":"","key":"","digits":"!!A"}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("position 2"), "{}", error);
+    }
+
+    #[cfg(feature = "serde_with")]
+    #[test]
+    fn test_serde_with_adapter() {
+        use crate::Base94;
+        use serde_with::serde_as;
+
+        #[serde_as]
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Keys {
+            #[serde_as(as = "Base94")]
+            key: [u8; 32],
+            #[serde_as(as = "Option<Base94>")]
+            present: Option<Vec<u8>>,
+            #[serde_as(as = "Option<Base94>")]
+            absent: Option<Vec<u8>>,
+            #[serde_as(as = "Vec<Base94<50>>")]
+            chunks: Vec<Vec<u8>>,
+        }
+
+        let mut key = rand::random::<[u8; 32]>();
+        key[31] = 0;
+        let keys = Keys {
+            key,
+            present: Some(b"Hello, World!".to_vec()),
+            absent: None,
+            chunks: vec![vec![], vec![0], b"abc".to_vec()],
+        };
+        let json = serde_json::to_value(&keys).unwrap();
+        assert_eq!(json["key"], crate::encode(&key, 94));
+        assert_eq!(json["present"], crate::encode(b"Hello, World!", 94));
+        assert!(json["absent"].is_null());
+        assert_eq!(json["chunks"][2], crate::encode(b"abc", 50));
+        assert_eq!(serde_json::from_value::<Keys>(json).unwrap(), keys);
+
+        let mut json = serde_json::to_value(&keys).unwrap();
+        json["key"] = crate::encode(&[1; 31], 94).into();
+        let error = serde_json::from_value::<Keys>(json)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("Expected 32 decoded bytes, got 31"),
+            "{}",
+            error
+        );
+    }
+}
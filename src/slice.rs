@@ -0,0 +1,275 @@
+//! Encoding and decoding into caller-provided fixed-size buffers.
+//!
+//! These functions never allocate their output, which suits embedded and FFI callers that
+//! own a fixed `&mut [u8]`. The big-number arithmetic itself still uses heap scratch space.
+
+use alloc::vec::Vec;
+
+use thiserror::Error;
+
+use crate::{decode_digits, encode_digits, is_valid_base, parse_digits, DecodeError, CHARACTERS};
+
+/// Errors that can occur while encoding into a slice.
+#[derive(Error, Debug)]
+pub enum EncodeSliceError {
+    #[error("Invalid base {base}, must be in the range 2..=94")]
+    InvalidBase { base: u8 },
+    #[error("Output buffer too small: {needed} bytes needed, {available} available")]
+    OutputTooSmall { needed: usize, available: usize },
+}
+
+/// Encodes a slice of bytes into a caller-provided buffer of ASCII characters.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+/// * `out` - The buffer that receives the encoded characters. Use
+///   [`encoded_len_upper_bound`](crate::encoded_len_upper_bound) to size it.
+///
+/// # Returns
+///
+/// The number of characters written to the start of `out`, or an `EncodeSliceError` if the
+/// base is invalid or `out` is too small. On error, `out` is left unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode, encode_to_slice, encoded_len_upper_bound};
+///
+/// let data = b"Hello, World!";
+/// let mut buffer = vec![0; encoded_len_upper_bound(data.len(), 94)];
+/// let written = encode_to_slice(data, 94, &mut buffer).unwrap();
+/// assert_eq!(&buffer[..written], encode(data, 94).as_bytes());
+/// ```
+pub fn encode_to_slice(data: &[u8], base: u8, out: &mut [u8]) -> Result<usize, EncodeSliceError> {
+    if !is_valid_base(base) {
+        return Err(EncodeSliceError::InvalidBase { base });
+    }
+
+    let digits = encode_digits(data, base);
+    let needed = digits.len();
+    if needed > out.len() {
+        return Err(EncodeSliceError::OutputTooSmall {
+            needed,
+            available: out.len(),
+        });
+    }
+
+    for (slot, &digit) in out.iter_mut().zip(&digits) {
+        *slot = CHARACTERS[digit as usize];
+    }
+    Ok(needed)
+}
+
+/// Decodes a Base94-encoded string into a caller-provided buffer.
+///
+/// # Arguments
+///
+/// * `encoded` - A Base94-encoded string to be decoded.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+/// * `out` - The buffer that receives the decoded bytes. A buffer of `encoded.len()` bytes
+///   is always large enough.
+///
+/// # Returns
+///
+/// The number of bytes written to the start of `out`, or a `DecodeError` under the same
+/// conditions as [`decode`](crate::decode), plus `DecodeError::OutputTooSmall` if the decoded
+/// data does not fit. On error, `out` is left unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_to_slice, encode};
+///
+/// let encoded = encode(b"Hello, World!", 94);
+/// let mut buffer = [0; 32];
+/// let written = decode_to_slice(&encoded, 94, &mut buffer).unwrap();
+/// assert_eq!(&buffer[..written], b"Hello, World!");
+/// ```
+pub fn decode_to_slice(encoded: &str, base: u8, out: &mut [u8]) -> Result<usize, DecodeError> {
+    let bytes = decode_digits(&parse_digits(encoded.as_bytes(), base)?, base);
+    let needed = bytes.len();
+    if needed > out.len() {
+        return Err(DecodeError::OutputTooSmall {
+            needed,
+            available: out.len(),
+        });
+    }
+
+    out[..needed].copy_from_slice(&bytes);
+    Ok(needed)
+}
+
+/// Decodes a Base94-encoded string into an array of exactly `N` bytes, e.g. a key or hash of
+/// known length.
+///
+/// Because encoding preserves length, the encoding of any `[u8; N]`, including ones with
+/// leading or trailing zero bytes, decodes back to it.
+///
+/// # Arguments
+///
+/// * `encoded` - A Base94-encoded string to be decoded.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+///
+/// # Returns
+///
+/// The decoded array, or a `DecodeError` under the same conditions as
+/// [`decode`](crate::decode), plus `DecodeError::LengthMismatch` if the string decodes to a
+/// different number of bytes.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_exact, encode, DecodeError};
+///
+/// let key = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 0];
+/// let encoded = encode(&key, 94);
+/// assert_eq!(decode_exact::<16>(&encoded, 94).unwrap(), key);
+/// assert!(matches!(
+///     decode_exact::<32>(&encoded, 94),
+///     Err(DecodeError::LengthMismatch { expected: 32, actual: 16 })
+/// ));
+/// ```
+pub fn decode_exact<const N: usize>(encoded: &str, base: u8) -> Result<[u8; N], DecodeError> {
+    let bytes = decode_digits(&parse_digits(encoded.as_bytes(), base)?, base);
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| DecodeError::LengthMismatch {
+            expected: N,
+            actual: bytes.len(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_encode_to_slice_exact_fit() {
+        for base in 2..=MAX_BASE {
+            for data in [&b"Hello, World!"[..], &[255; 16], &[1, 0, 0]] {
+                let encoded = encode(data, base);
+                let mut buffer = vec![0; encoded.len()];
+                assert_eq!(
+                    encode_to_slice(data, base, &mut buffer).unwrap(),
+                    encoded.len()
+                );
+                assert_eq!(buffer, encoded.as_bytes());
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_to_slice_too_small() {
+        for base in 2..=MAX_BASE {
+            let needed = encode(b"Hello, World!", base).len();
+            let mut buffer = vec![0; needed - 1];
+            assert!(matches!(
+                encode_to_slice(b"Hello, World!", base, &mut buffer),
+                Err(EncodeSliceError::OutputTooSmall { needed: n, available }) if n == needed && available == needed - 1
+            ));
+            assert!(buffer.iter().all(|&b| b == 0));
+        }
+    }
+
+    #[test]
+    fn test_encode_to_slice_empty() {
+        let mut buffer = [];
+        assert_eq!(encode_to_slice(&[], 94, &mut buffer).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_encode_to_slice_invalid_base() {
+        assert!(matches!(
+            encode_to_slice(b"abc", 95, &mut [0; 8]),
+            Err(EncodeSliceError::InvalidBase { base: 95 })
+        ));
+    }
+
+    #[test]
+    fn test_decode_to_slice_exact_fit() {
+        for base in 2..=MAX_BASE {
+            for data in [&b"Hello, World!"[..], &[255; 16], &[1, 0, 0]] {
+                let encoded = encode(data, base);
+                let mut buffer = vec![0; data.len()];
+                assert_eq!(
+                    decode_to_slice(&encoded, base, &mut buffer).unwrap(),
+                    data.len()
+                );
+                assert_eq!(buffer, data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_to_slice_too_small() {
+        for base in 2..=MAX_BASE {
+            let encoded = encode(b"Hello, World!", base);
+            let mut buffer = [0; 12];
+            assert!(matches!(
+                decode_to_slice(&encoded, base, &mut buffer),
+                Err(DecodeError::OutputTooSmall {
+                    needed: 13,
+                    available: 12
+                })
+            ));
+            assert_eq!(buffer, [0; 12]);
+        }
+    }
+
+    #[test]
+    fn test_decode_to_slice_empty() {
+        let mut buffer = [];
+        assert_eq!(decode_to_slice("", 94, &mut buffer).unwrap(), 0);
+        assert_eq!(decode("", 94).unwrap(), buffer);
+    }
+
+    #[test]
+    fn test_decode_exact_round_trip() {
+        for base in 2..=MAX_BASE {
+            let mut key = rand::random::<[u8; 16]>();
+            for (first, last) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+                key[0] = first;
+                key[15] = last;
+                assert_eq!(decode_exact::<16>(&encode(&key, base), base).unwrap(), key);
+            }
+
+            let mut hash = rand::random::<[u8; 32]>();
+            hash[..4].fill(0);
+            hash[28..].fill(0);
+            assert_eq!(
+                decode_exact::<32>(&encode(&hash, base), base).unwrap(),
+                hash
+            );
+            assert_eq!(
+                decode_exact::<32>(&encode(&[0; 32], base), base).unwrap(),
+                [0; 32]
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_exact_length_mismatch() {
+        for base in 2..=MAX_BASE {
+            let encoded = encode(&[7; 16], base);
+            assert!(matches!(
+                decode_exact::<15>(&encoded, base),
+                Err(DecodeError::LengthMismatch {
+                    expected: 15,
+                    actual: 16
+                })
+            ));
+            assert!(matches!(
+                decode_exact::<32>(&encoded, base),
+                Err(DecodeError::LengthMismatch {
+                    expected: 32,
+                    actual: 16
+                })
+            ));
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! Rendering decode errors with the part of the input they point at.
+
+use core::fmt;
+
+use crate::DecodeError;
+
+/// How many characters of the line to show on each side of the error.
+const CONTEXT: usize = 30;
+
+impl DecodeError {
+    /// Returns a [`Display`](fmt::Display) value that renders the error together with the
+    /// line of `input` it points at, a caret under the offending character, and its line
+    /// and column.
+    ///
+    /// Lines longer than the window shown around the error are cut, with `...` marking the
+    /// cut. Control characters are shown as U+FFFD so they cannot break up the snippet.
+    /// Errors without a position render like their [`Display`](fmt::Display) output.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The text that was decoded, which the error's byte offset refers to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base94::decode;
+    ///
+    /// let input = "A@#D9\ne@D 9n";
+    /// let error = decode(input, 94).unwrap_err();
+    /// assert_eq!(
+    ///     error.display_with_source(input).to_string(),
+    ///     "Invalid character '\\n' at position 5 (line 1, column 6)\n  A@#D9\u{fffd}\n       ^"
+    /// );
+    /// ```
+    pub fn display_with_source<'a>(&'a self, input: &'a str) -> WithSource<'a> {
+        self.display_with_window(input, 0, 0, 0)
+    }
+
+    /// Like [`display_with_source`](Self::display_with_source), for text too long to keep
+    /// whole, such as a stream: `input` is only the part of the text around the error.
+    ///
+    /// The line and column are those in the whole text. Errors that point before `input`
+    /// render like their [`Display`](fmt::Display) output.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The part of the decoded text that contains the error.
+    /// * `offset` - The byte offset in the whole text where `input` starts, which the error's
+    ///   byte offset counts from as well.
+    /// * `line` - The number of line breaks in the text before `input`.
+    /// * `column` - The number of characters between the last of those line breaks, or the
+    ///   start of the text, and `input`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base94::DecodeError;
+    ///
+    /// let error = DecodeError::InvalidCharacter { c: ' ', position: 1002 };
+    /// assert_eq!(
+    ///     error.display_with_window("!! !", 1000, 4, 60).to_string(),
+    ///     "Invalid character ' ' at position 1002 (line 5, column 63)\n  ...!! !\n       ^"
+    /// );
+    /// ```
+    pub fn display_with_window<'a>(
+        &'a self,
+        input: &'a str,
+        offset: usize,
+        line: usize,
+        column: usize,
+    ) -> WithSource<'a> {
+        WithSource {
+            error: self,
+            input,
+            offset,
+            line,
+            column,
+        }
+    }
+}
+
+/// A decode error rendered with the input it refers to, returned by
+/// [`DecodeError::display_with_source`].
+#[derive(Debug, Clone, Copy)]
+pub struct WithSource<'a> {
+    error: &'a DecodeError,
+    input: &'a str,
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl fmt::Display for WithSource<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        let Some(mut position) = self
+            .error
+            .position()
+            .and_then(|position| position.checked_sub(self.offset))
+        else {
+            return Ok(());
+        };
+        let input = self.input;
+        position = position.min(input.len());
+        while !input.is_char_boundary(position) {
+            position -= 1;
+        }
+
+        let line_start = input[..position].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[position..]
+            .find('\n')
+            .map_or(input.len(), |i| position + i);
+        let line_number = self.line + input[..line_start].matches('\n').count() + 1;
+        // The number of characters of the line that come before `input`.
+        let first = if line_start == 0 { self.column } else { 0 };
+        let column = first + input[line_start..position].chars().count();
+        write!(f, " (line {}, column {})", line_number, column + 1)?;
+
+        // A newline the error points at is shown as the last character of its line.
+        let line = if position == line_end && line_end < input.len() {
+            &input[line_start..=line_end]
+        } else {
+            &input[line_start..line_end]
+        };
+        let skip = column.saturating_sub(CONTEXT).max(first);
+        let width = first + line.chars().count();
+        let cut = if skip > 0 { "..." } else { "" };
+        f.write_str("\n  ")?;
+        f.write_str(cut)?;
+        for c in line.chars().skip(skip - first).take(2 * CONTEXT + 1) {
+            let c = if c.is_control() {
+                char::REPLACEMENT_CHARACTER
+            } else {
+                c
+            };
+            write!(f, "{}", c)?;
+        }
+        if width > skip + 2 * CONTEXT + 1 {
+            f.write_str("...")?;
+        }
+        write!(f, "\n  {:1$}^", "", cut.len() + column - skip)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+    use alloc::string::ToString;
+
+    fn render(input: &str, base: u8) -> alloc::string::String {
+        decode(input, base)
+            .unwrap_err()
+            .display_with_source(input)
+            .to_string()
+    }
+
+    #[test]
+    fn test_error_at_start() {
+        assert_eq!(
+            render(" !!", 94),
+            "Invalid character ' ' at position 0 (line 1, column 1)\n  \u{20}!!\n  ^"
+        );
+    }
+
+    #[test]
+    fn test_error_at_end() {
+        assert_eq!(
+            render("!!\u{e9}", 94),
+            "Invalid character '\u{e9}' at position 2 (line 1, column 3)\n  !!\u{e9}\n    ^"
+        );
+        let error = DecodeError::InvalidPadding { position: 3 };
+        assert_eq!(
+            error.display_with_source("!!!").to_string(),
+            "Invalid padding at position 3 (line 1, column 4)\n  !!!\n     ^"
+        );
+    }
+
+    #[test]
+    fn test_error_on_later_line() {
+        // `decode` stops at the first newline, but decoders that skip whitespace do not.
+        let input = "!!!!\n!!\r\n!~ !\n!!";
+        let error = crate::decode_ignoring_whitespace(input, 94).unwrap_err();
+        assert_eq!(
+            error.display_with_source(input).to_string(),
+            "Invalid character ' ' at position 11 (line 3, column 3)\n  !~ !\n    ^"
+        );
+        let error = DecodeError::InvalidCharacter {
+            c: '\r',
+            position: 7,
+        };
+        assert_eq!(
+            error.display_with_source(input).to_string(),
+            "Invalid character '\\r' at position 7 (line 2, column 3)\n  !!\u{fffd}\n    ^"
+        );
+
+        let input = "!!!!\n!!\n";
+        assert_eq!(
+            render(input, 94),
+            "Invalid character '\\n' at position 4 (line 1, column 5)\n  !!!!\u{fffd}\n      ^"
+        );
+    }
+
+    #[test]
+    fn test_long_line_is_windowed() {
+        let mut input = encode(&[0xab; 500], 94);
+        input.insert(300, ' ');
+        let rendered = render(&input, 94);
+        let lines = rendered.lines().collect::<alloc::vec::Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "Invalid character ' ' at position 300 (line 1, column 301)"
+        );
+        assert_eq!(lines[1], alloc::format!("  ...{}...", &input[270..331]));
+        assert_eq!(lines[2], alloc::format!("  {:33}^", ""));
+
+        // Only the side that is too long is cut.
+        let rendered = render(&input[..310], 94);
+        assert!(rendered.ends_with(&alloc::format!("  ...{}\n  {:33}^", &input[270..310], "")));
+    }
+
+    #[test]
+    fn test_multibyte_columns() {
+        assert_eq!(render("\u{e9}\u{e9}", 94).lines().nth(2), Some("  ^"));
+        let input = "\u{2192}\u{2192}\n!\u{2192}";
+        let error = DecodeError::InvalidCharacter {
+            c: '\u{2192}',
+            position: 8,
+        };
+        assert_eq!(
+            error.display_with_source(input).to_string(),
+            "Invalid character '\u{2192}' at position 8 (line 2, column 2)\n  !\u{2192}\n   ^"
+        );
+    }
+
+    #[test]
+    fn test_window_of_longer_text() {
+        let text = encode(&[0xab; 500], 94);
+        let mut input = alloc::format!("{}\n{}", text, text);
+        input.insert(text.len() + 301, ' ');
+        let error = crate::decode_ignoring_whitespace(&input, 94).unwrap_err();
+        let whole = error.display_with_source(&input).to_string();
+
+        // Any window that holds the error's line from well before it renders the same.
+        for start in [text.len() + 1, text.len() + 200, text.len() + 270] {
+            let column = start - text.len() - 1;
+            assert_eq!(
+                error
+                    .display_with_window(&input[start..], start, 1, column)
+                    .to_string(),
+                whole
+            );
+        }
+
+        // A window that starts after the first character shown is cut there.
+        let start = text.len() + 290;
+        let rendered = error
+            .display_with_window(&input[start..start + 20], start, 1, 289)
+            .to_string();
+        let lines = rendered.lines().collect::<alloc::vec::Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "Invalid character ' ' at position 912 (line 2, column 301)"
+        );
+        assert_eq!(
+            lines[1],
+            alloc::format!("  ...{}", &input[start..start + 20])
+        );
+        assert_eq!(lines[2], alloc::format!("  {:14}^", ""));
+
+        let error = DecodeError::InvalidCharacter {
+            c: ' ',
+            position: 3,
+        };
+        assert_eq!(
+            error.display_with_window("!!", 5, 0, 5).to_string(),
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_errors_without_position() {
+        let error = DecodeError::InvalidBase { base: 95 };
+        assert_eq!(
+            error.display_with_source("!!").to_string(),
+            error.to_string()
+        );
+    }
+}
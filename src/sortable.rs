@@ -0,0 +1,188 @@
+//! An encoding whose strings sort in the same order as the data, for database keys.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::len::digit_bits;
+use crate::{
+    encoded_len, from_digits, is_valid_base, parse_digits, to_digits, trailing_zeros, wipe,
+    DecodeError, EncodeError, CHARACTERS,
+};
+
+/// Encodes `data` as a big-endian number, most significant digit first, padded with zero
+/// digits to [`encoded_len(data.len(), base)`](crate::encoded_len) characters.
+///
+/// Because the standard alphabet is in ascending ASCII order and every input of the same
+/// length gets the same width, byte-wise comparison of the output matches comparison of the
+/// input: for `a` and `b` of equal length, `a < b` if and only if
+/// `encode_sortable(a, base) < encode_sortable(b, base)`. This makes the output usable as
+/// keys in ordered stores such as RocksDB or FoundationDB. Inputs of different lengths have
+/// different widths and are not ordered against each other; length-prefix or pad keys
+/// that need to be.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_sortable, encode_sortable};
+///
+/// let low = encode_sortable(&1000u32.to_be_bytes(), 94);
+/// let high = encode_sortable(&2000u32.to_be_bytes(), 94);
+/// assert!(low < high);
+/// assert_eq!(low.len(), high.len());
+/// assert_eq!(decode_sortable(&low, 94).unwrap(), 1000u32.to_be_bytes());
+/// ```
+pub fn encode_sortable(data: &[u8], base: u8) -> String {
+    if !is_valid_base(base) {
+        panic!("{}", EncodeError::InvalidBase { base });
+    }
+
+    let mut little_endian = data.iter().rev().copied().collect::<Vec<u8>>();
+    let significant = little_endian.len() - trailing_zeros(&little_endian, 0);
+    let mut digits = to_digits(&little_endian[..significant], base);
+    wipe(&mut little_endian);
+    digits.resize(encoded_len(data.len(), base), 0);
+
+    let encoded = digits
+        .iter()
+        .rev()
+        .map(|&digit| CHARACTERS[digit as usize] as char)
+        .collect();
+    wipe(&mut digits);
+    encoded
+}
+
+/// Decodes a string produced by [`encode_sortable`].
+///
+/// The length of the data follows from the length of `encoded`, since every byte length
+/// has its own width.
+///
+/// # Arguments
+///
+/// * `encoded` - A string produced by [`encode_sortable`].
+/// * `base` - The base used for encoding. Must match the base used for encoding.
+///
+/// # Returns
+///
+/// The decoded bytes, or a `DecodeError` if:
+/// - `encoded` is invalid in `base`, under the same conditions as [`decode`](crate::decode).
+/// - No byte length encodes to as many characters as `encoded` has, with
+///   `DecodeError::InvalidLength`.
+/// - The value does not fit in that many bytes, with `DecodeError::Overflow`.
+pub fn decode_sortable(encoded: &str, base: u8) -> Result<Vec<u8>, DecodeError> {
+    let mut digits = parse_digits(encoded.as_bytes(), base)?;
+
+    // A byte takes at least one digit in every base below 256, so the length is at most
+    // the width, and this estimate is never below it.
+    let mut len = (digit_bits(digits.len(), base) / 8 + 1).min(digits.len() as u128) as usize;
+    while encoded_len(len, base) > digits.len() {
+        len -= 1;
+    }
+    if encoded_len(len, base) != digits.len() {
+        wipe(&mut digits);
+        return Err(DecodeError::InvalidLength {
+            length: encoded.len(),
+        });
+    }
+
+    digits.reverse();
+    let significant = digits.len() - trailing_zeros(&digits, 0);
+    let mut bytes = from_digits(&digits[..significant], base);
+    wipe(&mut digits);
+    if bytes.len() > len {
+        wipe(&mut bytes);
+        return Err(DecodeError::Overflow {
+            bits: len as u32 * 8,
+        });
+    }
+    bytes.resize(len, 0);
+    bytes.reverse();
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_BASE: u8 = 94;
+
+    fn random(len: usize) -> Vec<u8> {
+        (0..len).map(|_| rand::random::<u8>()).collect()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for base in 2..=MAX_BASE {
+            for len in [0, 1, 2, 15, 16, 17, 100] {
+                for data in [random(len), vec![0; len], vec![255; len]] {
+                    let encoded = encode_sortable(&data, base);
+                    assert_eq!(encoded.len(), encoded_len(len, base));
+                    assert_eq!(decode_sortable(&encoded, base).unwrap(), data);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_order_matches_data() {
+        for base in 2..=MAX_BASE {
+            for len in [1, 4, 16, 33] {
+                for _ in 0..20 {
+                    let (a, mut b) = (random(len), random(len));
+                    if rand::random::<bool>() {
+                        // Share a prefix so that later bytes decide the order.
+                        b[..len / 2].copy_from_slice(&a[..len / 2]);
+                    }
+                    let (x, y) = (encode_sortable(&a, base), encode_sortable(&b, base));
+                    assert_eq!(a.cmp(&b), x.cmp(&y), "{:?} {:?} in base {}", a, b, base);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_adjacent_values() {
+        for base in 2..=MAX_BASE {
+            let keys = (0..=1000u16)
+                .map(|n| encode_sortable(&n.to_be_bytes(), base))
+                .collect::<Vec<String>>();
+            assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        for base in 2..=MAX_BASE {
+            let widths = (0..40)
+                .map(|len| encoded_len(len, base))
+                .collect::<Vec<_>>();
+            for width in 0..widths[39] {
+                let result = decode_sortable(&"!".repeat(width), base);
+                if widths.contains(&width) {
+                    assert!(result.is_ok());
+                } else {
+                    assert!(matches!(result, Err(DecodeError::InvalidLength { .. })));
+                }
+            }
+        }
+        assert!(matches!(
+            decode_sortable("~~~", 94),
+            Err(DecodeError::Overflow { bits: 16 })
+        ));
+        assert!(matches!(
+            decode_sortable("!! ", 94),
+            Err(DecodeError::InvalidCharacter {
+                c: ' ',
+                position: 2
+            })
+        ));
+    }
+}
@@ -0,0 +1,484 @@
+//! Incremental encoding and decoding of data that arrives in pieces.
+//!
+//! The streaming types produce the chunked block format of
+//! [`encode_chunked`](crate::encode_chunked) with a chunk size of [`STREAM_CHUNK_SIZE`] bytes
+//! unless another one is chosen. Each block is written out as soon as it is complete, so only
+//! a partial block is kept in memory. This output is not compatible with the whole-number
+//! format of [`encode`](crate::encode).
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::chunked::{block_width, decode_blocks, encode_blocks, layout};
+use crate::{digit_value, is_valid_base, DecodeError};
+
+/// The chunk size used by [`Encoder::new`] and [`Decoder::new`].
+pub const STREAM_CHUNK_SIZE: usize = 32;
+
+/// Encodes a byte stream piece by piece.
+///
+/// The concatenation of everything returned by [`push`](Self::push) followed by the result of
+/// [`finalize`](Self::finalize) equals `encode_chunked(&data, base, chunk_size)` for the whole
+/// stream, no matter how the data was split between pushes.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_chunked, Encoder, STREAM_CHUNK_SIZE};
+///
+/// let mut encoder = Encoder::new(94);
+/// let mut encoded = String::new();
+/// for piece in [&b"Hello, "[..], b"streaming ", b"World!"] {
+///     encoded.push_str(encoder.push(piece));
+/// }
+/// encoded.push_str(&encoder.finalize());
+///
+/// let decoded = decode_chunked(&encoded, 94, STREAM_CHUNK_SIZE).unwrap();
+/// assert_eq!(decoded, b"Hello, streaming World!");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Encoder {
+    base: u8,
+    chunk_size: usize,
+    full_width: usize,
+    /// Bytes of the current, incomplete block.
+    pending: Vec<u8>,
+    /// Text produced by the latest call to `push`.
+    output: String,
+}
+
+impl Encoder {
+    /// Creates an encoder that uses [`STREAM_CHUNK_SIZE`] byte blocks.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the specified base is outside the valid range (2 to 94).
+    pub fn new(base: u8) -> Self {
+        Self::with_chunk_size(base, STREAM_CHUNK_SIZE)
+    }
+
+    /// Creates an encoder that uses blocks of `chunk_size` bytes.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the specified base is outside the valid range (2 to 94) or if
+    /// `chunk_size` is zero.
+    pub fn with_chunk_size(base: u8, chunk_size: usize) -> Self {
+        assert!(
+            is_valid_base(base),
+            "Invalid base {}, must be in the range 2..=94",
+            base
+        );
+        assert!(chunk_size > 0, "Chunk size must be greater than 0");
+
+        Self {
+            base,
+            chunk_size,
+            full_width: block_width(chunk_size, base),
+            pending: Vec::with_capacity(chunk_size),
+            output: String::new(),
+        }
+    }
+
+    /// Feeds the next piece of the stream and returns the text of every block it completed.
+    /// The returned text may be empty if no block was completed.
+    pub fn push(&mut self, bytes: &[u8]) -> &str {
+        self.output.clear();
+        let mut bytes = bytes;
+
+        if !self.pending.is_empty() {
+            let take = bytes.len().min(self.chunk_size - self.pending.len());
+            self.pending.extend_from_slice(&bytes[..take]);
+            bytes = &bytes[take..];
+            if self.pending.len() < self.chunk_size {
+                return &self.output;
+            }
+            encode_blocks(
+                &self.pending,
+                self.base,
+                self.chunk_size,
+                self.full_width,
+                &mut self.output,
+            );
+            self.pending.clear();
+        }
+
+        let full = bytes.len() - bytes.len() % self.chunk_size;
+        encode_blocks(
+            &bytes[..full],
+            self.base,
+            self.chunk_size,
+            self.full_width,
+            &mut self.output,
+        );
+        self.pending.extend_from_slice(&bytes[full..]);
+
+        &self.output
+    }
+
+    /// Encodes the final partial block, if any, and ends the stream.
+    pub fn finalize(mut self) -> String {
+        self.finalize_in_place()
+    }
+
+    /// Encodes the final partial block like [`finalize`](Self::finalize), for owners that
+    /// cannot give up the encoder.
+    pub(crate) fn finalize_in_place(&mut self) -> String {
+        let mut out = String::new();
+        encode_blocks(
+            &self.pending,
+            self.base,
+            self.chunk_size,
+            self.full_width,
+            &mut out,
+        );
+        self.pending.clear();
+        out
+    }
+}
+
+/// Decodes the output of an [`Encoder`] piece by piece.
+///
+/// Encoded text may be split at any point between pushes. Error positions count from the
+/// start of the stream, not from the start of the latest push. After an error the stream
+/// cannot be resumed.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{Decoder, Encoder};
+///
+/// let mut encoder = Encoder::new(94);
+/// let mut encoded = encoder.push(b"Hello, streaming World!").to_string();
+/// encoded.push_str(&encoder.finalize());
+///
+/// let (first, second) = encoded.split_at(10);
+/// let mut decoder = Decoder::new(94);
+/// let mut decoded = decoder.push(first).unwrap();
+/// decoded.extend(decoder.push(second).unwrap());
+/// decoded.extend(decoder.finalize().unwrap());
+/// assert_eq!(decoded, b"Hello, streaming World!");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Decoder {
+    base: u8,
+    chunk_size: usize,
+    full_width: usize,
+    /// Digits of the current, incomplete block.
+    pending: Vec<u8>,
+    /// Number of characters consumed so far.
+    position: usize,
+}
+
+impl Decoder {
+    /// Creates a decoder for streams encoded with [`STREAM_CHUNK_SIZE`] byte blocks.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the specified base is outside the valid range (2 to 94).
+    pub fn new(base: u8) -> Self {
+        Self::with_chunk_size(base, STREAM_CHUNK_SIZE)
+    }
+
+    /// Creates a decoder for streams encoded with blocks of `chunk_size` bytes.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the specified base is outside the valid range (2 to 94) or if
+    /// `chunk_size` is zero.
+    pub fn with_chunk_size(base: u8, chunk_size: usize) -> Self {
+        assert!(
+            is_valid_base(base),
+            "Invalid base {}, must be in the range 2..=94",
+            base
+        );
+        assert!(chunk_size > 0, "Chunk size must be greater than 0");
+
+        let full_width = block_width(chunk_size, base);
+        Self {
+            base,
+            chunk_size,
+            full_width,
+            pending: Vec::with_capacity(full_width),
+            position: 0,
+        }
+    }
+
+    /// Feeds the next piece of encoded text and returns the bytes of every block it
+    /// completed.
+    ///
+    /// # Returns
+    ///
+    /// The decoded bytes, or a `DecodeError` if the text contains an invalid character or a
+    /// block holds a value too large for its byte length. An invalid character leaves the
+    /// decoder unchanged.
+    pub fn push(&mut self, text: &str) -> Result<Vec<u8>, DecodeError> {
+        self.push_bytes(text.as_bytes())
+    }
+
+    /// Feeds encoded text given as raw bytes, like [`push`](Self::push). Bytes that are not
+    /// ASCII are reported as invalid characters.
+    pub(crate) fn push_bytes(&mut self, text: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let digits = (0..text.len())
+            .map(|i| digit_value(&text[i..], self.position + i, self.base))
+            .collect::<Result<Vec<u8>, DecodeError>>()?;
+
+        let mut out = Vec::new();
+        let mut digits = &digits[..];
+        let mut start = self.position - self.pending.len();
+
+        if !self.pending.is_empty() {
+            let take = digits.len().min(self.full_width - self.pending.len());
+            self.pending.extend_from_slice(&digits[..take]);
+            digits = &digits[take..];
+            if self.pending.len() == self.full_width {
+                self.decode_full(&self.pending, start, &mut out)?;
+                self.pending.clear();
+                start += self.full_width;
+            }
+        }
+
+        let full = digits.len() - digits.len() % self.full_width;
+        self.decode_full(&digits[..full], start, &mut out)?;
+        self.pending.extend_from_slice(&digits[full..]);
+        self.position += text.len();
+
+        Ok(out)
+    }
+
+    /// Decodes the final partial block, if any, and ends the stream.
+    ///
+    /// # Returns
+    ///
+    /// The bytes of the final block, or a `DecodeError` if the stream ends in the middle of
+    /// a block or the final block holds a value too large for its byte length.
+    pub fn finalize(mut self) -> Result<Vec<u8>, DecodeError> {
+        self.finalize_in_place()
+    }
+
+    /// Decodes the final partial block like [`finalize`](Self::finalize), for owners that
+    /// cannot give up the decoder.
+    pub(crate) fn finalize_in_place(&mut self) -> Result<Vec<u8>, DecodeError> {
+        let (_, last_len) = layout(self.position, self.base, self.chunk_size)?;
+        let mut out = Vec::with_capacity(last_len);
+        decode_blocks(
+            &self.pending,
+            self.base,
+            self.chunk_size,
+            self.full_width,
+            last_len,
+            self.position - self.pending.len(),
+            &mut out,
+        )?;
+        self.pending.clear();
+        Ok(out)
+    }
+
+    /// Decodes whole blocks of `digits`, the first of which starts at `offset` in the stream.
+    fn decode_full(
+        &self,
+        digits: &[u8],
+        offset: usize,
+        out: &mut Vec<u8>,
+    ) -> Result<(), DecodeError> {
+        decode_blocks(
+            digits,
+            self.base,
+            self.chunk_size,
+            self.full_width,
+            0,
+            offset,
+            out,
+        )
+    }
+}
+
+/// Wipes the buffered bytes and the text of the latest push. The encoder stays usable and
+/// continues as if the buffered bytes had never been pushed.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Encoder {
+    fn zeroize(&mut self) {
+        self.pending.zeroize();
+        self.output.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Encoder {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Encoder {}
+
+/// Wipes the buffered digits of the current, incomplete block.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Decoder {
+    fn zeroize(&mut self) {
+        self.position -= self.pending.len();
+        self.pending.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Decoder {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Decoder {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode_chunked, encode_chunked};
+
+    const MAX_BASE: u8 = 94;
+
+    fn encode_in_pieces(data: &[u8], base: u8, piece: usize) -> String {
+        let mut encoder = Encoder::new(base);
+        let mut encoded = String::new();
+        for bytes in data.chunks(piece) {
+            encoded.push_str(encoder.push(bytes));
+        }
+        encoded.push_str(&encoder.finalize());
+        encoded
+    }
+
+    #[test]
+    fn test_push_sizes_give_identical_output() {
+        for base in 2..=MAX_BASE {
+            let data = (0..5000).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let expected = encode_chunked(&data, base, STREAM_CHUNK_SIZE);
+            for piece in [1, 7, 4096] {
+                assert_eq!(encode_in_pieces(&data, base, piece), expected);
+            }
+            assert_eq!(
+                decode_chunked(&expected, base, STREAM_CHUNK_SIZE).unwrap(),
+                data
+            );
+        }
+    }
+
+    #[test]
+    fn test_push_emits_completed_blocks() {
+        let mut encoder = Encoder::with_chunk_size(94, 4);
+        assert_eq!(encoder.push(b"abc"), "");
+        assert_eq!(encoder.push(b"d"), encode_chunked(b"abcd", 94, 4));
+        assert_eq!(encoder.push(b""), "");
+        assert_eq!(encoder.finalize(), "");
+    }
+
+    #[test]
+    fn test_empty_stream() {
+        assert_eq!(Encoder::new(94).finalize(), "");
+    }
+
+    fn decode_in_pieces(encoded: &str, base: u8, splits: &[usize]) -> Result<Vec<u8>, DecodeError> {
+        let mut decoder = Decoder::new(base);
+        let mut decoded = Vec::new();
+        let mut start = 0;
+        for &end in splits.iter().chain([encoded.len()].iter()) {
+            decoded.extend(decoder.push(&encoded[start..end])?);
+            start = end;
+        }
+        decoded.extend(decoder.finalize()?);
+        Ok(decoded)
+    }
+
+    #[test]
+    fn test_decode_at_every_split_point() {
+        for base in 2..=MAX_BASE {
+            let data = (0..100).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let encoded = encode_chunked(&data, base, STREAM_CHUNK_SIZE);
+            for split in 0..=encoded.len() {
+                assert_eq!(decode_in_pieces(&encoded, base, &[split]).unwrap(), data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_at_random_split_points() {
+        for base in 2..=MAX_BASE {
+            let data = (0..20_000)
+                .map(|_| rand::random::<u8>())
+                .collect::<Vec<u8>>();
+            let encoded = encode_in_pieces(&data, base, 4096);
+            let mut splits = (0..50)
+                .map(|_| rand::random::<usize>() % (encoded.len() + 1))
+                .collect::<Vec<usize>>();
+            splits.sort_unstable();
+            assert_eq!(decode_in_pieces(&encoded, base, &splits).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_decode_error_positions_are_stream_positions() {
+        let encoded = encode_chunked(&[7; 100], 94, STREAM_CHUNK_SIZE);
+        let mut decoder = Decoder::new(94);
+        decoder.push(&encoded[..50]).unwrap();
+        assert!(matches!(
+            decoder.push("!! !"),
+            Err(DecodeError::InvalidCharacter {
+                c: ' ',
+                position: 52
+            })
+        ));
+        // The rejected push left the decoder unchanged.
+        decoder.push(&encoded[50..]).unwrap();
+        decoder.finalize().unwrap();
+    }
+
+    #[test]
+    fn test_decode_block_overflow_position() {
+        let width = block_width(STREAM_CHUNK_SIZE, 94);
+        let text = "~".repeat(width);
+        let mut decoder = Decoder::new(94);
+        decoder.push(&"!".repeat(width + 3)).unwrap();
+        assert!(matches!(
+            decoder.push(&text),
+            Err(DecodeError::BlockOverflow { position }) if position == width
+        ));
+    }
+
+    #[test]
+    fn test_decode_truncated_final_block() {
+        // A full base-94 block of 32 bytes is 40 digits and no byte length needs exactly 1.
+        let mut decoder = Decoder::new(94);
+        decoder.push(&"!".repeat(41)).unwrap();
+        assert!(matches!(
+            decoder.finalize(),
+            Err(DecodeError::InvalidLength { length: 41 })
+        ));
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize_clears_buffered_data() {
+        use zeroize::{Zeroize, ZeroizeOnDrop};
+
+        fn assert_zeroize_on_drop<T: Zeroize + ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<Encoder>();
+        assert_zeroize_on_drop::<Decoder>();
+
+        for base in 2..=MAX_BASE {
+            let mut encoder = Encoder::new(base);
+            assert!(!encoder.push(&[0xAA; STREAM_CHUNK_SIZE + 5]).is_empty());
+            encoder.zeroize();
+            assert!(encoder.pending.is_empty() && encoder.output.is_empty());
+            assert_eq!(encoder.finalize(), "");
+
+            let encoded = encode_chunked(&[0xAA; STREAM_CHUNK_SIZE + 5], base, STREAM_CHUNK_SIZE);
+            let full_width = block_width(STREAM_CHUNK_SIZE, base);
+            let mut decoder = Decoder::new(base);
+            decoder.push(&encoded[..full_width + 1]).unwrap();
+            decoder.zeroize();
+            assert!(decoder.pending.is_empty());
+            assert_eq!(decoder.position, full_width);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! A self-describing format that records the base in the encoded string.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{
+    collect_digits, decode_digits, digit_value, encode, invalid_character, wipe, DecodeError,
+};
+
+/// The first character of every tagged string.
+const MAGIC: u8 = b'~';
+
+/// The format version written by [`encode_tagged`].
+const VERSION: u8 = b'1';
+
+/// The length of the magic character, version, and two-digit base.
+const HEADER_LEN: usize = 4;
+
+/// Encodes a slice of bytes like [`encode`], prefixed with a header that records `base`.
+///
+/// The header is `~`, the format version `1`, and the base as two decimal digits, so the
+/// output of base 94 starts with `~194`. [`decode_tagged`] reads the base back from it.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_tagged, encode, encode_tagged};
+///
+/// let encoded = encode_tagged(b"Hello", 16);
+/// assert_eq!(encoded, format!("~116{}", encode(b"Hello", 16)));
+/// assert_eq!(decode_tagged(&encoded).unwrap(), (b"Hello".to_vec(), 16));
+/// ```
+pub fn encode_tagged(data: &[u8], base: u8) -> String {
+    let payload = encode(data, base);
+    format!("{}{}{:02}{}", MAGIC as char, VERSION as char, base, payload)
+}
+
+/// Decodes a string produced by [`encode_tagged`], taking the base from its header.
+///
+/// # Returns
+///
+/// The decoded bytes and the base, or a `DecodeError` if:
+/// - `encoded` does not start with `~`, with `DecodeError::MissingTag`.
+/// - The version is not `1`, with `DecodeError::UnsupportedVersion`.
+/// - `encoded` ends inside the header, with `DecodeError::TruncatedInput`.
+/// - The base is not two decimal digits, or outside the valid range (2 to 94).
+/// - The payload is invalid for the base. Error positions count from the start of
+///   `encoded`, including the header.
+pub fn decode_tagged(encoded: &str) -> Result<(Vec<u8>, u8), DecodeError> {
+    let bytes = encoded.as_bytes();
+    if bytes.first() != Some(&MAGIC) {
+        return Err(DecodeError::MissingTag);
+    }
+    match bytes.get(1) {
+        Some(&VERSION) => {}
+        Some(&version) => return Err(DecodeError::UnsupportedVersion { version }),
+        None => return Err(DecodeError::TruncatedInput),
+    }
+    if bytes.len() < HEADER_LEN {
+        return Err(DecodeError::TruncatedInput);
+    }
+
+    let mut base = 0;
+    for (position, &c) in bytes.iter().enumerate().take(HEADER_LEN).skip(2) {
+        if !c.is_ascii_digit() {
+            return Err(invalid_character(&bytes[position..], position));
+        }
+        base = base * 10 + (c - b'0');
+    }
+    if !(2..=94).contains(&base) {
+        return Err(DecodeError::InvalidBase { base });
+    }
+
+    let mut digits =
+        collect_digits((HEADER_LEN..bytes.len()).map(|i| digit_value(&bytes[i..], i, base)))?;
+    let decoded = decode_digits(&digits, base);
+    wipe(&mut digits);
+    Ok((decoded, base))
+}
+
+/// Returns whether `encoded` starts with `~`, a version digit, and a two-digit base, the
+/// shape of every header [`encode_tagged`] has written or will write.
+pub(crate) fn has_header(encoded: &str) -> bool {
+    match encoded.as_bytes() {
+        [MAGIC, header @ ..] if header.len() >= HEADER_LEN - 1 => {
+            header[..HEADER_LEN - 1].iter().all(u8::is_ascii_digit)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_round_trip() {
+        for base in 2..=MAX_BASE {
+            for data in [&b"Hello, World!"[..], &[], &[0], &[1, 0, 0]] {
+                let encoded = encode_tagged(data, base);
+                assert_eq!(&encoded[..2], "~1");
+                assert_eq!(encoded[2..4].parse::<u8>().unwrap(), base);
+                assert_eq!(decode_tagged(&encoded).unwrap(), (data.to_vec(), base));
+            }
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(decode_tagged(""), Err(DecodeError::MissingTag)));
+        assert!(matches!(
+            decode_tagged("194abc"),
+            Err(DecodeError::MissingTag)
+        ));
+        assert!(matches!(
+            decode_tagged("~294abc"),
+            Err(DecodeError::UnsupportedVersion { version: b'2' })
+        ));
+        assert!(matches!(
+            decode_tagged("~19"),
+            Err(DecodeError::TruncatedInput)
+        ));
+        assert!(matches!(
+            decode_tagged("~1a4"),
+            Err(DecodeError::InvalidCharacter {
+                c: 'a',
+                position: 2
+            })
+        ));
+        for base in [0, 1, 95, 99] {
+            assert!(matches!(
+                decode_tagged(&format!("~1{:02}", base)),
+                Err(DecodeError::InvalidBase { base: found }) if found == base
+            ));
+        }
+        assert!(matches!(
+            decode_tagged("~110!!+"),
+            Err(DecodeError::DigitOutOfRange {
+                c: '+',
+                position: 6,
+                base: 10
+            })
+        ));
+    }
+}
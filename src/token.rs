@@ -0,0 +1,171 @@
+//! Random printable tokens for secrets, session IDs, and passwords.
+
+use alloc::string::String;
+use alloc::vec;
+
+use rand::rngs::OsRng;
+use rand::{CryptoRng, Rng, RngCore};
+
+use crate::{encode, is_valid_base, wipe, EncodeError, CHARACTERS};
+
+/// Encodes `entropy_bytes` random bytes from the operating system's RNG in `base`.
+///
+/// The token carries exactly `8 * entropy_bytes` bits of entropy. Its length varies: it is
+/// at most [`encoded_len(entropy_bytes, base)`](crate::encoded_len) characters, and can be
+/// shorter when the random number happens to be small. Use [`generate_token_chars`] for a
+/// fixed length.
+///
+/// # Arguments
+///
+/// * `entropy_bytes` - The number of random bytes to encode.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94), or if
+/// the operating system's RNG fails.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode, encoded_len, generate_token};
+///
+/// let token = generate_token(32, 94);
+/// assert!(token.len() <= encoded_len(32, 94));
+/// assert_eq!(decode(&token, 94).unwrap().len(), 32);
+/// ```
+pub fn generate_token(entropy_bytes: usize, base: u8) -> String {
+    generate_token_with_rng(entropy_bytes, base, &mut OsRng)
+}
+
+/// Like [`generate_token`], drawing the random bytes from `rng`.
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+pub fn generate_token_with_rng<R>(entropy_bytes: usize, base: u8, rng: &mut R) -> String
+where
+    R: RngCore + CryptoRng + ?Sized,
+{
+    let mut bytes = vec![0; entropy_bytes];
+    rng.fill_bytes(&mut bytes);
+    let token = encode(&bytes, base);
+    wipe(&mut bytes);
+    token
+}
+
+/// Returns `len` characters drawn uniformly and independently from the first `base`
+/// characters of the alphabet, using the operating system's RNG.
+///
+/// The token carries `len * log2(base)` bits of entropy, about 6.55 bits per character in
+/// base 94; for example, 20 characters of base 94 carry 131 bits. Unlike
+/// [`generate_token`], the length is exact but the token is not the encoding of a whole
+/// number of random bytes.
+///
+/// # Arguments
+///
+/// * `len` - The number of characters in the token.
+/// * `base` - The number of alphabet characters to draw from. Must be between 2 and 94
+///   (inclusive).
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94), or if
+/// the operating system's RNG fails.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{generate_token_chars, validate};
+///
+/// let password = generate_token_chars(20, 94);
+/// assert_eq!(password.len(), 20);
+/// assert!(validate(&password, 94).is_ok());
+/// ```
+pub fn generate_token_chars(len: usize, base: u8) -> String {
+    generate_token_chars_with_rng(len, base, &mut OsRng)
+}
+
+/// Like [`generate_token_chars`], drawing the characters from `rng`.
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+pub fn generate_token_chars_with_rng<R>(len: usize, base: u8, rng: &mut R) -> String
+where
+    R: RngCore + CryptoRng + ?Sized,
+{
+    if !is_valid_base(base) {
+        panic!("{}", EncodeError::InvalidBase { base });
+    }
+    (0..len)
+        .map(|_| CHARACTERS[rng.gen_range(0..base as usize)] as char)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, digit_index, encoded_len};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_generate_token() {
+        for base in 2..=MAX_BASE {
+            for entropy_bytes in [0, 1, 16, 32] {
+                let token = generate_token(entropy_bytes, base);
+                assert!(token.len() <= encoded_len(entropy_bytes, base));
+                assert_eq!(decode(&token, base).unwrap().len(), entropy_bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_token_chars() {
+        for base in 2..=MAX_BASE {
+            for len in [0, 1, 20, 100] {
+                let token = generate_token_chars(len, base);
+                assert_eq!(token.len(), len);
+                assert!(token
+                    .bytes()
+                    .all(|c| CHARACTERS[..base as usize].contains(&c)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_character_appears() {
+        for base in [2, 10, 64, 94] {
+            let mut counts = [0usize; 94];
+            for c in generate_token_chars(base as usize * 200, base).bytes() {
+                counts[digit_index(c).unwrap() as usize] += 1;
+            }
+            // Each character is expected 200 times; 100 is more than 7 standard deviations
+            // below that.
+            assert!(counts[..base as usize].iter().all(|&count| count > 100));
+            assert!(counts[base as usize..].iter().all(|&count| count == 0));
+        }
+    }
+
+    #[test]
+    fn test_reproducible_with_rng() {
+        let token = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (
+                generate_token_with_rng(16, 94, &mut rng),
+                generate_token_chars_with_rng(16, 50, &mut rng),
+            )
+        };
+        assert_eq!(token(1), token(1));
+        assert_ne!(token(1), token(2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_base() {
+        generate_token_chars(10, 95);
+    }
+}
@@ -0,0 +1,298 @@
+//! A `tokio_util` codec that frames binary messages as encoded lines.
+
+use std::io;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use thiserror::Error;
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::{decode_bytes, encode, encoded_len, is_valid_base, DecodeError};
+
+/// The largest message [`Base94Codec::new`] accepts, in bytes before encoding.
+pub const DEFAULT_MAX_FRAME_LENGTH: usize = 8 * 1024 * 1024;
+
+/// Errors of [`Base94Codec`].
+#[derive(Error, Debug)]
+pub enum Base94CodecError {
+    /// A received line is not valid encoded text. Positions count from the start of the
+    /// line.
+    #[error(transparent)]
+    Decode(#[from] DecodeError),
+    /// A message, or the encoded line it would take, exceeds the maximum frame length.
+    #[error("Frame exceeds the maximum length of {max_frame_length} bytes")]
+    FrameTooLong { max_frame_length: usize },
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Frames each message as its [`encode`] output followed by `\n`, for sending binary
+/// messages over line-based text transports.
+///
+/// Use it with [`Framed`](tokio_util::codec::Framed), [`FramedRead`](tokio_util::codec::FramedRead),
+/// or [`FramedWrite`](tokio_util::codec::FramedWrite). The decoder accepts `\r\n` line
+/// endings as well, and keeps a partial line buffered until the rest of it arrives. To
+/// bound that buffer, messages longer than the maximum frame length are rejected when they
+/// are sent, and lines longer than any such message could encode to are rejected as soon as
+/// that much has arrived, with [`Base94CodecError::FrameTooLong`]. Received lines that
+/// decode to a longer message are rejected the same way.
+///
+/// # Examples
+///
+/// ```
+/// use base94::Base94Codec;
+/// use bytes::{Bytes, BytesMut};
+/// use tokio_util::codec::{Decoder, Encoder};
+///
+/// let mut codec = Base94Codec::new(94);
+/// let mut wire = BytesMut::new();
+/// codec.encode(Bytes::from_static(b"Hello"), &mut wire).unwrap();
+/// assert_eq!(wire, &b"G8GP4b\n"[..]);
+///
+/// let message = codec.decode(&mut wire).unwrap().unwrap();
+/// assert_eq!(message, &b"Hello"[..]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Base94Codec {
+    base: u8,
+    max_frame_length: usize,
+    /// The longest line a message of `max_frame_length` bytes can encode to.
+    max_line_length: usize,
+    /// How much of the buffer has been searched for a newline without finding one.
+    scanned: usize,
+}
+
+impl Base94Codec {
+    /// Creates a codec for `base` that accepts messages of up to
+    /// [`DEFAULT_MAX_FRAME_LENGTH`] bytes.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the specified base is outside the valid range (2 to 94).
+    pub fn new(base: u8) -> Self {
+        Self::with_max_frame_length(base, DEFAULT_MAX_FRAME_LENGTH)
+    }
+
+    /// Creates a codec for `base` that accepts messages of up to `max_frame_length` bytes.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the specified base is outside the valid range (2 to 94).
+    pub fn with_max_frame_length(base: u8, max_frame_length: usize) -> Self {
+        assert!(
+            is_valid_base(base),
+            "Invalid base {}, must be in the range 2..=94",
+            base
+        );
+        let max_line_length = if max_frame_length > usize::MAX / 8 {
+            usize::MAX
+        } else {
+            encoded_len(max_frame_length, base)
+        };
+        Self {
+            base,
+            max_frame_length,
+            max_line_length,
+            scanned: 0,
+        }
+    }
+
+    /// Returns the base messages are encoded in.
+    pub fn base(&self) -> u8 {
+        self.base
+    }
+
+    /// Returns the largest message the codec accepts, in bytes before encoding.
+    pub fn max_frame_length(&self) -> usize {
+        self.max_frame_length
+    }
+
+    fn too_long(&self) -> Base94CodecError {
+        Base94CodecError::FrameTooLong {
+            max_frame_length: self.max_frame_length,
+        }
+    }
+}
+
+impl Decoder for Base94Codec {
+    type Item = BytesMut;
+    type Error = Base94CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<BytesMut>, Base94CodecError> {
+        let Some(newline) = src[self.scanned..].iter().position(|&b| b == b'\n') else {
+            self.scanned = src.len();
+            // Leave room for a `\r` before the newline that has not arrived yet.
+            if src.len() > self.max_line_length + 1 {
+                return Err(self.too_long());
+            }
+            return Ok(None);
+        };
+
+        let line = src.split_to(self.scanned + newline + 1);
+        self.scanned = 0;
+        let mut line = &line[..line.len() - 1];
+        if let [rest @ .., b'\r'] = line {
+            line = rest;
+        }
+        if line.len() > self.max_line_length {
+            return Err(self.too_long());
+        }
+        let message = decode_bytes(line, self.base)?;
+        // Zero digits decode to one byte each, so a line within the limit can still decode to
+        // a longer message.
+        if message.len() > self.max_frame_length {
+            return Err(self.too_long());
+        }
+        Ok(Some(BytesMut::from(&message[..])))
+    }
+}
+
+impl Encoder<Bytes> for Base94Codec {
+    type Error = Base94CodecError;
+
+    fn encode(&mut self, item: Bytes, dst: &mut BytesMut) -> Result<(), Base94CodecError> {
+        if item.len() > self.max_frame_length {
+            return Err(self.too_long());
+        }
+        let encoded = encode(&item, self.base);
+        dst.reserve(encoded.len() + 1);
+        dst.put_slice(encoded.as_bytes());
+        dst.put_u8(b'\n');
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{SinkExt, StreamExt};
+    use tokio_util::codec::{Framed, FramedRead};
+
+    const MAX_BASE: u8 = 94;
+
+    #[tokio::test]
+    async fn test_duplex_round_trip() {
+        let messages = (0..50)
+            .map(|len| {
+                (0..len * 7)
+                    .map(|_| rand::random::<u8>())
+                    .collect::<Bytes>()
+            })
+            .collect::<Vec<Bytes>>();
+        // A small duplex buffer splits lines across many reads.
+        let (client, server) = tokio::io::duplex(16);
+
+        let sender = {
+            let messages = messages.clone();
+            async move {
+                let mut framed = Framed::new(client, Base94Codec::new(94));
+                for message in messages {
+                    framed.send(message).await.unwrap();
+                }
+            }
+        };
+        let receiver = async move {
+            let framed = Framed::new(server, Base94Codec::new(94));
+            framed
+                .map(|frame| frame.unwrap().freeze())
+                .collect::<Vec<Bytes>>()
+                .await
+        };
+        let ((), received) = tokio::join!(sender, receiver);
+        assert_eq!(received, messages);
+    }
+
+    #[test]
+    fn test_partial_lines() {
+        for base in 2..=MAX_BASE {
+            let data = (0..40).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let line = format!("{}\r\n", encode(&data, base));
+            let mut codec = Base94Codec::new(base);
+            let mut src = BytesMut::new();
+            for (i, &byte) in line.as_bytes().iter().enumerate() {
+                src.put_u8(byte);
+                let frame = codec.decode(&mut src).unwrap();
+                if i + 1 < line.len() {
+                    assert_eq!(frame, None);
+                } else {
+                    assert_eq!(frame.unwrap(), &data[..]);
+                }
+            }
+            assert!(src.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_several_lines_in_one_read() {
+        let mut codec = Base94Codec::new(94);
+        let mut src = BytesMut::from(&b"G8GP4b\n\n!!\nG8G"[..]);
+        assert_eq!(codec.decode(&mut src).unwrap().unwrap(), &b"Hello"[..]);
+        assert_eq!(codec.decode(&mut src).unwrap().unwrap(), &b""[..]);
+        assert_eq!(codec.decode(&mut src).unwrap().unwrap(), &[0, 0][..]);
+        assert_eq!(codec.decode(&mut src).unwrap(), None);
+        assert_eq!(src, &b"G8G"[..]);
+    }
+
+    #[test]
+    fn test_decode_error() {
+        let mut codec = Base94Codec::new(94);
+        let mut src = BytesMut::from(&b"G8 GP4b\nG8GP4b\n"[..]);
+        let error = codec.decode(&mut src).unwrap_err();
+        assert!(matches!(
+            error,
+            Base94CodecError::Decode(DecodeError::InvalidCharacter {
+                c: ' ',
+                position: 2
+            })
+        ));
+        // The bad line is consumed, so the next one still decodes.
+        assert_eq!(codec.decode(&mut src).unwrap().unwrap(), &b"Hello"[..]);
+    }
+
+    #[test]
+    fn test_max_frame_length() {
+        let mut codec = Base94Codec::with_max_frame_length(94, 16);
+        assert_eq!(codec.max_frame_length(), 16);
+        let mut dst = BytesMut::new();
+        codec.encode(Bytes::from(vec![0xff; 16]), &mut dst).unwrap();
+        assert!(matches!(
+            codec.encode(Bytes::from(vec![0xff; 17]), &mut dst),
+            Err(Base94CodecError::FrameTooLong {
+                max_frame_length: 16
+            })
+        ));
+        assert_eq!(codec.decode(&mut dst).unwrap().unwrap(), &[0xff; 16][..]);
+
+        // A line without a newline is rejected once it cannot be a valid frame.
+        let mut src = BytesMut::from(&[b'~'; 21][..]);
+        assert_eq!(codec.decode(&mut src).unwrap(), None);
+        src.put_u8(b'~');
+        assert!(matches!(
+            codec.decode(&mut src),
+            Err(Base94CodecError::FrameTooLong { .. })
+        ));
+
+        let mut src = BytesMut::from(&[b'~'; 21][..]);
+        src.put_slice(b"\n");
+        assert!(matches!(
+            codec.decode(&mut src),
+            Err(Base94CodecError::FrameTooLong { .. })
+        ));
+
+        // A line at the limit that decodes to more than 16 bytes is rejected too.
+        let mut src = BytesMut::from(&[b'!'; 21][..]);
+        src.put_slice(b"\n");
+        assert!(matches!(
+            codec.decode(&mut src),
+            Err(Base94CodecError::FrameTooLong { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_truncated_stream() {
+        let reader = FramedRead::new(&b"G8GP4b\nG8G"[..], Base94Codec::new(94));
+        let frames = reader.collect::<Vec<_>>().await;
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].as_ref().unwrap(), &b"Hello"[..]);
+        assert!(matches!(frames[1], Err(Base94CodecError::Io(_))));
+    }
+}
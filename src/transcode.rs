@@ -0,0 +1,178 @@
+//! Conversion of encoded text from one encoding to another.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use thiserror::Error;
+
+use crate::compat::{ascii85, base32, base45, base64, base91, hex, z85, CompatError};
+use crate::{decode, decode_base58, encode_base58, try_encode, DecodeError, EncodeError};
+
+/// A text encoding that [`transcode`] can read and write.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// This crate's encoding in the given base, as written by [`encode`](crate::encode).
+    Base(u8),
+    /// Lower-case hex. Upper-case digits are accepted when decoding.
+    Hex,
+    /// Padded RFC 4648 Base32.
+    Base32,
+    /// Padded RFC 4648 Base64 with the standard alphabet.
+    Base64,
+    /// Padded RFC 4648 Base64 with the URL-safe alphabet.
+    Base64Url,
+    /// Bitcoin's Base58.
+    Base58,
+    /// Henke's basE91.
+    Base91,
+    /// Ascii85 without the `<~ ~>` frame. The frame is accepted when decoding.
+    Ascii85,
+    /// Z85, with a shortened final group for data whose length is not a multiple of 4.
+    Z85,
+    /// RFC 9285 Base45.
+    Base45,
+}
+
+impl Encoding {
+    /// Encodes `data` in this encoding.
+    ///
+    /// # Returns
+    ///
+    /// The encoded string, or `TranscodeError::Encode` if this is a [`Base`](Self::Base)
+    /// outside the valid range (2 to 94).
+    pub fn encode(self, data: &[u8]) -> Result<String, TranscodeError> {
+        Ok(match self {
+            Encoding::Base(base) => try_encode(data, base)?,
+            Encoding::Hex => hex::encode(data),
+            Encoding::Base32 => base32::encode(data),
+            Encoding::Base64 => base64::encode(data),
+            Encoding::Base64Url => base64::encode_url_safe(data),
+            Encoding::Base58 => encode_base58(data),
+            Encoding::Base91 => base91::encode(data),
+            Encoding::Ascii85 => ascii85::encode(data),
+            Encoding::Z85 => z85::encode_padded(data),
+            Encoding::Base45 => base45::encode(data),
+        })
+    }
+
+    /// Decodes `encoded` from this encoding.
+    ///
+    /// # Returns
+    ///
+    /// The decoded bytes, or the error of the underlying decoder.
+    pub fn decode(self, encoded: &str) -> Result<Vec<u8>, TranscodeError> {
+        Ok(match self {
+            Encoding::Base(base) => decode(encoded, base)?,
+            Encoding::Hex => hex::decode(encoded)?,
+            Encoding::Base32 => base32::decode(encoded)?,
+            Encoding::Base64 => base64::decode(encoded)?,
+            Encoding::Base64Url => base64::decode_url_safe(encoded)?,
+            Encoding::Base58 => decode_base58(encoded)?,
+            Encoding::Base91 => base91::decode(encoded)?,
+            Encoding::Ascii85 => ascii85::decode(encoded)?,
+            Encoding::Z85 => z85::decode_padded(encoded)?,
+            Encoding::Base45 => base45::decode(encoded)?,
+        })
+    }
+}
+
+/// Errors that can occur while transcoding, from whichever codec failed.
+#[derive(Error, Debug)]
+pub enum TranscodeError {
+    #[error(transparent)]
+    Decode(#[from] DecodeError),
+    #[error(transparent)]
+    Compat(#[from] CompatError),
+    #[error(transparent)]
+    Encode(#[from] EncodeError),
+}
+
+/// Decodes `input` from the encoding `from` and encodes the bytes in the encoding `to`.
+///
+/// Every encoding preserves leading and trailing zero bytes, so the bytes survive any chain
+/// of conversions unchanged.
+///
+/// # Arguments
+///
+/// * `input` - The encoded string.
+/// * `from` - The encoding of `input`.
+/// * `to` - The encoding of the result.
+///
+/// # Returns
+///
+/// The re-encoded string, or a `TranscodeError` if `input` is not valid in `from` or `to`
+/// is an invalid base.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{encode, transcode, Encoding};
+///
+/// let encoded = transcode("48656c6c6f", Encoding::Hex, Encoding::Base(94)).unwrap();
+/// assert_eq!(encoded, encode(b"Hello", 94));
+/// assert_eq!(transcode(&encoded, Encoding::Base(94), Encoding::Base64).unwrap(), "SGVsbG8=");
+/// ```
+pub fn transcode(input: &str, from: Encoding, to: Encoding) -> Result<String, TranscodeError> {
+    to.encode(&from.decode(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENCODINGS: &[Encoding] = &[
+        Encoding::Base(2),
+        Encoding::Base(16),
+        Encoding::Base(94),
+        Encoding::Hex,
+        Encoding::Base32,
+        Encoding::Base64,
+        Encoding::Base64Url,
+        Encoding::Base58,
+        Encoding::Base91,
+        Encoding::Ascii85,
+        Encoding::Z85,
+        Encoding::Base45,
+    ];
+
+    #[test]
+    fn test_transcode_between_all_encodings() {
+        for len in [0, 1, 5, 16, 17, 50] {
+            let mut data = vec![0, 0];
+            data.extend((0..len).map(|_| rand::random::<u8>()));
+            data.extend([0, 0]);
+            for &from in ENCODINGS {
+                let input = from.encode(&data).unwrap();
+                for &to in ENCODINGS {
+                    let output = transcode(&input, from, to).unwrap();
+                    assert_eq!(output, to.encode(&data).unwrap());
+                    assert_eq!(to.decode(&output).unwrap(), data);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            transcode("xy", Encoding::Hex, Encoding::Base(94)),
+            Err(TranscodeError::Compat(CompatError::InvalidCharacter {
+                c: b'x',
+                position: 0
+            }))
+        ));
+        assert!(matches!(
+            transcode("0OIl", Encoding::Base58, Encoding::Hex),
+            Err(TranscodeError::Decode(DecodeError::InvalidCharacter {
+                c: '0',
+                position: 0
+            }))
+        ));
+        assert!(matches!(
+            transcode("00", Encoding::Hex, Encoding::Base(95)),
+            Err(TranscodeError::Encode(EncodeError::InvalidBase {
+                base: 95
+            }))
+        ));
+    }
+}
@@ -0,0 +1,315 @@
+//! A UniFFI interface for Kotlin and Swift, defined with UniFFI's proc macros.
+//!
+//! The interface has the fallible functions only: [`encode`] returns an error for an
+//! invalid base where [`crate::encode`] would panic, since a panic cannot cross into the
+//! foreign language in a useful form. Byte slices and vectors become `ByteArray` in Kotlin
+//! and `Data` in Swift, and the errors mirror [`crate::DecodeError`] and
+//! [`crate::AlphabetError`] variant by variant, with positions and lengths as `u64`.
+//!
+//! Build a `cdylib` with the interface, either this crate with `--features uniffi` or the
+//! `base94-uniffi` workspace member in `bindings/uniffi`, and run the `uniffi-bindgen`
+//! binary, built with `--features uniffi-bindgen`, on it to generate the sources:
+//!
+//! ```text
+//! cargo build --release -p base94-uniffi
+//! cargo run --features uniffi-bindgen --bin uniffi-bindgen -- generate \
+//!     --library target/release/libbase94_uniffi.so --language kotlin --out-dir out
+//! ```
+//!
+//! The tests of `base94-uniffi` run Kotlin, Swift, and Python scripts against the generated
+//! bindings.
+
+use std::sync::Arc;
+
+/// Quotes a character held in a string the way `{:?}` quotes a `char`, so messages read the
+/// same as those of the Rust errors.
+fn quote(c: &str) -> String {
+    c.chars().map(|c| format!("{:?}", c)).collect()
+}
+
+/// The errors of decoding, mirroring [`crate::DecodeError`].
+///
+/// Characters are strings of one character, and the armor error, which has no stable shape
+/// of its own, is carried as its message.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, ::uniffi::Error)]
+pub enum DecodeError {
+    #[error("Invalid character {} at position {position}", quote(c))]
+    InvalidCharacter { c: String, position: u64 },
+    #[error(
+        "Character {} at position {position} is out of range for base {base}",
+        quote(c)
+    )]
+    DigitOutOfRange { c: String, position: u64, base: u8 },
+    #[error("Invalid base {base}, must be in the range 2..=94")]
+    InvalidBase { base: u8 },
+    #[error("Invalid encoded length {length}")]
+    InvalidLength { length: u64 },
+    #[error("Input ends before its header or checksum is complete")]
+    TruncatedInput,
+    #[error("Block starting at position {position} holds a value too large for its length")]
+    BlockOverflow { position: u64 },
+    #[error("Output buffer too small: {needed} bytes needed, {available} available")]
+    OutputTooSmall { needed: u64, available: u64 },
+    #[error("Encoded value does not fit in {bits} bits")]
+    Overflow { bits: u32 },
+    #[error("Decoded data exceeds the capacity of {capacity} bytes")]
+    CapacityExceeded { capacity: u64 },
+    #[error("Expected {expected} decoded bytes, got {actual}")]
+    LengthMismatch { expected: u64, actual: u64 },
+    #[error("Invalid encoded data")]
+    InvalidInput,
+    #[error("Checksum mismatch: expected {expected:08x}, computed {actual:08x}")]
+    ChecksumMismatch { expected: u32, actual: u32 },
+    #[error("Separator '{separator}' is a digit in base {base}")]
+    SeparatorInAlphabet { separator: String, base: u8 },
+    #[error("Invalid padding at position {position}")]
+    InvalidPadding { position: u64 },
+    #[error("Missing '~' tag at the start of the string")]
+    MissingTag,
+    #[error("Unsupported tagged format version '{version}'")]
+    UnsupportedVersion { version: u8 },
+    #[error("Unknown compression method {method}")]
+    UnknownCompression { method: u8 },
+    #[error("Corrupt compressed data")]
+    CorruptCompressedData,
+    #[error("Input is not armored or tagged, and no base was given for plain input")]
+    UnknownFormat,
+    #[error("{reason}")]
+    Armor { reason: String },
+}
+
+impl From<crate::DecodeError> for DecodeError {
+    fn from(err: crate::DecodeError) -> Self {
+        use crate::DecodeError as E;
+
+        match err {
+            E::InvalidCharacter { c, position } => Self::InvalidCharacter {
+                c: c.to_string(),
+                position: position as u64,
+            },
+            E::DigitOutOfRange { c, position, base } => Self::DigitOutOfRange {
+                c: c.to_string(),
+                position: position as u64,
+                base,
+            },
+            E::InvalidBase { base } => Self::InvalidBase { base },
+            E::InvalidLength { length } => Self::InvalidLength {
+                length: length as u64,
+            },
+            E::TruncatedInput => Self::TruncatedInput,
+            E::BlockOverflow { position } => Self::BlockOverflow {
+                position: position as u64,
+            },
+            E::OutputTooSmall { needed, available } => Self::OutputTooSmall {
+                needed: needed as u64,
+                available: available as u64,
+            },
+            E::Overflow { bits } => Self::Overflow { bits },
+            E::CapacityExceeded { capacity } => Self::CapacityExceeded {
+                capacity: capacity as u64,
+            },
+            E::LengthMismatch { expected, actual } => Self::LengthMismatch {
+                expected: expected as u64,
+                actual: actual as u64,
+            },
+            E::InvalidInput => Self::InvalidInput,
+            E::ChecksumMismatch { expected, actual } => Self::ChecksumMismatch { expected, actual },
+            E::SeparatorInAlphabet { separator, base } => Self::SeparatorInAlphabet {
+                separator: separator.to_string(),
+                base,
+            },
+            E::InvalidPadding { position } => Self::InvalidPadding {
+                position: position as u64,
+            },
+            E::MissingTag => Self::MissingTag,
+            E::UnsupportedVersion { version } => Self::UnsupportedVersion { version },
+            E::UnknownCompression { method } => Self::UnknownCompression { method },
+            E::CorruptCompressedData => Self::CorruptCompressedData,
+            E::UnknownFormat => Self::UnknownFormat,
+            E::Armor(err) => Self::Armor {
+                reason: err.to_string(),
+            },
+        }
+    }
+}
+
+/// The errors of building an alphabet, mirroring [`crate::AlphabetError`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, ::uniffi::Error)]
+pub enum AlphabetError {
+    #[error("Invalid alphabet length {length}, must be in the range 2..={max}")]
+    InvalidLength { length: u64, max: u64 },
+    #[error(
+        "Character {} at position {position} cannot be used in an alphabet",
+        quote(c)
+    )]
+    InvalidCharacter { c: String, position: u64 },
+    #[error("Character {} appears at positions {first} and {second}", quote(c))]
+    DuplicateCharacter { c: String, first: u64, second: u64 },
+    #[error(
+        "Alphabet contains both {} and {}, so it cannot ignore case",
+        quote(upper),
+        quote(lower)
+    )]
+    CaseCollision { upper: String, lower: String },
+}
+
+impl From<crate::AlphabetError> for AlphabetError {
+    fn from(err: crate::AlphabetError) -> Self {
+        use crate::AlphabetError as E;
+
+        match err {
+            E::InvalidLength { length, max } => Self::InvalidLength {
+                length: length as u64,
+                max: max as u64,
+            },
+            E::InvalidCharacter { c, position } => Self::InvalidCharacter {
+                c: c.to_string(),
+                position: position as u64,
+            },
+            E::DuplicateCharacter { c, first, second } => Self::DuplicateCharacter {
+                c: c.to_string(),
+                first: first as u64,
+                second: second as u64,
+            },
+            E::CaseCollision { upper, lower } => Self::CaseCollision {
+                upper: upper.to_string(),
+                lower: lower.to_string(),
+            },
+        }
+    }
+}
+
+/// A custom alphabet, wrapping [`crate::Alphabet`].
+#[derive(Debug, ::uniffi::Object)]
+pub struct Alphabet(crate::Alphabet);
+
+#[::uniffi::export]
+impl Alphabet {
+    /// Builds an alphabet from `symbols`, distinct printable ASCII characters standing for
+    /// the digits in order.
+    #[uniffi::constructor]
+    pub fn new(symbols: String) -> Result<Arc<Self>, AlphabetError> {
+        Ok(Arc::new(Self(crate::Alphabet::new(symbols.as_bytes())?)))
+    }
+
+    /// The standard alphabet of base 94.
+    #[uniffi::constructor]
+    pub fn standard() -> Arc<Self> {
+        Arc::new(Self(crate::STANDARD))
+    }
+
+    /// Returns the base of encodings with this alphabet, the number of its characters.
+    pub fn base(&self) -> u8 {
+        self.0.base()
+    }
+
+    /// Returns the characters of the alphabet in digit order.
+    pub fn symbols(&self) -> String {
+        // Alphabets only hold printable ASCII.
+        self.0.chars().iter().map(|&c| char::from(c)).collect()
+    }
+}
+
+/// Encodes `data` in `base`, failing with `DecodeError::InvalidBase` if the base is outside
+/// 2 to 94.
+#[::uniffi::export]
+pub fn encode(data: Vec<u8>, base: u8) -> Result<String, DecodeError> {
+    crate::try_encode(&data, base).map_err(|_| DecodeError::InvalidBase { base })
+}
+
+/// Decodes `encoded` in `base`.
+#[::uniffi::export]
+pub fn decode(encoded: String, base: u8) -> Result<Vec<u8>, DecodeError> {
+    Ok(crate::decode(&encoded, base)?)
+}
+
+/// Encodes `data` with `alphabet`. Never fails, since every alphabet has a valid base.
+#[::uniffi::export]
+pub fn encode_with_alphabet(data: Vec<u8>, alphabet: Arc<Alphabet>) -> String {
+    crate::encode_with_alphabet(&data, &alphabet.0)
+}
+
+/// Decodes `encoded` with `alphabet`.
+#[::uniffi::export]
+pub fn decode_with_alphabet(
+    encoded: String,
+    alphabet: Arc<Alphabet>,
+) -> Result<Vec<u8>, DecodeError> {
+    Ok(crate::decode_with_alphabet(&encoded, &alphabet.0)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_round_trip() {
+        for base in 2..=MAX_BASE {
+            let data = (0..100).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let encoded = encode(data.clone(), base).unwrap();
+            assert_eq!(encoded, crate::encode(&data, base));
+            assert_eq!(decode(encoded, base).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_errors_mirror_decode_error() {
+        assert_eq!(
+            encode(b"Hi".to_vec(), 95),
+            Err(DecodeError::InvalidBase { base: 95 })
+        );
+        let err = decode("ab \u{e9}".into(), 94).unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::InvalidCharacter {
+                c: " ".into(),
+                position: 2
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            crate::decode("ab \u{e9}", 94).unwrap_err().to_string()
+        );
+        assert_eq!(
+            decode("!!~".into(), 10),
+            Err(DecodeError::DigitOutOfRange {
+                c: "~".into(),
+                position: 2,
+                base: 10
+            })
+        );
+    }
+
+    #[test]
+    fn test_alphabet() {
+        let alphabet = Alphabet::new("0123456789abcdef".into()).unwrap();
+        assert_eq!(alphabet.base(), 16);
+        assert_eq!(alphabet.symbols(), "0123456789abcdef");
+        let encoded = encode_with_alphabet(b"Hello".to_vec(), alphabet.clone());
+        assert_eq!(
+            decode_with_alphabet(encoded, alphabet.clone()).unwrap(),
+            b"Hello"
+        );
+        assert!(matches!(
+            decode_with_alphabet("0g".into(), alphabet),
+            Err(DecodeError::InvalidCharacter { position: 1, .. })
+        ));
+        assert_eq!(Alphabet::standard().base(), 94);
+
+        let err = Alphabet::new("aba".into()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            crate::Alphabet::new(b"aba").unwrap_err().to_string()
+        );
+        assert_eq!(
+            err,
+            AlphabetError::DuplicateCharacter {
+                c: "a".into(),
+                first: 0,
+                second: 2
+            }
+        );
+    }
+}
@@ -0,0 +1,11 @@
+//! Generates the Kotlin and Swift bindings for the `uniffi` interface:
+//!
+//! ```text
+//! cargo build --release -p base94-uniffi
+//! cargo run --features uniffi-bindgen --bin uniffi-bindgen -- generate \
+//!     --library target/release/libbase94_uniffi.so --language kotlin --out-dir out
+//! ```
+
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
@@ -0,0 +1,229 @@
+//! Character validation without decoding.
+
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+#[cfg(feature = "alloc")]
+use crate::{decode_digits, wipe};
+use crate::{digit_value, is_valid_base, DecodeError};
+
+/// The most errors [`decode_collect_errors`] reports for one input.
+#[cfg(feature = "alloc")]
+pub const MAX_COLLECTED_ERRORS: usize = 100;
+
+/// Checks that `encoded` only contains digits that are valid in `base`, without decoding it.
+///
+/// This only scans the characters, so it is much cheaper than [`decode`](crate::decode) for
+/// rejecting bad input early.
+///
+/// # Arguments
+///
+/// * `encoded` - The string to validate.
+/// * `base` - The base the string is expected to be encoded in.
+///
+/// # Returns
+///
+/// `Ok(())` if `decode` would accept the string, or the same `DecodeError` that `decode`
+/// would report, including the exact position of the first invalid character.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{validate, DecodeError};
+///
+/// assert!(validate("A@#D9e@D9n9RRb6^", 94).is_ok());
+/// assert!(matches!(
+///     validate("!#*~", 10),
+///     Err(DecodeError::DigitOutOfRange { position: 3, .. })
+/// ));
+/// ```
+pub fn validate(encoded: &str, base: u8) -> Result<(), DecodeError> {
+    if !is_valid_base(base) {
+        return Err(DecodeError::InvalidBase { base });
+    }
+
+    for i in 0..encoded.len() {
+        digit_value(&encoded.as_bytes()[i..], i, base)?;
+    }
+    Ok(())
+}
+
+/// Returns whether `encoded` only contains digits that are valid in `base`.
+///
+/// See [`validate`] for a version that reports why a string is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use base94::is_valid;
+///
+/// assert!(is_valid("A@#D9e@D9n9RRb6^", 94));
+/// assert!(!is_valid("A@#D9e@D9n9RRb6^", 10));
+/// assert!(!is_valid("héllo", 94));
+/// ```
+pub fn is_valid(encoded: &str, base: u8) -> bool {
+    validate(encoded, base).is_ok()
+}
+
+/// Decodes `encoded` like [`decode`](crate::decode), but reports every invalid character
+/// instead of stopping at the first, so all of them can be fixed at once.
+///
+/// The whole input is scanned before any numeric conversion, which only runs if the scan
+/// found no errors.
+///
+/// # Arguments
+///
+/// * `encoded` - The string to decode.
+/// * `base` - The base used for decoding. Must match the base used for encoding.
+///
+/// # Returns
+///
+/// The decoded bytes, or the errors in input order: one `DecodeError::InvalidCharacter` or
+/// `DecodeError::DigitOutOfRange` per bad character, with the byte offset of each. Scanning
+/// stops after [`MAX_COLLECTED_ERRORS`] errors. An invalid base is reported as a single
+/// `DecodeError::InvalidBase`.
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_collect_errors, DecodeError};
+///
+/// let errors = decode_collect_errors("!x#y%", 10).unwrap_err();
+/// assert_eq!(
+///     errors,
+///     [
+///         DecodeError::DigitOutOfRange { c: 'x', position: 1, base: 10 },
+///         DecodeError::DigitOutOfRange { c: 'y', position: 3, base: 10 },
+///     ]
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_collect_errors(encoded: &str, base: u8) -> Result<Vec<u8>, Vec<DecodeError>> {
+    if !is_valid_base(base) {
+        return Err(vec![DecodeError::InvalidBase { base }]);
+    }
+
+    let mut digits = Vec::with_capacity(encoded.len());
+    let mut errors = Vec::new();
+    // Stepping by character reports a multibyte character once, not once per byte.
+    for (i, _) in encoded.char_indices() {
+        match digit_value(&encoded.as_bytes()[i..], i, base) {
+            Ok(digit) => digits.push(digit),
+            Err(err) => {
+                errors.push(err);
+                if errors.len() == MAX_COLLECTED_ERRORS {
+                    break;
+                }
+            }
+        }
+    }
+
+    let result = if errors.is_empty() {
+        Ok(decode_digits(&digits, base))
+    } else {
+        Err(errors)
+    };
+    wipe(&mut digits);
+    result
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_validate_encoded() {
+        for base in 2..=MAX_BASE {
+            let encoded = encode(b"Hello, World!", base);
+            assert!(validate(&encoded, base).is_ok());
+            assert!(is_valid(&encoded, base));
+            assert!(is_valid("", base));
+        }
+    }
+
+    #[test]
+    fn test_validate_matches_decode_errors() {
+        for input in ["ab\u{7f}cd", "~~", " x", "0129"] {
+            for base in [0, 2, 10, 94, 95] {
+                let expected = decode(input, base).err().map(|err| err.to_string());
+                let actual = validate(input, base).err().map(|err| err.to_string());
+                assert_eq!(actual, expected);
+                assert_eq!(is_valid(input, base), expected.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn test_collect_errors_matches_decode() {
+        for base in 2..=MAX_BASE {
+            let data = (0..50).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+            let encoded = encode(&data, base);
+            assert_eq!(decode_collect_errors(&encoded, base).unwrap(), data);
+        }
+        assert_eq!(decode_collect_errors("", 94).unwrap(), b"");
+        assert_eq!(
+            decode_collect_errors("!!", 95),
+            Err(vec![DecodeError::InvalidBase { base: 95 }])
+        );
+    }
+
+    #[test]
+    fn test_collect_scattered_errors() {
+        let mut encoded = encode(&[0xab; 100], 94);
+        encoded.insert(90, ' ');
+        encoded.insert(40, '\u{e9}');
+        encoded.insert(3, '\u{7f}');
+        encoded.insert(0, '\u{2192}');
+        let errors = decode_collect_errors(&encoded, 94).unwrap_err();
+        assert_eq!(
+            errors,
+            [
+                DecodeError::InvalidCharacter {
+                    c: '\u{2192}',
+                    position: 0
+                },
+                DecodeError::InvalidCharacter {
+                    c: '\u{7f}',
+                    position: 6
+                },
+                DecodeError::InvalidCharacter {
+                    c: '\u{e9}',
+                    position: 44
+                },
+                DecodeError::InvalidCharacter {
+                    c: ' ',
+                    position: 96
+                },
+            ]
+        );
+        assert_eq!(errors[0], decode(&encoded, 94).unwrap_err());
+
+        let errors = decode_collect_errors("!a#b%c", 10).unwrap_err();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|err| match err {
+                    DecodeError::DigitOutOfRange { c, position, .. } => (*c, *position),
+                    err => panic!("unexpected error {:?}", err),
+                })
+                .collect::<Vec<_>>(),
+            [('a', 1), ('b', 3), ('c', 5)]
+        );
+    }
+
+    #[test]
+    fn test_collect_errors_is_capped() {
+        let errors = decode_collect_errors(&" ".repeat(10_000), 94).unwrap_err();
+        assert_eq!(errors.len(), MAX_COLLECTED_ERRORS);
+        assert_eq!(
+            errors.last(),
+            Some(&DecodeError::InvalidCharacter {
+                c: ' ',
+                position: MAX_COLLECTED_ERRORS - 1
+            })
+        );
+    }
+}
@@ -0,0 +1,421 @@
+//! Known-answer vectors that pin down the output of [`encode`](crate::encode).
+//!
+//! These outputs are part of the crate's semver guarantee: `encode` will produce exactly
+//! these strings, and `decode` will accept them, in every release until the next major
+//! version. Any change to the alphabet, digit order, or zero handling fails the tests
+//! below. Downstream crates can run the vectors against their own integrations, such as
+//! another implementation or a layer that stores encoded strings:
+//!
+//! ```
+//! use base94::{decode, encode, vectors::VECTORS};
+//!
+//! for vector in VECTORS {
+//!     assert_eq!(encode(vector.input, vector.base), vector.encoded, "{}", vector.name);
+//!     assert_eq!(decode(vector.encoded, vector.base).unwrap(), vector.input);
+//! }
+//! ```
+
+/// An input, a base, and the string that `encode` produces for them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Vector {
+    /// A short description of the input, for failure messages.
+    pub name: &'static str,
+    pub input: &'static [u8],
+    pub base: u8,
+    pub encoded: &'static str,
+}
+
+/// 1024 pseudo-random bytes: the low byte of each successive state of the xorshift64
+/// generator with shifts 13, 7, and 17, seeded with `0x9e3779b97f4a7c15`, so the input can
+/// be reproduced without this crate.
+pub static RANDOM_1K: [u8; 1024] = xorshift_bytes(0x9e37_79b9_7f4a_7c15);
+
+/// Returns the low bytes of the first `N` xorshift64 states after `seed`.
+const fn xorshift_bytes<const N: usize>(seed: u64) -> [u8; N] {
+    let mut bytes = [0; N];
+    let mut state = seed;
+    let mut i = 0;
+    while i < N {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        bytes[i] = state as u8;
+        i += 1;
+    }
+    bytes
+}
+
+/// Every vector, for the bases 2, 10, 16, 50, and 94.
+pub static VECTORS: &[Vector] = &[
+    Vector {
+        name: "empty",
+        input: b"",
+        base: 2,
+        encoded: "",
+    },
+    Vector {
+        name: "empty",
+        input: b"",
+        base: 10,
+        encoded: "",
+    },
+    Vector {
+        name: "empty",
+        input: b"",
+        base: 16,
+        encoded: "",
+    },
+    Vector {
+        name: "empty",
+        input: b"",
+        base: 50,
+        encoded: "",
+    },
+    Vector {
+        name: "empty",
+        input: b"",
+        base: 94,
+        encoded: "",
+    },
+    Vector {
+        name: "zero byte",
+        input: &[0x00],
+        base: 2,
+        encoded: "!",
+    },
+    Vector {
+        name: "zero byte",
+        input: &[0x00],
+        base: 10,
+        encoded: "!",
+    },
+    Vector {
+        name: "zero byte",
+        input: &[0x00],
+        base: 16,
+        encoded: "!",
+    },
+    Vector {
+        name: "zero byte",
+        input: &[0x00],
+        base: 50,
+        encoded: "!",
+    },
+    Vector {
+        name: "zero byte",
+        input: &[0x00],
+        base: 94,
+        encoded: "!",
+    },
+    Vector {
+        name: "one byte",
+        input: &[0x01],
+        base: 2,
+        encoded: "\"",
+    },
+    Vector {
+        name: "one byte",
+        input: &[0x01],
+        base: 10,
+        encoded: "\"",
+    },
+    Vector {
+        name: "one byte",
+        input: &[0x01],
+        base: 16,
+        encoded: "\"",
+    },
+    Vector {
+        name: "one byte",
+        input: &[0x01],
+        base: 50,
+        encoded: "\"",
+    },
+    Vector {
+        name: "one byte",
+        input: &[0x01],
+        base: 94,
+        encoded: "\"",
+    },
+    Vector {
+        name: "high byte",
+        input: &[0xff],
+        base: 2,
+        encoded: "\"\"\"\"\"\"\"\"",
+    },
+    Vector {
+        name: "high byte",
+        input: &[0xff],
+        base: 10,
+        encoded: "&&#",
+    },
+    Vector {
+        name: "high byte",
+        input: &[0xff],
+        base: 16,
+        encoded: "00",
+    },
+    Vector {
+        name: "high byte",
+        input: &[0xff],
+        base: 50,
+        encoded: "&&",
+    },
+    Vector {
+        name: "high byte",
+        input: &[0xff],
+        base: 94,
+        encoded: "d#",
+    },
+    Vector {
+        name: "0xff run",
+        input: &[0xff; 16],
+        base: 2,
+        encoded: "\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"\"",
+    },
+    Vector {
+        name: "0xff run",
+        input: &[0xff; 16],
+        base: 10,
+        encoded: "&&%\"\"#)'(\"$%(!'%($$'%$'%)$*!#*''$#)#!%$",
+    },
+    Vector {
+        name: "0xff run",
+        input: &[0xff; 16],
+        base: 16,
+        encoded: "00000000000000000000000000000000",
+    },
+    Vector {
+        name: "0xff run",
+        input: &[0xff; 16],
+        base: 50,
+        encoded: "&>CNA@:PHP/2O=00DH?*@./",
+    },
+    Vector {
+        name: "0xff run",
+        input: &[0xff; 16],
+        base: 94,
+        encoded: ".,q**vef7&i;h;WQ~H#,",
+    },
+    Vector {
+        name: "zero run",
+        input: &[0x00; 4],
+        base: 2,
+        encoded: "!!!!",
+    },
+    Vector {
+        name: "zero run",
+        input: &[0x00; 4],
+        base: 10,
+        encoded: "!!!!",
+    },
+    Vector {
+        name: "zero run",
+        input: &[0x00; 4],
+        base: 16,
+        encoded: "!!!!",
+    },
+    Vector {
+        name: "zero run",
+        input: &[0x00; 4],
+        base: 50,
+        encoded: "!!!!",
+    },
+    Vector {
+        name: "zero run",
+        input: &[0x00; 4],
+        base: 94,
+        encoded: "!!!!",
+    },
+    Vector {
+        name: "inner zeros",
+        input: &[0x01, 0x00, 0x00, 0x02],
+        base: 2,
+        encoded: "\"!!!!!!!!!!!!!!!!!!!!!!!!\"",
+    },
+    Vector {
+        name: "inner zeros",
+        input: &[0x01, 0x00, 0x00, 0x02],
+        base: 10,
+        encoded: "$$%%&&$$",
+    },
+    Vector {
+        name: "inner zeros",
+        input: &[0x01, 0x00, 0x00, 0x02],
+        base: 16,
+        encoded: "\"!!!!!#",
+    },
+    Vector {
+        name: "inner zeros",
+        input: &[0x01, 0x00, 0x00, 0x02],
+        base: 50,
+        encoded: "BG63&",
+    },
+    Vector {
+        name: "inner zeros",
+        input: &[0x01, 0x00, 0x00, 0x02],
+        base: 94,
+        encoded: "&MFI",
+    },
+    Vector {
+        name: "leading zeros",
+        input: &[0x00, 0x00, 0x2a],
+        base: 2,
+        encoded: "!!!!!!!!!!!!!!!!!\"!\"!\"",
+    },
+    Vector {
+        name: "leading zeros",
+        input: &[0x00, 0x00, 0x2a],
+        base: 10,
+        encoded: "#\"&#&(#",
+    },
+    Vector {
+        name: "leading zeros",
+        input: &[0x00, 0x00, 0x2a],
+        base: 16,
+        encoded: "!!!!+#",
+    },
+    Vector {
+        name: "leading zeros",
+        input: &[0x00, 0x00, 0x2a],
+        base: 50,
+        encoded: "-!\"7",
+    },
+    Vector {
+        name: "leading zeros",
+        input: &[0x00, 0x00, 0x2a],
+        base: 94,
+        encoded: "%Q>$",
+    },
+    Vector {
+        name: "trailing zeros",
+        input: &[0x2a, 0x00, 0x00],
+        base: 2,
+        encoded: "!\"!\"!\"!!",
+    },
+    Vector {
+        name: "trailing zeros",
+        input: &[0x2a, 0x00, 0x00],
+        base: 10,
+        encoded: "#%!!",
+    },
+    Vector {
+        name: "trailing zeros",
+        input: &[0x2a, 0x00, 0x00],
+        base: 16,
+        encoded: "+#!!",
+    },
+    Vector {
+        name: "trailing zeros",
+        input: &[0x2a, 0x00, 0x00],
+        base: 50,
+        encoded: "K!!",
+    },
+    Vector {
+        name: "trailing zeros",
+        input: &[0x2a, 0x00, 0x00],
+        base: 94,
+        encoded: "K!!",
+    },
+    Vector {
+        name: "text",
+        input: b"Hello, World!",
+        base: 2,
+        encoded: "!!!\"!!\"!\"!\"!!\"\"!!!\"\"!\"\"!!!\"\"!\"\"!\"\"\"\"!\"\"!!!\"\"!\"!!!!!!!\"!!\"\"\"!\"!\"!\"\"\"\"!\"\"!!\"!!\"\"\"!!!\"\"!\"\"!!!\"!!\"\"!\"!!!!\"",
+    },
+    Vector {
+        name: "text",
+        input: b"Hello, World!",
+        base: 10,
+        encoded: "%)*!$)($#*'%$$(\"#!&%$)'*)!'&%'#",
+    },
+    Vector {
+        name: "text",
+        input: b"Hello, World!",
+        base: 16,
+        encoded: ")%&'-'-'0'-#!#(&0'#(-'%'\"#",
+    },
+    Vector {
+        name: "text",
+        input: b"Hello, World!",
+        base: 50,
+        encoded: "C4A#=\"K3'\"$2CE0JBC",
+    },
+    Vector {
+        name: "text",
+        input: b"Hello, World!",
+        base: 94,
+        encoded: "wFLskRz7:'I\\W,b'",
+    },
+    Vector {
+        name: "1 KiB random",
+        input: &RANDOM_1K,
+        base: 2,
+        encoded: include_str!("vectors/random_1k_base2.txt"),
+    },
+    Vector {
+        name: "1 KiB random",
+        input: &RANDOM_1K,
+        base: 10,
+        encoded: include_str!("vectors/random_1k_base10.txt"),
+    },
+    Vector {
+        name: "1 KiB random",
+        input: &RANDOM_1K,
+        base: 16,
+        encoded: include_str!("vectors/random_1k_base16.txt"),
+    },
+    Vector {
+        name: "1 KiB random",
+        input: &RANDOM_1K,
+        base: 50,
+        encoded: include_str!("vectors/random_1k_base50.txt"),
+    },
+    Vector {
+        name: "1 KiB random",
+        input: &RANDOM_1K,
+        base: 94,
+        encoded: include_str!("vectors/random_1k_base94.txt"),
+    },
+];
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+
+    #[test]
+    fn test_encode_matches_vectors() {
+        for vector in VECTORS {
+            assert_eq!(
+                encode(vector.input, vector.base),
+                vector.encoded,
+                "{} in base {}",
+                vector.name,
+                vector.base
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_matches_vectors() {
+        for vector in VECTORS {
+            assert_eq!(
+                decode(vector.encoded, vector.base).unwrap(),
+                vector.input,
+                "{} in base {}",
+                vector.name,
+                vector.base
+            );
+        }
+    }
+
+    #[test]
+    fn test_random_input_is_fixed() {
+        assert_eq!(
+            RANDOM_1K[..8],
+            [0xad, 0x76, 0x36, 0x74, 0xec, 0x79, 0xcf, 0xea]
+        );
+        assert_eq!(VECTORS.len(), 55);
+    }
+}
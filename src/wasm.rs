@@ -0,0 +1,75 @@
+//! JavaScript bindings to [`encode`](crate::encode) and [`decode`](crate::decode), built with
+//! `wasm-bindgen`.
+//!
+//! Build the crate for `wasm32-unknown-unknown` with `--no-default-features --features wasm`
+//! and run `wasm-bindgen` or `wasm-pack` on the result. The module exports `encode` and
+//! `decode`:
+//!
+//! ```js
+//! import { encode, decode } from "base94";
+//!
+//! const text = encode(new TextEncoder().encode("Hello"), 94);
+//! try {
+//!     decode("Hel lo", 94);
+//! } catch (error) {
+//!     console.log(error.message, error.code, error.position); // ..., 1, 3
+//! }
+//! ```
+//!
+//! Errors are thrown as JavaScript `Error`s whose message is the Rust error message, with a
+//! `code` property holding the [`DecodeError::code`] and a `position` property holding the
+//! [`DecodeError::position`], or `undefined` for errors without one. Positions are byte
+//! offsets into the UTF-8 form of the input, which equal string indices when every character
+//! before the error is ASCII, as it is for the first invalid character of plain input.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use js_sys::{Error, Reflect};
+use wasm_bindgen::prelude::*;
+
+use crate::{decode_bytes, try_encode, DecodeError};
+
+/// Converts `err` into a JavaScript `Error` with `code` and `position` properties.
+fn js_error(err: &DecodeError) -> JsValue {
+    let error = Error::new(&err.to_string());
+    let position = err
+        .position()
+        .map_or(JsValue::UNDEFINED, |position| (position as f64).into());
+    // Setting a property on a fresh `Error` object cannot fail.
+    let _ = Reflect::set(&error, &"code".into(), &err.code().into());
+    let _ = Reflect::set(&error, &"position".into(), &position);
+    error.into()
+}
+
+/// Encodes `data` in `base`.
+///
+/// # Arguments
+///
+/// * `data` - The bytes to encode, a `Uint8Array` on the JavaScript side.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+///
+/// # Returns
+///
+/// The encoded string, or an `Error` with the code of `DecodeError::InvalidBase` if the base
+/// is outside the valid range.
+#[wasm_bindgen]
+pub fn encode(data: &[u8], base: u8) -> Result<String, JsValue> {
+    try_encode(data, base).map_err(|_| js_error(&DecodeError::InvalidBase { base }))
+}
+
+/// Decodes `encoded` in `base`.
+///
+/// # Arguments
+///
+/// * `encoded` - The encoded string.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+///
+/// # Returns
+///
+/// The decoded bytes as a `Uint8Array`, or an `Error` with `code` and `position` properties
+/// describing the decode error.
+#[wasm_bindgen]
+pub fn decode(encoded: &str, base: u8) -> Result<Vec<u8>, JsValue> {
+    decode_bytes(encoded.as_bytes(), base).map_err(|err| js_error(&err))
+}
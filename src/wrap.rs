@@ -0,0 +1,119 @@
+//! Line wrapping of encoded output for channels that limit line length.
+
+use alloc::string::String;
+
+use crate::encode;
+
+/// The line break inserted by [`encode_wrapped`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// `\n`, as used on Unix and in git.
+    #[default]
+    Lf,
+    /// `\r\n`, as required by MIME and most network protocols.
+    CrLf,
+}
+
+impl LineEnding {
+    /// Returns the characters of this line ending.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Encodes a slice of bytes like [`encode`], inserting `line_ending` after every `width`
+/// characters.
+///
+/// The last line does not end with a line break; append [`LineEnding::as_str`] if the
+/// channel expects one. [`decode_ignoring_whitespace`](crate::decode_ignoring_whitespace)
+/// reads the output back with either line ending.
+///
+/// # Arguments
+///
+/// * `data` - A slice of bytes representing the data to be encoded.
+/// * `base` - The base used for encoding. Must be between 2 and 94 (inclusive).
+/// * `width` - The number of characters per line, or 0 for a single line.
+/// * `line_ending` - The line break to insert.
+///
+/// # Panics
+///
+/// This function panics if the specified base is outside the valid range (2 to 94).
+///
+/// # Examples
+///
+/// ```
+/// use base94::{decode_ignoring_whitespace, encode_wrapped, LineEnding};
+///
+/// let data = [0xff; 100];
+/// let wrapped = encode_wrapped(&data, 94, 76, LineEnding::CrLf);
+/// assert!(wrapped.lines().all(|line| line.len() <= 76));
+/// assert_eq!(decode_ignoring_whitespace(&wrapped, 94).unwrap(), data);
+/// ```
+pub fn encode_wrapped(data: &[u8], base: u8, width: usize, line_ending: LineEnding) -> String {
+    wrap(&encode(data, base), width, line_ending)
+}
+
+/// Inserts `line_ending` after every `width` characters of the ASCII string `text`, or
+/// returns it unchanged if `width` is 0.
+fn wrap(text: &str, width: usize, line_ending: LineEnding) -> String {
+    if width == 0 {
+        return text.into();
+    }
+
+    let breaks = text.len().saturating_sub(1) / width;
+    let mut out = String::with_capacity(text.len() + breaks * line_ending.as_str().len());
+    for (i, line) in text.as_bytes().chunks(width).enumerate() {
+        if i > 0 {
+            out.push_str(line_ending.as_str());
+        }
+        out.push_str(core::str::from_utf8(line).expect("encoded strings are ASCII"));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode_ignoring_whitespace;
+
+    const MAX_BASE: u8 = 94;
+
+    #[test]
+    fn test_round_trip() {
+        let data = (0..200).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+        for base in 2..=MAX_BASE {
+            let encoded = encode(&data, base);
+            for width in [1, 64, 76, encoded.len() + 1] {
+                for line_ending in [LineEnding::Lf, LineEnding::CrLf] {
+                    let wrapped = encode_wrapped(&data, base, width, line_ending);
+                    assert!(!wrapped.ends_with(['\r', '\n']));
+                    assert_eq!(wrapped.lines().collect::<String>(), encoded);
+                    assert!(wrapped.lines().all(|line| line.len() <= width));
+                    assert_eq!(wrapped.lines().count(), encoded.len().div_ceil(width));
+                    assert_eq!(decode_ignoring_whitespace(&wrapped, base).unwrap(), data);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_edge_widths() {
+        let encoded = encode(b"Hello, World!", 94);
+        assert_eq!(
+            encode_wrapped(b"Hello, World!", 94, 0, LineEnding::CrLf),
+            encoded
+        );
+        assert_eq!(
+            encode_wrapped(b"Hello, World!", 94, encoded.len(), LineEnding::Lf),
+            encoded
+        );
+        assert_eq!(encode_wrapped(b"", 94, 1, LineEnding::Lf), "");
+        assert_eq!(
+            encode_wrapped(&[0; 3], 94, 1, LineEnding::CrLf),
+            "!\r\n!\r\n!"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Allocation counts and peak heap use of encoding and decoding, measured with dhat.
+//!
+//! Run with:
+//!
+//! ```sh
+//! cargo test --release --features dhat-heap --test allocations -- --ignored --nocapture
+//! ```
+//!
+//! Each case prints the number of allocations it made, the bytes they added up to, and the
+//! most bytes that were live at once, on top of what was live before. The assertions use
+//! generous limits, about four times the measured values, so they only fail on real
+//! regressions such as an extra allocation per digit.
+//!
+//! The `num` backend allocates on every `BigUint` operation, millions of times for 1 MB,
+//! while the native backend allocates its buffers up front; measure the native backend with
+//! `--no-default-features --features dhat-heap,std,backend-native`. Converting 1 MB as a
+//! single number takes minutes with either backend under dhat, so the test is also ignored
+//! by default and `--all-features` runs stay fast.
+
+#![cfg(feature = "dhat-heap")]
+
+use base94::{decode, decode_chunked, encode, encode_chunked, encode_into};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+#[global_allocator]
+static ALLOCATOR: dhat::Alloc = dhat::Alloc;
+
+/// Allocation statistics of one measured operation.
+struct Usage {
+    blocks: u64,
+    bytes: u64,
+    peak_bytes: usize,
+}
+
+/// Runs `f` under a fresh profiler and returns what it allocated, excluding `input`.
+fn measure<T, R>(name: &str, input: &T, f: impl FnOnce(&T) -> R) -> Usage {
+    let profiler = dhat::Profiler::builder().testing().build();
+    let before = dhat::HeapStats::get();
+    let result = f(input);
+    let after = dhat::HeapStats::get();
+    drop(result);
+    drop(profiler);
+
+    let usage = Usage {
+        blocks: after.total_blocks - before.total_blocks,
+        bytes: after.total_bytes - before.total_bytes,
+        peak_bytes: after.max_bytes - before.curr_bytes,
+    };
+    println!(
+        "{:<24} {:>8} allocations {:>12} bytes {:>10} peak bytes",
+        name, usage.blocks, usage.bytes, usage.peak_bytes
+    );
+    usage
+}
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0; len];
+    StdRng::seed_from_u64(94).fill_bytes(&mut bytes);
+    bytes
+}
+
+/// Whether the crate was built with the native backend, which allocates its buffers up
+/// front instead of on every `BigUint` operation.
+const NATIVE: bool = cfg!(any(
+    feature = "backend-native",
+    feature = "zeroize",
+    not(feature = "backend-num")
+));
+
+/// Checks `usage` against the limits of the selected backend, given as
+/// `(allocations, peak bytes)` for the `num` and the native backend.
+fn check(usage: Usage, num: (u64, usize), native: (u64, usize)) {
+    let (blocks, peak_bytes) = if NATIVE { native } else { num };
+    assert!(usage.blocks <= blocks, "{} allocations", usage.blocks);
+    assert!(
+        usage.peak_bytes <= peak_bytes,
+        "{} peak bytes",
+        usage.peak_bytes
+    );
+}
+
+/// dhat allows one profiler at a time, so every case runs in this one test.
+#[test]
+#[ignore = "takes minutes; run with --ignored"]
+fn test_allocations() {
+    const MB: usize = 1_000_000;
+
+    let small = random_bytes(1_000);
+    let large = random_bytes(MB);
+    let small_encoded = encode(&small, 94);
+    let large_encoded = encode(&large, 94);
+    let chunked = encode_chunked(&large, 94, 32);
+
+    let usage = measure("encode 1 KB", &small, |data| encode(data, 94));
+    check(usage, (15_000, 30_000), (20, 10_000));
+
+    let usage = measure("decode 1 KB", &small_encoded, |encoded| decode(encoded, 94));
+    check(usage, (200, 20_000), (20, 15_000));
+
+    let mut buffer = String::with_capacity(2 * small_encoded.len());
+    let usage = measure("encode_into 1 KB", &small, |data| {
+        buffer.clear();
+        encode_into(data, 94, &mut buffer)
+    });
+    check(usage, (15_000, 30_000), (20, 10_000));
+
+    let usage = measure("encode 1 MB", &large, |data| encode(data, 94));
+    check(usage, (30_000_000, 50 * MB), (20, 10 * MB));
+
+    let usage = measure("decode 1 MB", &large_encoded, |encoded| decode(encoded, 94));
+    check(usage, (4_000_000, 30 * MB), (20, 15 * MB));
+
+    let usage = measure("encode_chunked 1 MB", &large, |data| {
+        encode_chunked(data, 94, 32)
+    });
+    check(usage, (16_000_000, 5 * MB), (250_000, 5 * MB));
+
+    let usage = measure("decode_chunked 1 MB", &chunked, |encoded| {
+        decode_chunked(encoded, 94, 32)
+    });
+    check(usage, (250_000, 9 * MB), (250_000, 9 * MB));
+}
@@ -0,0 +1,851 @@
+//! End-to-end tests of the `base94cli` binary with piped standard input and output.
+
+#![cfg(feature = "cli")]
+
+use std::fs;
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use base94::{encode, encode_chunked, STREAM_CHUNK_SIZE};
+use sha2::{Digest, Sha256};
+
+/// Binary data that newline translation or text-mode I/O would corrupt.
+const BINARY: &[u8] = b"\x00\r\n\x1a\xff\xfe\nline\r\n\x00";
+
+fn cli() -> Command {
+    Command::cargo_bin("base94cli").unwrap()
+}
+
+/// The output of `encode` without `--whole-file`.
+fn chunked(data: &[u8], base: u8) -> String {
+    encode_chunked(data, base, STREAM_CHUNK_SIZE)
+}
+
+/// A path in the temporary directory that no other test uses.
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("base94-cli-{}-{}", rand::random::<u64>(), name))
+}
+
+#[test]
+fn test_encode_stdin_to_stdout() {
+    for args in [&["encode"][..], &["encode", "-"], &["encode", "-", "-"]] {
+        let output = cli().args(args).write_stdin(BINARY).output().unwrap();
+        assert!(output.status.success());
+        // A pipe gets no trailing newline.
+        assert_eq!(output.stdout, chunked(BINARY, 94).as_bytes());
+    }
+}
+
+#[test]
+fn test_newline_flag() {
+    let output = cli()
+        .args(["encode", "--newline", "--base", "16"])
+        .write_stdin(BINARY)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        output.stdout,
+        format!("{}\n", chunked(BINARY, 16)).as_bytes()
+    );
+}
+
+#[test]
+fn test_decode_stdin_to_stdout() {
+    for base in [2, 10, 64, 94] {
+        let output = cli()
+            .args(["decode", "-", "-", "--base", &base.to_string()])
+            .write_stdin(chunked(BINARY, base))
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, BINARY);
+    }
+
+    // The newline that `--newline` or a terminal adds does not get in the way.
+    cli()
+        .arg("decode")
+        .write_stdin(format!("{}\n", chunked(BINARY, 94)))
+        .assert()
+        .success()
+        .stdout(BINARY);
+}
+
+#[test]
+fn test_pipeline_round_trip() {
+    let data = (0..2000).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+    let encoded = cli()
+        .arg("encode")
+        .write_stdin(data.clone())
+        .output()
+        .unwrap();
+    assert!(encoded.status.success());
+    cli()
+        .arg("decode")
+        .write_stdin(encoded.stdout)
+        .assert()
+        .success()
+        .stdout(data);
+}
+
+#[test]
+fn test_mixed_files_and_stdio() {
+    let input = temp_path("input");
+    fs::write(&input, BINARY).unwrap();
+    cli()
+        .args(["encode".as_ref(), input.as_os_str()])
+        .assert()
+        .success()
+        .stdout(chunked(BINARY, 94));
+    fs::remove_file(&input).unwrap();
+
+    let output = temp_path("output");
+    cli()
+        .args(["decode".as_ref(), "-".as_ref(), output.as_os_str()])
+        .write_stdin(chunked(BINARY, 94))
+        .assert()
+        .success()
+        .stdout("");
+    assert_eq!(fs::read(&output).unwrap(), BINARY);
+    fs::remove_file(&output).unwrap();
+}
+
+#[test]
+fn test_decode_error() {
+    let output = cli()
+        .args(["decode", "--format", "plain"])
+        .write_stdin("ab c")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("error: "), "{}", stderr);
+}
+
+#[test]
+fn test_wrap() {
+    let data = (0..500).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+    let encoded = chunked(&data, 94);
+    for (args, line_ending) in [
+        (&["encode", "-w", "76"][..], "\n"),
+        (&["encode", "--wrap", "10", "--crlf"], "\r\n"),
+    ] {
+        let output = cli().args(args).write_stdin(data.clone()).output().unwrap();
+        assert!(output.status.success());
+        let text = String::from_utf8(output.stdout).unwrap();
+        let width = args[2].parse::<usize>().unwrap();
+        let lines = text.split(line_ending).collect::<Vec<&str>>();
+        assert!(lines
+            .iter()
+            .all(|line| line.len() <= width && !line.contains(['\r', '\n'])));
+        assert!(lines[..lines.len() - 1]
+            .iter()
+            .all(|line| line.len() == width));
+        // The last line has no line break after it.
+        assert!(!lines.last().unwrap().is_empty());
+        assert_eq!(lines.concat(), encoded);
+
+        for format in ["auto", "plain"] {
+            cli()
+                .args(["decode", "--format", format])
+                .write_stdin(text.clone())
+                .assert()
+                .success()
+                .stdout(data.clone());
+        }
+    }
+}
+
+#[test]
+fn test_wrap_with_newline() {
+    cli()
+        .args([
+            "encode",
+            "--wrap",
+            "4",
+            "--crlf",
+            "--newline",
+            "--base",
+            "16",
+        ])
+        .write_stdin("Hi!")
+        .assert()
+        .success()
+        .stdout(format!(
+            "{}\r\n",
+            chunked(b"Hi!", 16)
+                .as_bytes()
+                .chunks(4)
+                .map(|line| std::str::from_utf8(line).unwrap())
+                .collect::<Vec<&str>>()
+                .join("\r\n")
+        ));
+}
+
+#[test]
+fn test_wrap_rejects_zero() {
+    let output = cli()
+        .args(["encode", "--wrap", "0"])
+        .write_stdin("Hi")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+/// The data encoded in `tests/fixtures/quoted.txt`, by `encode --base 10 --wrap 24` and then
+/// quoted with line numbers.
+const QUOTED_DATA: &[u8] = b"The quick brown fox jumps over the lazy dog.";
+
+fn fixture(name: &str) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "tests", "fixtures", name]
+        .iter()
+        .collect()
+}
+
+#[test]
+fn test_ignore_garbage() {
+    let quoted = fixture("quoted.txt");
+    cli()
+        .args([
+            "decode".as_ref(),
+            "--base".as_ref(),
+            "10".as_ref(),
+            quoted.as_os_str(),
+        ])
+        .assert()
+        .failure();
+
+    let output = cli()
+        .args([
+            "decode".as_ref(),
+            "-b".as_ref(),
+            "10".as_ref(),
+            "-i".as_ref(),
+            quoted.as_os_str(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(output.stdout, QUOTED_DATA);
+    // Without `--verbose`, nothing is reported.
+    assert!(output.stderr.is_empty());
+
+    // `--whole-file` reads the format of `encode` through `decode_lossy`.
+    let quoted = format!(
+        "> {}\n> {}",
+        &encode(QUOTED_DATA, 10)[..30],
+        &encode(QUOTED_DATA, 10)[30..]
+    );
+    cli()
+        .args(["decode", "-b", "10", "-i", "--whole-file"])
+        .write_stdin(quoted)
+        .assert()
+        .success()
+        .stdout(QUOTED_DATA);
+}
+
+#[test]
+fn test_ignore_garbage_verbose() {
+    let text = fs::read_to_string(fixture("quoted.txt")).unwrap();
+    let output = cli()
+        .args(["decode", "--base", "10", "--ignore-garbage", "--verbose"])
+        .write_stdin(text.clone())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(output.stdout, QUOTED_DATA);
+    let garbage = text
+        .trim()
+        .chars()
+        .filter(|&c| !('!'..='*').contains(&c) && c != '\n')
+        .count();
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        format!(
+            "skipped {} characters that are not digits in base 10\n",
+            garbage
+        )
+    );
+}
+
+#[test]
+fn test_ignore_garbage_without_digits() {
+    let output = cli()
+        .args(["decode", "--base", "10", "--ignore-garbage"])
+        .write_stdin("> no digits here\n")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+
+    // Input that is all digits of the base decodes as usual.
+    cli()
+        .args(["decode", "--ignore-garbage"])
+        .write_stdin(chunked(BINARY, 94))
+        .assert()
+        .success()
+        .stdout(BINARY);
+}
+
+#[test]
+fn test_ignore_garbage_rejects_containers() {
+    cli()
+        .args(["decode", "--ignore-garbage", "--format", "tagged"])
+        .write_stdin("~094!!")
+        .assert()
+        .failure();
+}
+
+/// The data encoded in base 94 in `bom.txt`, `crlf.txt`, and `mixed.txt`.
+const WINDOWS_DATA: &[u8] = b"Saved by Notepad on Windows.";
+
+#[test]
+fn test_windows_files() {
+    // `bom.txt` is one line after a byte order mark, `crlf.txt` is wrapped with `\r\n`, and
+    // `mixed.txt` has a byte order mark and `\r\n`, `\n`, and lone `\r` line endings.
+    for name in ["bom.txt", "crlf.txt", "mixed.txt"] {
+        let path = fixture(name);
+        for format in ["auto", "plain"] {
+            cli()
+                .args([
+                    "decode".as_ref(),
+                    "--format".as_ref(),
+                    format.as_ref(),
+                    path.as_os_str(),
+                ])
+                .assert()
+                .success()
+                .stdout(WINDOWS_DATA);
+        }
+        cli()
+            .arg("decode")
+            .write_stdin(fs::read(&path).unwrap())
+            .assert()
+            .success()
+            .stdout(WINDOWS_DATA);
+    }
+}
+
+#[test]
+fn test_strict() {
+    for (name, expected) in [
+        ("bom.txt", "Invalid character '\\u{feff}' at position 0"),
+        ("crlf.txt", "Invalid character '\\r' at position 10"),
+        ("mixed.txt", "Invalid character '\\u{feff}' at position 0"),
+    ] {
+        let output = cli()
+            .args([
+                "decode".as_ref(),
+                "--strict".as_ref(),
+                fixture(name).as_os_str(),
+            ])
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        assert!(output.stdout.is_empty());
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains(expected), "{}: {}", name, stderr);
+    }
+
+    // Clean input decodes the same either way.
+    cli()
+        .args(["decode", "--strict"])
+        .write_stdin(chunked(BINARY, 94))
+        .assert()
+        .success()
+        .stdout(BINARY);
+}
+
+#[test]
+fn test_error_positions_count_skipped_bytes() {
+    // 'é' is at byte 25, on line 3 after a byte order mark and both kinds of line break.
+    let input = "\u{feff}!!!!!!!!!!\r\n!!!!!!\n!!!\u{e9}!!!\n";
+    for (args, expected) in [
+        (
+            &["decode"][..],
+            "Invalid character '\u{e9}' at position 25 (line 3, column 4)\n  !!!\u{e9}!!!\n     ^",
+        ),
+        (
+            &["decode", "--whole-file"],
+            "Invalid character '\u{e9}' at position 25 (line 3, column 4)\n  !!!\u{e9}!!!\n     ^",
+        ),
+    ] {
+        let output = cli().args(args).write_stdin(input).output().unwrap();
+        assert!(!output.status.success());
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains(expected), "{:?}: {}", args, stderr);
+    }
+
+    // Errors far into a stream are found in the input the decoder has read recently.
+    let data = (0..200_000)
+        .map(|_| rand::random::<u8>())
+        .collect::<Vec<u8>>();
+    let output = cli()
+        .args(["encode", "--wrap", "76", "--crlf"])
+        .write_stdin(data)
+        .output()
+        .unwrap();
+    let mut input = String::from_utf8(output.stdout).unwrap();
+    let position = input.len() - 1000 + input[input.len() - 1000..].find('\n').unwrap() + 10;
+    input.replace_range(position..position + 1, " ");
+    let line_start = input[..position].rfind('\n').unwrap() + 1;
+    let expected = format!(
+        "Invalid character ' ' at position {} (line {}, column 10)\n  {}...\n  {:9}^",
+        position,
+        input[..position].matches('\n').count() + 1,
+        &input[line_start..line_start + 61],
+        ""
+    );
+    let output = cli().arg("decode").write_stdin(input).output().unwrap();
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        format!("error: {}\n", expected)
+    );
+
+    // A block that overflows is reported where it starts in the input.
+    let input = format!(
+        "{}\n{}\r\n{}",
+        "!".repeat(20),
+        "!".repeat(20),
+        "~".repeat(40)
+    );
+    let output = cli().arg("decode").write_stdin(input).output().unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Block starting at position 43"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_base_warning_when_streaming() {
+    let data = (0..1000).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+    let output = cli()
+        .args(["decode", "--base", "94"])
+        .write_stdin(chunked(&data, 16))
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.starts_with("warning: the input only uses digits of base 16, but the base is 94"),
+        "{}",
+        stderr
+    );
+
+    let output = cli()
+        .args(["decode", "--base", "10", "--wrap", "1"])
+        .write_stdin(chunked(&data, 94))
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.starts_with("warning: the input uses digits of base"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_whole_file() {
+    for base in [2, 10, 94] {
+        let data = (0..300).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+        let base = base.to_string();
+        let output = cli()
+            .args(["encode", "--whole-file", "--base", &base])
+            .write_stdin(data.clone())
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(
+            output.stdout,
+            encode(&data, base.parse().unwrap()).as_bytes()
+        );
+
+        cli()
+            .args(["decode", "--whole-file", "--base", &base])
+            .write_stdin(output.stdout)
+            .assert()
+            .success()
+            .stdout(data);
+    }
+}
+
+#[test]
+fn test_auto_detects_containers_in_stream() {
+    cli()
+        .arg("decode")
+        .write_stdin(format!("\n{}\n", base94::encode_tagged(BINARY, 16)))
+        .assert()
+        .success()
+        .stdout(BINARY);
+    cli()
+        .arg("decode")
+        .write_stdin(base94::encode_armored(BINARY, 50, "DATA"))
+        .assert()
+        .success()
+        .stdout(BINARY);
+}
+
+#[test]
+fn test_plain_text_that_looks_tagged() {
+    // A block whose lowest base 94 digits are 93, 15, 16 and 17, so it encodes to `~012...`.
+    let mut block = [0; 32];
+    block[..4].copy_from_slice(&(93 + 15 * 94 + 16 * 94 * 94 + 17 * 94 * 94 * 94u32).to_le_bytes());
+    let data = [&block[..], BINARY].concat();
+    let encoded = cli()
+        .arg("encode")
+        .write_stdin(data.clone())
+        .output()
+        .unwrap();
+    assert!(encoded.stdout.starts_with(b"~012"));
+    cli()
+        .arg("decode")
+        .write_stdin(encoded.stdout)
+        .assert()
+        .success()
+        .stdout(data);
+
+    let encoded = cli()
+        .args(["encode", "--whole-file"])
+        .write_stdin(block)
+        .output()
+        .unwrap();
+    assert!(encoded.stdout.starts_with(b"~012"));
+    cli()
+        .args(["decode", "--whole-file"])
+        .write_stdin(encoded.stdout)
+        .assert()
+        .success()
+        .stdout(block.to_vec());
+}
+
+#[test]
+fn test_small_buffer_size() {
+    let data = (0..1000).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+    let output = cli()
+        .args(["encode", "--buffer-size", "1", "--wrap", "7"])
+        .write_stdin(data.clone())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout.clone())
+            .unwrap()
+            .replace('\n', ""),
+        chunked(&data, 94)
+    );
+    cli()
+        .args(["decode", "--buffer-size", "1"])
+        .write_stdin(output.stdout)
+        .assert()
+        .success()
+        .stdout(data);
+
+    cli()
+        .args(["encode", "--buffer-size", "0"])
+        .write_stdin("Hi")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_small_buffer_size_with_containers() {
+    let armored = format!(
+        "Here is the backup key:\n\n{}",
+        base94::encode_armored(BINARY, 50, "DATA")
+    );
+    let tagged = format!("\n{}\n", base94::encode_tagged(BINARY, 16));
+    for input in [armored, tagged] {
+        let path = temp_path("container.txt");
+        fs::write(&path, &input).unwrap();
+        for size in ["1", "2", "8"] {
+            cli()
+                .args(["decode", "--buffer-size", size])
+                .arg(&path)
+                .assert()
+                .success()
+                .stdout(BINARY);
+            cli()
+                .args(["decode", "--buffer-size", size])
+                .write_stdin(input.clone())
+                .assert()
+                .success()
+                .stdout(BINARY);
+        }
+        fs::remove_file(path).unwrap();
+    }
+
+    // A byte order mark is found however little is read at a time.
+    cli()
+        .args(["decode", "--buffer-size", "2"])
+        .write_stdin(format!("\u{feff}{}", chunked(BINARY, 94)))
+        .assert()
+        .success()
+        .stdout(BINARY);
+}
+
+/// The byte at `index` of the data [`stream_through_pipeline`] sends, so the data can be
+/// checked without holding it in memory.
+fn generated_byte(index: u64) -> u8 {
+    // SplitMix64 of the index of each 8-byte word.
+    let mut z = (index / 8)
+        .wrapping_add(1)
+        .wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    (z ^ (z >> 31)).to_le_bytes()[(index % 8) as usize]
+}
+
+/// Returns the peak resident memory of process `pid` so far, in bytes.
+#[cfg(target_os = "linux")]
+fn peak_memory(pid: u32) -> Option<u64> {
+    let status = fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kib = line.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn peak_memory(_pid: u32) -> Option<u64> {
+    None
+}
+
+/// Pipes `len` generated bytes through `base94cli encode | base94cli decode` and checks the
+/// output, and that neither process holds more than a few buffers in memory.
+fn stream_through_pipeline(len: u64) {
+    use std::io::{Read, Write};
+    use std::process::{Command, Stdio};
+
+    const BUFFER: usize = 64 * 1024;
+    const MAX_MEMORY: u64 = 32 * 1024 * 1024;
+
+    let bin = env!("CARGO_BIN_EXE_base94cli");
+    let mut encoder = Command::new(bin)
+        .arg("encode")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut decoder = Command::new(bin)
+        .arg("decode")
+        .stdin(encoder.stdout.take().unwrap())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut stdin = encoder.stdin.take().unwrap();
+    let writer = std::thread::spawn(move || {
+        let mut buf = vec![0; BUFFER];
+        let mut sent = 0;
+        while sent < len {
+            let n = (len - sent).min(BUFFER as u64) as usize;
+            for (i, byte) in buf[..n].iter_mut().enumerate() {
+                *byte = generated_byte(sent + i as u64);
+            }
+            stdin.write_all(&buf[..n]).unwrap();
+            sent += n as u64;
+        }
+    });
+
+    let mut stdout = decoder.stdout.take().unwrap();
+    let mut buf = vec![0; BUFFER];
+    let mut received = 0;
+    let mut peak = 0;
+    let mut next_sample = 0;
+    loop {
+        let n = stdout.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        for (i, &byte) in buf[..n].iter().enumerate() {
+            let index = received + i as u64;
+            assert_eq!(byte, generated_byte(index), "byte {}", index);
+        }
+        received += n as u64;
+        if received >= next_sample {
+            for pid in [encoder.id(), decoder.id()] {
+                peak = peak.max(peak_memory(pid).unwrap_or(0));
+            }
+            next_sample += 1024 * 1024;
+        }
+    }
+
+    writer.join().unwrap();
+    assert!(encoder.wait().unwrap().success());
+    assert!(decoder.wait().unwrap().success());
+    assert_eq!(received, len);
+    assert!(peak <= MAX_MEMORY, "peak memory {} bytes", peak);
+}
+
+#[test]
+fn test_streaming_pipeline() {
+    stream_through_pipeline(1024 * 1024);
+}
+
+/// A few hundred megabytes, far more than the memory each process may use. Run it with
+///
+/// ```sh
+/// cargo test --release --test cli -- --ignored
+/// ```
+#[test]
+#[ignore]
+fn test_streaming_large_input() {
+    stream_through_pipeline(256 * 1024 * 1024);
+}
+
+#[test]
+fn test_progress_without_terminal() {
+    // Standard error is a pipe here, so `--progress` must not draw anything, on either stream.
+    let data = (0..5000).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+    let input = temp_path("progress");
+    fs::write(&input, &data).unwrap();
+    let output = cli()
+        .args(["encode".as_ref(), "--progress".as_ref(), input.as_os_str()])
+        .output()
+        .unwrap();
+    fs::remove_file(&input).unwrap();
+    assert!(output.status.success());
+    assert_eq!(output.stdout, chunked(&data, 94).as_bytes());
+    assert!(output.stderr.is_empty());
+
+    cli()
+        .args(["decode", "--progress"])
+        .write_stdin(chunked(&data, 94))
+        .assert()
+        .success()
+        .stdout(data)
+        .stderr("");
+
+    cli()
+        .args(["encode", "--progress", "--quiet"])
+        .write_stdin("Hi")
+        .assert()
+        .failure();
+}
+
+/// Writes `data` and its streaming encoding to new files and returns their paths.
+fn encoded_file(name: &str, data: &[u8]) -> (PathBuf, PathBuf) {
+    let original = temp_path(&format!("{}.bin", name));
+    let encoded = temp_path(&format!("{}.b94", name));
+    fs::write(&original, data).unwrap();
+    fs::write(&encoded, chunked(data, 94)).unwrap();
+    (original, encoded)
+}
+
+#[test]
+fn test_verify() {
+    let data = (0..1000).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+    let (original, encoded) = encoded_file("verify", &data);
+
+    cli()
+        .arg("verify")
+        .arg(&encoded)
+        .assert()
+        .success()
+        .stdout("decoded 1000 bytes\n");
+
+    let output = cli()
+        .arg("verify")
+        .arg(&encoded)
+        .arg("--sha256")
+        .arg("--against")
+        .arg(&original)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let sha256 = Sha256::digest(&data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>();
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!(
+            "decoded 1000 bytes\nsha256 {}\nidentical to {}\n",
+            sha256,
+            original.display()
+        )
+    );
+
+    // Standard input works too, and there is nowhere to write the result.
+    cli()
+        .args(["verify", "-", "--base", "16"])
+        .write_stdin(chunked(BINARY, 16))
+        .assert()
+        .success()
+        .stdout(format!("decoded {} bytes\n", BINARY.len()));
+    cli()
+        .arg("verify")
+        .arg(&encoded)
+        .arg(temp_path("unused.bin"))
+        .assert()
+        .failure();
+    cli()
+        .args(["encode", "--sha256"])
+        .write_stdin(BINARY)
+        .assert()
+        .failure();
+
+    fs::remove_file(original).unwrap();
+    fs::remove_file(encoded).unwrap();
+}
+
+#[test]
+fn test_verify_corrupted_character() {
+    let mut text = chunked(&[0xab; 100], 94);
+    text.replace_range(90..91, " ");
+    let output = cli().arg("verify").write_stdin(text).output().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("' '"));
+}
+
+#[test]
+fn test_verify_against_mismatch() {
+    let data = (0..1000).map(|i| i as u8).collect::<Vec<u8>>();
+    let (original, encoded) = encoded_file("mismatch", &data);
+
+    let mut changed = data.clone();
+    changed[617] ^= 1;
+    fs::write(&original, &changed).unwrap();
+    let output = cli()
+        .arg("verify")
+        .arg(&encoded)
+        .arg("--against")
+        .arg(&original)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("at byte offset 617"), "{}", stderr);
+
+    // A reference that is shorter or longer differs where the shorter one ends.
+    for (len, message) in [
+        (999, "differs from {} at byte offset 999"),
+        (1001, "ends at byte offset 1000, before the end of {}"),
+    ] {
+        let mut resized = data.clone();
+        resized.resize(len, 0);
+        fs::write(&original, &resized).unwrap();
+        let output = cli()
+            .arg("verify")
+            .arg(&encoded)
+            .arg("--against")
+            .arg(&original)
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(1));
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        let message = message.replace("{}", &original.display().to_string());
+        assert!(stderr.contains(&message), "{}", stderr);
+    }
+
+    fs::remove_file(original).unwrap();
+    fs::remove_file(encoded).unwrap();
+}
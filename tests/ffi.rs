@@ -0,0 +1,54 @@
+//! Compiles `tests/ffi/main.c` against the `cdylib` and the checked-in header, then runs it.
+//!
+//! The C compiler is `$CC`, or `cc` if that is unset.
+
+#![cfg(all(feature = "ffi", target_os = "linux"))]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The directory that holds the `libbase94.so` built for this test run: the `deps`
+/// directory this test binary is in. The copy one level up is only refreshed by
+/// `cargo build` and may lack the `ffi` feature.
+fn library_dir() -> PathBuf {
+    let exe = std::env::current_exe().unwrap();
+    exe.parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn test_c_program() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let lib_dir = library_dir();
+    assert!(
+        lib_dir.join("libbase94.so").exists(),
+        "no cdylib in {}",
+        lib_dir.display()
+    );
+    let program = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ffi_main");
+
+    let compiler = std::env::var("CC").unwrap_or_else(|_| "cc".into());
+    let status = Command::new(compiler)
+        .args(["-std=c99", "-Wall", "-Wextra", "-Werror", "-I"])
+        .arg(root.join("include"))
+        .arg(root.join("tests/ffi/main.c"))
+        .arg("-o")
+        .arg(&program)
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg("-lbase94")
+        .status()
+        .expect("failed to run the C compiler");
+    assert!(status.success(), "compiling tests/ffi/main.c failed");
+
+    // Cargo's own `LD_LIBRARY_PATH` may list the stale copy first.
+    let output = Command::new(&program)
+        .env("LD_LIBRARY_PATH", &lib_dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(output.stdout, b"ok\n");
+}
@@ -0,0 +1,68 @@
+/* Exercises the C interface from C. Run by `tests/ffi.rs`; exits nonzero on failure. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "base94.h"
+
+#define CHECK(cond)                                                       \
+    do {                                                                  \
+        if (!(cond)) {                                                    \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,        \
+                    __LINE__, #cond);                                     \
+            return 1;                                                     \
+        }                                                                 \
+    } while (0)
+
+int main(void) {
+    const uint8_t data[] = "Hello, World!";
+    char *encoded = NULL;
+    size_t encoded_len = 0;
+    uint8_t *decoded = NULL;
+    size_t decoded_len = 0;
+
+    /* Round trip in every base. */
+    for (int base = 2; base <= 94; base++) {
+        CHECK(base94_encode(data, sizeof data, (uint8_t)base, &encoded, &encoded_len) ==
+              BASE94_OK);
+        CHECK(strlen(encoded) == encoded_len);
+        CHECK(base94_decode(encoded, encoded_len, (uint8_t)base, &decoded, &decoded_len) ==
+              BASE94_OK);
+        CHECK(decoded_len == sizeof data);
+        CHECK(memcmp(decoded, data, sizeof data) == 0);
+        base94_free((uint8_t *)encoded, encoded_len);
+        base94_free(decoded, decoded_len);
+    }
+    CHECK(base94_last_error_code() == BASE94_OK);
+    CHECK(base94_last_error_position() == BASE94_NO_POSITION);
+
+    CHECK(base94_encode(data, 5, 94, &encoded, &encoded_len) == BASE94_OK);
+    CHECK(strcmp(encoded, "G8GP4b") == 0);
+    base94_free((uint8_t *)encoded, encoded_len);
+
+    /* An invalid character reports its code and byte offset. */
+    CHECK(base94_decode("ab c", 4, 94, &decoded, &decoded_len) == 1);
+    CHECK(decoded == NULL && decoded_len == 0);
+    CHECK(base94_last_error_code() == 1);
+    CHECK(base94_last_error_position() == 2);
+
+    /* Invalid bases and null pointers are errors, not crashes. */
+    CHECK(base94_encode(data, sizeof data, 95, &encoded, &encoded_len) ==
+          BASE94_ERROR_INVALID_BASE);
+    CHECK(encoded == NULL);
+    CHECK(base94_decode("!!", 2, 1, &decoded, &decoded_len) == BASE94_ERROR_INVALID_BASE);
+    CHECK(base94_encode(NULL, 1, 94, &encoded, &encoded_len) == BASE94_ERROR_NULL_POINTER);
+    CHECK(base94_encode(data, sizeof data, 94, NULL, &encoded_len) ==
+          BASE94_ERROR_NULL_POINTER);
+    CHECK(base94_decode("!!", 2, 94, &decoded, NULL) == BASE94_ERROR_NULL_POINTER);
+    CHECK(base94_last_error_position() == BASE94_NO_POSITION);
+    base94_free(NULL, 0);
+
+    /* Empty input may be passed as a null pointer. */
+    CHECK(base94_encode(NULL, 0, 94, &encoded, &encoded_len) == BASE94_OK);
+    CHECK(encoded_len == 0 && encoded[0] == '\0');
+    base94_free((uint8_t *)encoded, encoded_len);
+
+    puts("ok");
+    return 0;
+}
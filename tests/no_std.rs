@@ -0,0 +1,57 @@
+//! Uses the crate from a `#![no_std]` crate that only has `alloc`, as firmware would.
+//!
+//! Run with `--no-default-features --features alloc` to check the library itself without
+//! `std`, or with `--no-default-features --features heapless` to check it without an
+//! allocator; building for an embedded target is done with
+//! `cargo build --lib --no-default-features --features alloc --target thumbv7em-none-eabihf`.
+
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_round_trip_without_std() {
+    let data = [0xAB, 0x00, 0x42, 0xFF, 0x00, 0x00];
+    for base in 2..=94 {
+        let encoded = base94::encode(&data, base);
+        assert_eq!(base94::decode(&encoded, base).unwrap(), data);
+
+        let mut out = String::new();
+        base94::encode_into(&data, base, &mut out);
+        assert_eq!(out, encoded);
+
+        let mut bytes = Vec::new();
+        base94::decode_into(&encoded, base, &mut bytes).unwrap();
+        assert_eq!(bytes, data);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_slice_apis_without_std() {
+    let data = *b"no_std";
+    let mut text = [0; base94::encoded_len(6, 94)];
+    let len = base94::encode_to_slice(&data, 94, &mut text).unwrap();
+
+    let mut bytes = [0; 6];
+    let encoded = core::str::from_utf8(&text[..len]).unwrap();
+    let n = base94::decode_to_slice(encoded, 94, &mut bytes).unwrap();
+    assert_eq!(bytes[..n], data);
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn test_heapless_without_allocator() {
+    let key = [0x5A; 16];
+    let encoded =
+        base94::encode_to_arraystring::<{ base94::encoded_len(16, 94) }>(&key, 94).unwrap();
+    let decoded = base94::decode_to_arrayvec::<16>(&encoded, 94).unwrap();
+    assert_eq!(decoded[..], key);
+}
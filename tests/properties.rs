@@ -0,0 +1,98 @@
+//! Property-based tests of the round trip and the character set.
+//!
+//! Each property runs 64 cases by default so `cargo test` stays fast. Set `PROPTEST_CASES`
+//! to run more, e.g. `PROPTEST_CASES=10000 cargo test --test properties`. The custom
+//! alphabet properties need the `proptest` feature.
+
+#![cfg(feature = "alloc")]
+
+use base94::{decode, encode, DecodeError, CHARACTERS};
+use proptest::prelude::*;
+
+/// Runs 64 cases unless `PROPTEST_CASES` asks for another number.
+fn config() -> ProptestConfig {
+    match std::env::var_os("PROPTEST_CASES") {
+        Some(_) => ProptestConfig::default(),
+        None => ProptestConfig::with_cases(64),
+    }
+}
+
+fn base() -> impl Strategy<Value = u8> {
+    2..=94u8
+}
+
+fn data() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 0..200)
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn round_trip(data in data(), base in base()) {
+        prop_assert_eq!(decode(&encode(&data, base), base).unwrap(), data);
+    }
+
+    #[test]
+    fn round_trip_with_zero_padding(
+        data in data(),
+        leading in 0..10usize,
+        trailing in 0..10usize,
+        base in base(),
+    ) {
+        let data = [vec![0; leading], data, vec![0; trailing]].concat();
+        let decoded = decode(&encode(&data, base), base).unwrap();
+        prop_assert_eq!(decoded.len(), data.len());
+        prop_assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn output_uses_base_prefix(data in data(), base in base()) {
+        let digits = &CHARACTERS[..base as usize];
+        prop_assert!(encode(&data, base).bytes().all(|c| digits.contains(&c)));
+    }
+
+    #[test]
+    fn rejects_characters_outside_prefix(
+        data in data(),
+        base in base(),
+        c in any::<u8>(),
+        position in any::<prop::sample::Index>(),
+    ) {
+        prop_assume!(!CHARACTERS[..base as usize].contains(&c));
+        let mut encoded = encode(&data, base).into_bytes();
+        let position = position.index(encoded.len() + 1);
+        encoded.insert(position, c);
+        let result = base94::decode_bytes(&encoded, base);
+        prop_assert!(
+            matches!(
+                result,
+                Err(DecodeError::InvalidCharacter { position: p, .. })
+                    | Err(DecodeError::DigitOutOfRange { position: p, .. })
+                    if p == position
+            ),
+            "{:?}",
+            result
+        );
+    }
+}
+
+#[cfg(feature = "proptest")]
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn round_trip_with_alphabet(data in data(), alphabet in any::<base94::Alphabet>()) {
+        let encoded = base94::encode_with_alphabet(&data, &alphabet);
+        prop_assert!(encoded.bytes().all(|c| alphabet.digit_of(c).is_some()));
+        prop_assert_eq!(base94::decode_with_alphabet(&encoded, &alphabet).unwrap(), data);
+    }
+
+    #[test]
+    fn alphabet_is_valid(alphabet in any::<base94::Alphabet>()) {
+        prop_assert!((2..=94).contains(&alphabet.len()));
+        for (digit, &c) in alphabet.chars().iter().enumerate() {
+            prop_assert_eq!(alphabet.digit_of(c), Some(digit as u8));
+        }
+    }
+}
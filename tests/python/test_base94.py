@@ -0,0 +1,83 @@
+"""Tests for the Python extension module, run with `pytest` after `maturin develop`."""
+
+import os
+import threading
+
+import pytest
+
+import base94
+
+MAX_BASE = 94
+
+
+@pytest.mark.parametrize("base", range(2, MAX_BASE + 1))
+def test_round_trip(base):
+    data = os.urandom(100)
+    encoded = base94.encode(data, base)
+    assert isinstance(encoded, str)
+    assert base94.decode(encoded, base) == data
+
+
+def test_default_base():
+    assert base94.encode(b"Hello") == "G8GP4b"
+    assert base94.decode("G8GP4b") == b"Hello"
+    assert base94.decode(base94.encode(b"Hello", base=16), base=16) == b"Hello"
+    assert base94.encode(b"") == ""
+    assert base94.decode("") == b""
+
+
+@pytest.mark.parametrize("base", [0, 1, 95, 255])
+def test_wrong_base(base):
+    with pytest.raises(ValueError, match="Invalid base") as error:
+        base94.encode(b"Hi", base)
+    assert error.value.code == 3
+    assert error.value.position is None
+    with pytest.raises(ValueError, match="Invalid base"):
+        base94.decode("!!", base)
+
+
+def test_base_out_of_u8_range():
+    with pytest.raises(OverflowError):
+        base94.encode(b"Hi", 256)
+
+
+def test_invalid_character():
+    with pytest.raises(ValueError, match="at position 3") as error:
+        base94.decode("Hel lo")
+    assert error.value.code == 1
+    assert error.value.position == 3
+
+    with pytest.raises(ValueError, match="out of range for base 10") as error:
+        base94.decode("!!~", 10)
+    assert error.value.code == 2
+    assert error.value.position == 2
+
+
+def test_multi_megabyte_payload():
+    # Plain encoding is quadratic in the length, so this takes seconds in a release build.
+    data = os.urandom(2 * 1024 * 1024)
+    encoded = base94.encode(data)
+    assert len(encoded) > base94.DETACH_LEN
+    assert base94.decode(encoded) == data
+
+
+def test_large_conversions_release_the_gil():
+    data = os.urandom(512 * 1024)
+    ticks = []
+    stop = threading.Event()
+
+    def tick():
+        while not stop.is_set():
+            ticks.append(None)
+            stop.wait(0.001)
+
+    thread = threading.Thread(target=tick)
+    thread.start()
+    try:
+        before = len(ticks)
+        base94.decode(base94.encode(data))
+        during = len(ticks) - before
+    finally:
+        stop.set()
+        thread.join()
+    assert during > 0
@@ -0,0 +1,147 @@
+//! Round trips of very large inputs. The tests are ignored by default; run them with
+//!
+//! ```sh
+//! cargo test --release --test stress -- --ignored
+//! ```
+//!
+//! A counting allocator tracks the peak heap use, so the streaming test can check that it
+//! keeps only a few blocks in memory no matter how large the input is.
+
+#![cfg(feature = "alloc")]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use base94::{decode, decode_chunked, encode, encode_chunked, Decoder, Encoder};
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+/// The system allocator, counting the bytes that are live and the most that were.
+struct CountingAllocator;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let live = LIVE.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(live, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Keeps the tests from running at the same time, which would mix up their peaks.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+const MB: usize = 1 << 20;
+
+/// An incremental FNV-1a hash, so the data can be compared without keeping a copy.
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+/// Streams 100 MB of seeded pseudo-random data through `Encoder` and `Decoder` in uneven
+/// pieces, and checks that the output hashes like the input and that the heap never holds
+/// more than a few pieces.
+#[test]
+#[ignore = "100 MB; run with --ignored"]
+fn test_stream_100_mb() {
+    let _serial = SERIAL.lock().unwrap();
+    const PIECE: usize = 64 * 1024;
+
+    let mut rng = StdRng::seed_from_u64(94);
+    let mut encoder = Encoder::new(94);
+    let mut decoder = Decoder::new(94);
+    let (mut input_hash, mut output_hash) = (Fnv::new(), Fnv::new());
+    let mut piece = vec![0; PIECE];
+    let mut decoded_len = 0;
+
+    let baseline = LIVE.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    let mut remaining = 100 * MB;
+    while remaining > 0 {
+        let len = rng.gen_range(1..=PIECE).min(remaining);
+        remaining -= len;
+        rng.fill_bytes(&mut piece[..len]);
+        input_hash.update(&piece[..len]);
+
+        let encoded = encoder.push(&piece[..len]);
+        let (first, second) = encoded.split_at(encoded.len() / 3);
+        for text in [first, second] {
+            let decoded = decoder.push(text).unwrap();
+            output_hash.update(&decoded);
+            decoded_len += decoded.len();
+        }
+    }
+    let decoded = decoder.push(&encoder.finalize()).unwrap();
+    output_hash.update(&decoded);
+    decoded_len += decoded.len();
+    let decoded = decoder.finalize().unwrap();
+    output_hash.update(&decoded);
+    decoded_len += decoded.len();
+
+    let peak = PEAK.load(Ordering::Relaxed) - baseline;
+    assert_eq!(decoded_len, 100 * MB);
+    assert_eq!(output_hash.0, input_hash.0);
+    assert!(peak < 4 * MB, "peak of {} bytes", peak);
+}
+
+/// Round-trips 100 MB through the one-shot chunked functions, which hold the whole input
+/// and output.
+#[test]
+#[ignore = "100 MB; run with --ignored"]
+fn test_chunked_100_mb() {
+    let _serial = SERIAL.lock().unwrap();
+
+    let mut data = vec![0; 100 * MB];
+    StdRng::seed_from_u64(94).fill_bytes(&mut data);
+    let mut hash = Fnv::new();
+    hash.update(&data);
+
+    let encoded = encode_chunked(&data, 94, 32);
+    drop(data);
+    let decoded = decode_chunked(&encoded, 94, 32).unwrap();
+    assert_eq!(decoded.len(), 100 * MB);
+    let mut decoded_hash = Fnv::new();
+    decoded_hash.update(&decoded);
+    assert_eq!(decoded_hash.0, hash.0);
+}
+
+/// Every zero byte is one zero digit, so 10 MB of zeros is 10 MB of `!`, even though it
+/// is the number 0.
+#[test]
+#[ignore = "10 MB; run with --ignored"]
+fn test_10_mb_of_zeros() {
+    let _serial = SERIAL.lock().unwrap();
+
+    let zeros = vec![0; 10 * MB];
+    let encoded = encode(&zeros, 94);
+    assert_eq!(encoded.len(), zeros.len());
+    assert!(encoded.bytes().all(|c| c == b'!'));
+    assert_eq!(decode(&encoded, 94).unwrap(), zeros);
+
+    let encoded = encode_chunked(&zeros, 94, 32);
+    assert_eq!(decode_chunked(&encoded, 94, 32).unwrap(), zeros);
+}
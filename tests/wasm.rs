@@ -0,0 +1,53 @@
+//! Tests for the JavaScript bindings, run in Node.js by `wasm-bindgen-test-runner`. See the
+//! WebAssembly section of the README for the command.
+
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use base94::wasm::{decode, encode};
+use js_sys::{Error, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const MAX_BASE: u8 = 94;
+
+/// Returns the property `name` of the thrown `error`.
+fn property(error: &JsValue, name: &str) -> JsValue {
+    Reflect::get(error, &name.into()).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_round_trip() {
+    for base in 2..=MAX_BASE {
+        let data = (0..=255).collect::<Vec<u8>>();
+        let encoded = encode(&data, base).unwrap();
+        assert_eq!(encoded, base94::encode(&data, base));
+        assert_eq!(decode(&encoded, base).unwrap(), data);
+    }
+    assert_eq!(encode(b"Hello", 94).unwrap(), "G8GP4b");
+    assert_eq!(decode("", 94).unwrap(), b"");
+}
+
+#[wasm_bindgen_test]
+fn test_decode_error_properties() {
+    let error = decode("Hel lo", 94).unwrap_err();
+    assert!(error.is_instance_of::<Error>());
+    assert_eq!(
+        String::from(error.unchecked_ref::<Error>().message()),
+        "Invalid character ' ' at position 3"
+    );
+    assert_eq!(property(&error, "code"), 1);
+    assert_eq!(property(&error, "position"), 3);
+
+    let error = decode("!!~", 10).unwrap_err();
+    assert_eq!(property(&error, "code"), 2);
+    assert_eq!(property(&error, "position"), 2);
+}
+
+#[wasm_bindgen_test]
+fn test_invalid_base() {
+    for error in [encode(b"Hi", 95).unwrap_err(), decode("!!", 1).unwrap_err()] {
+        assert!(error.is_instance_of::<Error>());
+        assert_eq!(property(&error, "code"), 3);
+        assert!(property(&error, "position").is_undefined());
+    }
+}